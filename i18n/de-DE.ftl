@@ -0,0 +1,212 @@
+# Character builder
+clear = Leeren
+undo = Rückgängig
+redo = Wiederholen
+save = Speichern
+search-champions = Champions suchen
+search-items = Gegenstände suchen
+filter-by-trait = Nach Merkmal filtern
+then-by = Danach nach
+has-bis-saved = Mit gespeichertem BIS
+no-champion-selected = Kein Champion ausgewählt
+
+sort-alphabetical = Alphabetisch
+sort-by-cost = Nach Kosten
+sort-by-trait = Nach Merkmal
+sort-key-name = Name
+sort-key-cost = Kosten
+sort-key-health = Leben
+sort-key-range = Reichweite
+
+item-kind-component = Komponente
+item-kind-completed = Fertig
+item-kind-emblem = Emblem
+
+# Champion detail
+champion-cost = Kosten: { $cost }
+stat-health = Leben
+stat-attack-damage = Angriffsschaden
+stat-attack-speed = Angriffstempo
+stat-crit-chance = Kritische Trefferchance
+stat-armor = Rüstung
+stat-magic-resist = Magieresistenz
+champion-mana = Mana: { $initial }/{ $max }
+champion-range = Reichweite: { $range }
+champion-bis = BIS: { $items }
+
+# Item detail
+item-unique = Einzigartig
+item-recipe = Rezept:
+item-grants = Gewährt: { $traits }
+item-used-by = Benutzt von: { $champions }
+item-unused = In keiner gespeicherten BIS-Liste
+
+# Navigation
+go-to-character-builder = Zum Champion-Planer
+go-to-item-determiner = Zur Gegenstandsauswahl
+go-to-comps = Zu den Comps
+go-to-game-log = Zum Spielprotokoll
+go-to-analytics = Zur Auswertung
+go-to-augments = Zu den Augments
+go-to-tier-lists = Zu den Tier-Listen
+go-to-patch-changes = Zu den Patch-Änderungen
+go-to-settings = Zu den Einstellungen
+
+# Languages
+data-language = Sprache der Spieldaten
+ui-language = Sprache der Oberfläche
+same-as-data = Wie Spieldaten
+loading-locale = { $locale } wird geladen...
+
+# Settings
+settings = Einstellungen
+settings-theme = Design
+theme-light = Hell
+theme-dark = Dunkel
+settings-ui-scale = UI-Skalierung ({ $min } bis { $max })
+settings-set = Set
+settings-default-set = Standard-Set
+settings-data-source = Datenquelle
+settings-cache-size = Größe des Symbol-Caches in MB (0 für unbegrenzt)
+settings-default-sort = Standardsortierung
+settings-revert = Zurücksetzen
+settings-saved = Gespeichert
+settings-cache-cleanup-failed = Der Symbol-Cache konnte nicht bereinigt werden: { $error }
+problem-scale-not-number = Die Skalierung muss eine Zahl sein
+problem-cache-size-not-number = Die Cache-Größe muss eine ganze Zahl an Megabytes sein
+problem-scale-range = Die Skalierung muss zwischen { $min } und { $max } liegen
+problem-unknown-locale = Unbekanntes Gebietsschema { $locale }
+problem-unknown-ui-language = Keine Oberflächensprache { $language }
+problem-data-url-scheme = Die Daten-URL muss mit http:// oder https:// beginnen
+problem-blank-set = Das Set darf nicht leer sein, lass das Feld stattdessen frei
+
+# Shared
+delete = Löschen
+confirm-delete = Löschen bestätigen
+notes = Notizen
+exported-to = Exportiert nach { $path }
+cost-group = { $cost } Kosten
+filters-save-failed = Die Filter konnten nicht gespeichert werden: { $error }
+
+# Notebooks
+notebook-name = Name des Notizbuchs
+notebook-new = Neu
+notebook-rename = Umbenennen
+notebook-duplicate = Duplizieren
+notebook-unreadable = „{ $name }“ konnte nicht gelesen werden und wird daher nicht gespeichert
+notebook-imported = { $bis } BIS-Listen und { $comps } Comps importiert
+toml-file = TOML-Datei
+export-toml = TOML exportieren
+import-toml = TOML importieren
+
+# Comps
+comp-name = Name der Comp
+comp-new = Neue Comp
+comp-paste-code = Code einfügen
+comp-champion-to-place = Zu platzierender Champion
+comp-select-unit = Wähle eine Einheit auf dem Feld
+comp-notes = Notizen zur Comp
+comp-copy-planner-code = Planer-Code kopieren
+comp-copy-share-code = Teilen-Code kopieren
+comp-delete = Comp löschen
+comp-none-selected = Keine Comp ausgewählt
+comp-use-bis-items = BIS-Gegenstände verwenden
+comp-remove-unit = Entfernen
+comp-planner-code-copied = Planer-Code kopiert
+comp-share-code-copied = Teilen-Code kopiert
+comp-imported = { $name } importiert
+
+# Patch changes
+changelog-previous-snapshot = Vorheriger Schnappschuss
+changelog-compare = Vergleichen
+changelog-prompt = Vergleiche einen Schnappschuss, um die Änderungen zu sehen
+changelog-no-changes = Keine Änderungen
+changelog-added = Neu: { $name }
+changelog-removed = Entfernt: { $name }
+changelog-change = { $name } { $field }: { $old } -> { $new } ({ $direction })
+category-champions = Champions
+category-traits = Merkmale
+category-items = Gegenstände
+direction-buff = Buff
+direction-nerf = Nerf
+direction-adjusted = angepasst
+
+# Game log
+game-log-date = Datum
+game-log-patch = Patch
+game-log-final-comp = Finale Comp
+game-log-augments = Augments, durch Kommas getrennt
+game-log-log = Spiel eintragen
+game-log-csv-file = CSV-Datei
+game-log-export-csv = CSV exportieren
+game-log-bad-date = Daten sehen so aus: 2023-06-30
+game-log-pick-placement = Wähle eine Platzierung
+game-log-logged = Spiel eingetragen
+game-log-unreadable = Das Spielprotokoll konnte nicht gelesen werden, neue Spiele werden trotzdem angehängt: { $error }
+game-log-not-rewritten = Spiele können nicht gelöscht werden, solange das Spielprotokoll nicht lesbar ist
+
+# Analytics
+analytics-from = Von JJJJ-MM-TT
+analytics-to = Bis JJJJ-MM-TT
+analytics-summary = { $games } Spiele | Ø { $average } | Top 4 { $top-four } % | Siege { $wins } %
+analytics-by-comp = Durchschnittliche Platzierung nach Comp
+analytics-by-carry = Durchschnittliche Platzierung nach Carry
+analytics-top-four-over-time = Top-4-Quote der letzten { $games } Spiele
+analytics-top-four-by-item = Top-4-Quote nach Gegenstand (%)
+analytics-by-patch = Durchschnittliche Platzierung nach Patch
+
+# Item audit
+audit-removed = { $item } wurde entfernt
+audit-recipe-changed = Rezept von { $item } geändert von { $old } zu { $new }
+audit-effect-changed = { $item }: { $effect } geändert von { $old } zu { $new }
+audit-use = { $item } verwenden
+audit-keep = Behalten
+
+# Calculator
+calc-title = Rechner (BIS-Gegenstände und Merkmalsboni)
+calc-trait-bonus = Merkmal: { $stat }
+stat-ability-power = Fähigkeitsstärke
+calc-stats = AD { $ad } | AP { $ap } | AS { $as } | Krit { $crit } %
+calc-attack-dps = Angriffs-DPS: { $dps }
+calc-ability-damage = { $ability } pro Wirken: { $damage }
+calc-effective-hp = Effektive LP: { $physical } physisch, { $magic } magisch
+
+# Live game
+live-read-game = Laufendes Spiel auslesen
+live-client-url = Live-Client-URL
+live-disconnected = Getrennt: { $reason }
+live-level = Stufe { $level }
+live-gold = Gold { $gold }
+live-interest = Zinsen +{ $interest }
+live-next-interest = Noch { $gold } Gold bis zu den nächsten Zinsen
+live-max-interest = Maximale Zinsen
+
+# Match stats
+match-folder = Ordner der Match-Dateien
+match-import = Matches importieren
+match-imported = { $added } neue von { $total } Matches importiert
+match-stats-save-failed = Die Match-Statistiken konnten nicht gespeichert werden: { $error }
+match-no-stats = Keine Match-Statistiken
+match-stats-title = Match-Statistiken ({ $games } Spiele)
+match-item-frequency = { $item } - { $percent } %
+match-combo = Ø { $average } | Top 4 { $top-four } % | { $games } Spiele
+match-use-as-bis = Als BIS verwenden
+
+# Tier lists
+tier-list-default-name = Tierliste { $number }
+tier-list-name = Name der Tierliste
+tier-new-list = Neu
+tier-rename-list = Umbenennen
+tier-rename-row = Umbenennen
+tier-row-label = Zeilenname
+tier-add-row = Zeile hinzufügen
+tier-unranked = Nicht eingestuft
+tier-moving = { $champion } wird verschoben
+tier-no-lists = Noch keine Tierlisten
+tier-export-file = Exportdatei
+tier-export-text = Text exportieren
+tier-export-png = PNG exportieren
+
+# Augments
+search-augments = Augments suchen
+augment-tier-list = Tierliste von { $notebook }
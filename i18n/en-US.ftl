@@ -0,0 +1,212 @@
+# Character builder
+clear = Clear
+undo = Undo
+redo = Redo
+save = Save
+search-champions = Search champions
+search-items = Search items
+filter-by-trait = Filter by trait
+then-by = Then by
+has-bis-saved = Has BIS saved
+no-champion-selected = No champion selected
+
+sort-alphabetical = Alphabetical
+sort-by-cost = By Cost
+sort-by-trait = By Trait
+sort-key-name = Name
+sort-key-cost = Cost
+sort-key-health = Health
+sort-key-range = Range
+
+item-kind-component = Component
+item-kind-completed = Completed
+item-kind-emblem = Emblem
+
+# Champion detail
+champion-cost = Cost: { $cost }
+stat-health = Health
+stat-attack-damage = Attack Damage
+stat-attack-speed = Attack Speed
+stat-crit-chance = Crit Chance
+stat-armor = Armor
+stat-magic-resist = Magic Resist
+champion-mana = Mana: { $initial }/{ $max }
+champion-range = Range: { $range }
+champion-bis = BIS: { $items }
+
+# Item detail
+item-unique = Unique
+item-recipe = Recipe:
+item-grants = Grants: { $traits }
+item-used-by = Used by: { $champions }
+item-unused = Not in any saved BIS list
+
+# Navigation
+go-to-character-builder = Go to Character Builder
+go-to-item-determiner = Go to Item Determiner
+go-to-comps = Go to Comps
+go-to-game-log = Go to Game Log
+go-to-analytics = Go to Analytics
+go-to-augments = Go to Augments
+go-to-tier-lists = Go to Tier Lists
+go-to-patch-changes = Go to Patch Changes
+go-to-settings = Go to Settings
+
+# Languages
+data-language = Data language
+ui-language = Interface language
+same-as-data = Same as data
+loading-locale = Loading { $locale }...
+
+# Settings
+settings = Settings
+settings-theme = Theme
+theme-light = Light
+theme-dark = Dark
+settings-ui-scale = UI scale ({ $min } to { $max })
+settings-set = Set
+settings-default-set = Default set
+settings-data-source = Data source
+settings-cache-size = Icon cache size in MB (0 for no limit)
+settings-default-sort = Default sort
+settings-revert = Revert
+settings-saved = Saved
+settings-cache-cleanup-failed = Could not clean up the icon cache: { $error }
+problem-scale-not-number = Scale must be a number
+problem-cache-size-not-number = Cache size must be a whole number of megabytes
+problem-scale-range = Scale must be between { $min } and { $max }
+problem-unknown-locale = Unknown locale { $locale }
+problem-unknown-ui-language = No interface language { $language }
+problem-data-url-scheme = Data URL must start with http:// or https://
+problem-blank-set = Set can't be blank, leave it empty instead
+
+# Shared
+delete = Delete
+confirm-delete = Confirm delete
+notes = Notes
+exported-to = Exported to { $path }
+cost-group = { $cost } Cost
+filters-save-failed = Could not save the filters: { $error }
+
+# Notebooks
+notebook-name = Notebook name
+notebook-new = New
+notebook-rename = Rename
+notebook-duplicate = Duplicate
+notebook-unreadable = "{ $name }" couldn't be read, so it isn't saved
+notebook-imported = Imported { $bis } BIS lists and { $comps } comps
+toml-file = TOML file
+export-toml = Export TOML
+import-toml = Import TOML
+
+# Comps
+comp-name = Comp name
+comp-new = New comp
+comp-paste-code = Paste code
+comp-champion-to-place = Champion to place
+comp-select-unit = Select a unit on the board
+comp-notes = Comp notes
+comp-copy-planner-code = Copy planner code
+comp-copy-share-code = Copy share code
+comp-delete = Delete comp
+comp-none-selected = No comp selected
+comp-use-bis-items = Use BIS items
+comp-remove-unit = Remove
+comp-planner-code-copied = Copied planner code
+comp-share-code-copied = Copied share code
+comp-imported = Imported { $name }
+
+# Patch changes
+changelog-previous-snapshot = Previous snapshot
+changelog-compare = Compare
+changelog-prompt = Compare a snapshot to see what changed
+changelog-no-changes = No changes
+changelog-added = New: { $name }
+changelog-removed = Removed: { $name }
+changelog-change = { $name } { $field }: { $old } -> { $new } ({ $direction })
+category-champions = Champions
+category-traits = Traits
+category-items = Items
+direction-buff = buff
+direction-nerf = nerf
+direction-adjusted = adjusted
+
+# Game log
+game-log-date = Date
+game-log-patch = Patch
+game-log-final-comp = Final comp
+game-log-augments = Augments, comma separated
+game-log-log = Log game
+game-log-csv-file = CSV file
+game-log-export-csv = Export CSV
+game-log-bad-date = Dates look like 2023-06-30
+game-log-pick-placement = Pick a placement
+game-log-logged = Game logged
+game-log-unreadable = The game log couldn't be read, new games are still added to it: { $error }
+game-log-not-rewritten = Games can't be deleted while the game log can't be read
+
+# Analytics
+analytics-from = From YYYY-MM-DD
+analytics-to = To YYYY-MM-DD
+analytics-summary = { $games } games | avg { $average } | top 4 { $top-four }% | wins { $wins }%
+analytics-by-comp = Average placement by comp
+analytics-by-carry = Average placement by carry
+analytics-top-four-over-time = Top 4 rate over the last { $games } games
+analytics-top-four-by-item = Top 4 rate by item (%)
+analytics-by-patch = Average placement by patch
+
+# Item audit
+audit-removed = { $item } was removed
+audit-recipe-changed = { $item } recipe changed from { $old } to { $new }
+audit-effect-changed = { $item } { $effect } changed from { $old } to { $new }
+audit-use = Use { $item }
+audit-keep = Keep
+
+# Calculator
+calc-title = Calculator (BIS items and trait bonuses)
+calc-trait-bonus = Trait { $stat }
+stat-ability-power = Ability Power
+calc-stats = AD { $ad } | AP { $ap } | AS { $as } | Crit { $crit }%
+calc-attack-dps = Attack DPS: { $dps }
+calc-ability-damage = { $ability } per cast: { $damage }
+calc-effective-hp = Effective HP: { $physical } physical, { $magic } magic
+
+# Live game
+live-read-game = Read the live game
+live-client-url = Live Client URL
+live-disconnected = Disconnected: { $reason }
+live-level = Level { $level }
+live-gold = Gold { $gold }
+live-interest = Interest +{ $interest }
+live-next-interest = { $gold } gold to the next interest
+live-max-interest = Max interest
+
+# Match stats
+match-folder = Match files folder
+match-import = Import matches
+match-imported = Imported { $added } new of { $total } matches
+match-stats-save-failed = Could not save the match stats: { $error }
+match-no-stats = No match stats
+match-stats-title = Match stats ({ $games } games)
+match-item-frequency = { $item } - { $percent }%
+match-combo = avg { $average } | top 4 { $top-four }% | { $games } games
+match-use-as-bis = Use as BIS
+
+# Tier lists
+tier-list-default-name = Tier list { $number }
+tier-list-name = Tier list name
+tier-new-list = New
+tier-rename-list = Rename
+tier-rename-row = Rename
+tier-row-label = Row label
+tier-add-row = Add row
+tier-unranked = Unranked
+tier-moving = Moving { $champion }
+tier-no-lists = No tier lists yet
+tier-export-file = Export file
+tier-export-text = Export text
+tier-export-png = Export PNG
+
+# Augments
+search-augments = Search augments
+augment-tier-list = Tier list of { $notebook }
@@ -0,0 +1,212 @@
+# Character builder
+clear = Vaciar
+undo = Deshacer
+redo = Rehacer
+save = Guardar
+search-champions = Buscar campeones
+search-items = Buscar objetos
+filter-by-trait = Filtrar por rasgo
+then-by = Después por
+has-bis-saved = Con BIS guardado
+no-champion-selected = Ningún campeón seleccionado
+
+sort-alphabetical = Alfabético
+sort-by-cost = Por coste
+sort-by-trait = Por rasgo
+sort-key-name = Nombre
+sort-key-cost = Coste
+sort-key-health = Vida
+sort-key-range = Alcance
+
+item-kind-component = Componente
+item-kind-completed = Completo
+item-kind-emblem = Emblema
+
+# Champion detail
+champion-cost = Coste: { $cost }
+stat-health = Vida
+stat-attack-damage = Daño de ataque
+stat-attack-speed = Velocidad de ataque
+stat-crit-chance = Probabilidad de crítico
+stat-armor = Armadura
+stat-magic-resist = Resistencia mágica
+champion-mana = Maná: { $initial }/{ $max }
+champion-range = Alcance: { $range }
+champion-bis = BIS: { $items }
+
+# Item detail
+item-unique = Único
+item-recipe = Receta:
+item-grants = Otorga: { $traits }
+item-used-by = Usado por: { $champions }
+item-unused = No está en ninguna lista BIS guardada
+
+# Navigation
+go-to-character-builder = Ir al planificador de campeones
+go-to-item-determiner = Ir al selector de objetos
+go-to-comps = Ir a composiciones
+go-to-game-log = Ir al registro de partidas
+go-to-analytics = Ir a estadísticas
+go-to-augments = Ir a aumentos
+go-to-tier-lists = Ir a listas de niveles
+go-to-patch-changes = Ir a cambios del parche
+go-to-settings = Ir a ajustes
+
+# Languages
+data-language = Idioma de los datos
+ui-language = Idioma de la interfaz
+same-as-data = Igual que los datos
+loading-locale = Cargando { $locale }...
+
+# Settings
+settings = Ajustes
+settings-theme = Tema
+theme-light = Claro
+theme-dark = Oscuro
+settings-ui-scale = Escala de la interfaz ({ $min } a { $max })
+settings-set = Set
+settings-default-set = Set predeterminado
+settings-data-source = Fuente de datos
+settings-cache-size = Tamaño de la caché de iconos en MB (0 sin límite)
+settings-default-sort = Orden predeterminado
+settings-revert = Revertir
+settings-saved = Guardado
+settings-cache-cleanup-failed = No se pudo limpiar la caché de iconos: { $error }
+problem-scale-not-number = La escala debe ser un número
+problem-cache-size-not-number = El tamaño de la caché debe ser un número entero de megabytes
+problem-scale-range = La escala debe estar entre { $min } y { $max }
+problem-unknown-locale = Configuración regional desconocida { $locale }
+problem-unknown-ui-language = No hay idioma de interfaz { $language }
+problem-data-url-scheme = La URL de datos debe empezar por http:// o https://
+problem-blank-set = El set no puede estar en blanco, déjalo vacío
+
+# Shared
+delete = Eliminar
+confirm-delete = Confirmar eliminación
+notes = Notas
+exported-to = Exportado a { $path }
+cost-group = Coste { $cost }
+filters-save-failed = No se pudieron guardar los filtros: { $error }
+
+# Notebooks
+notebook-name = Nombre del cuaderno
+notebook-new = Nuevo
+notebook-rename = Renombrar
+notebook-duplicate = Duplicar
+notebook-unreadable = No se pudo leer "{ $name }", así que no se guarda
+notebook-imported = Importadas { $bis } listas BIS y { $comps } composiciones
+toml-file = Archivo TOML
+export-toml = Exportar TOML
+import-toml = Importar TOML
+
+# Comps
+comp-name = Nombre de la composición
+comp-new = Nueva composición
+comp-paste-code = Pegar código
+comp-champion-to-place = Campeón que colocar
+comp-select-unit = Selecciona una unidad del tablero
+comp-notes = Notas de la composición
+comp-copy-planner-code = Copiar código del planificador
+comp-copy-share-code = Copiar código para compartir
+comp-delete = Eliminar composición
+comp-none-selected = Ninguna composición seleccionada
+comp-use-bis-items = Usar objetos BIS
+comp-remove-unit = Quitar
+comp-planner-code-copied = Código del planificador copiado
+comp-share-code-copied = Código para compartir copiado
+comp-imported = { $name } importada
+
+# Patch changes
+changelog-previous-snapshot = Instantánea anterior
+changelog-compare = Comparar
+changelog-prompt = Compara una instantánea para ver qué cambió
+changelog-no-changes = Sin cambios
+changelog-added = Nuevo: { $name }
+changelog-removed = Eliminado: { $name }
+changelog-change = { $name } { $field }: { $old } -> { $new } ({ $direction })
+category-champions = Campeones
+category-traits = Rasgos
+category-items = Objetos
+direction-buff = mejora
+direction-nerf = debilitación
+direction-adjusted = ajuste
+
+# Game log
+game-log-date = Fecha
+game-log-patch = Parche
+game-log-final-comp = Composición final
+game-log-augments = Aumentos, separados por comas
+game-log-log = Registrar partida
+game-log-csv-file = Archivo CSV
+game-log-export-csv = Exportar CSV
+game-log-bad-date = Las fechas tienen el formato 2023-06-30
+game-log-pick-placement = Elige una posición
+game-log-logged = Partida registrada
+game-log-unreadable = No se pudo leer el registro de partidas, las partidas nuevas se siguen añadiendo: { $error }
+game-log-not-rewritten = No se pueden borrar partidas mientras el registro no se pueda leer
+
+# Analytics
+analytics-from = Desde AAAA-MM-DD
+analytics-to = Hasta AAAA-MM-DD
+analytics-summary = { $games } partidas | media { $average } | top 4 { $top-four } % | victorias { $wins } %
+analytics-by-comp = Posición media por composición
+analytics-by-carry = Posición media por carry
+analytics-top-four-over-time = Tasa de top 4 en las últimas { $games } partidas
+analytics-top-four-by-item = Tasa de top 4 por objeto (%)
+analytics-by-patch = Posición media por parche
+
+# Item audit
+audit-removed = { $item } se eliminó
+audit-recipe-changed = La receta de { $item } cambió de { $old } a { $new }
+audit-effect-changed = { $item }: { $effect } cambió de { $old } a { $new }
+audit-use = Usar { $item }
+audit-keep = Mantener
+
+# Calculator
+calc-title = Calculadora (objetos BIS y bonificaciones de rasgos)
+calc-trait-bonus = Rasgo: { $stat }
+stat-ability-power = Poder de habilidad
+calc-stats = AD { $ad } | AP { $ap } | VA { $as } | Crítico { $crit } %
+calc-attack-dps = DPS de ataque: { $dps }
+calc-ability-damage = { $ability } por lanzamiento: { $damage }
+calc-effective-hp = Vida efectiva: { $physical } física, { $magic } mágica
+
+# Live game
+live-read-game = Leer la partida en curso
+live-client-url = URL del Live Client
+live-disconnected = Desconectado: { $reason }
+live-level = Nivel { $level }
+live-gold = Oro { $gold }
+live-interest = Interés +{ $interest }
+live-next-interest = { $gold } de oro para el siguiente interés
+live-max-interest = Interés máximo
+
+# Match stats
+match-folder = Carpeta de archivos de partidas
+match-import = Importar partidas
+match-imported = Importadas { $added } nuevas de { $total } partidas
+match-stats-save-failed = No se pudieron guardar las estadísticas de partidas: { $error }
+match-no-stats = Sin estadísticas de partidas
+match-stats-title = Estadísticas de partidas ({ $games } partidas)
+match-item-frequency = { $item } - { $percent } %
+match-combo = media { $average } | top 4 { $top-four } % | { $games } partidas
+match-use-as-bis = Usar como BIS
+
+# Tier lists
+tier-list-default-name = Tier list { $number }
+tier-list-name = Nombre de la tier list
+tier-new-list = Nueva
+tier-rename-list = Renombrar
+tier-rename-row = Renombrar
+tier-row-label = Etiqueta de fila
+tier-add-row = Añadir fila
+tier-unranked = Sin clasificar
+tier-moving = Moviendo a { $champion }
+tier-no-lists = Aún no hay tier lists
+tier-export-file = Archivo de exportación
+tier-export-text = Exportar texto
+tier-export-png = Exportar PNG
+
+# Augments
+search-augments = Buscar aumentos
+augment-tier-list = Tier list de { $notebook }
@@ -0,0 +1,212 @@
+# Character builder
+clear = Vider
+undo = Annuler
+redo = Rétablir
+save = Enregistrer
+search-champions = Rechercher des champions
+search-items = Rechercher des objets
+filter-by-trait = Filtrer par trait
+then-by = Puis par
+has-bis-saved = Avec BIS enregistré
+no-champion-selected = Aucun champion sélectionné
+
+sort-alphabetical = Alphabétique
+sort-by-cost = Par coût
+sort-by-trait = Par trait
+sort-key-name = Nom
+sort-key-cost = Coût
+sort-key-health = PV
+sort-key-range = Portée
+
+item-kind-component = Composant
+item-kind-completed = Complet
+item-kind-emblem = Emblème
+
+# Champion detail
+champion-cost = Coût : { $cost }
+stat-health = PV
+stat-attack-damage = Dégâts d'attaque
+stat-attack-speed = Vitesse d'attaque
+stat-crit-chance = Chances de coup critique
+stat-armor = Armure
+stat-magic-resist = Résistance magique
+champion-mana = Mana : { $initial }/{ $max }
+champion-range = Portée : { $range }
+champion-bis = BIS : { $items }
+
+# Item detail
+item-unique = Unique
+item-recipe = Recette :
+item-grants = Confère : { $traits }
+item-used-by = Utilisé par : { $champions }
+item-unused = Dans aucune liste BIS enregistrée
+
+# Navigation
+go-to-character-builder = Aller au planificateur de champions
+go-to-item-determiner = Aller au choix d'objets
+go-to-comps = Aller aux compos
+go-to-game-log = Aller au journal des parties
+go-to-analytics = Aller aux statistiques
+go-to-augments = Aller aux augments
+go-to-tier-lists = Aller aux tier lists
+go-to-patch-changes = Aller aux changements du patch
+go-to-settings = Aller aux paramètres
+
+# Languages
+data-language = Langue des données
+ui-language = Langue de l'interface
+same-as-data = Comme les données
+loading-locale = Chargement de { $locale }...
+
+# Settings
+settings = Paramètres
+settings-theme = Thème
+theme-light = Clair
+theme-dark = Sombre
+settings-ui-scale = Échelle de l'interface ({ $min } à { $max })
+settings-set = Set
+settings-default-set = Set par défaut
+settings-data-source = Source des données
+settings-cache-size = Taille du cache d'icônes en Mo (0 pour aucune limite)
+settings-default-sort = Tri par défaut
+settings-revert = Annuler les modifications
+settings-saved = Enregistré
+settings-cache-cleanup-failed = Impossible de nettoyer le cache d'icônes : { $error }
+problem-scale-not-number = L'échelle doit être un nombre
+problem-cache-size-not-number = La taille du cache doit être un nombre entier de mégaoctets
+problem-scale-range = L'échelle doit être comprise entre { $min } et { $max }
+problem-unknown-locale = Langue des données inconnue { $locale }
+problem-unknown-ui-language = Aucune langue d'interface { $language }
+problem-data-url-scheme = L'URL des données doit commencer par http:// ou https://
+problem-blank-set = Le set ne peut pas être vide, laissez le champ vide à la place
+
+# Shared
+delete = Supprimer
+confirm-delete = Confirmer la suppression
+notes = Notes
+exported-to = Exporté vers { $path }
+cost-group = Coût { $cost }
+filters-save-failed = Impossible d'enregistrer les filtres : { $error }
+
+# Notebooks
+notebook-name = Nom du carnet
+notebook-new = Nouveau
+notebook-rename = Renommer
+notebook-duplicate = Dupliquer
+notebook-unreadable = « { $name } » n'a pas pu être lu, il n'est donc pas enregistré
+notebook-imported = { $bis } listes BIS et { $comps } compos importées
+toml-file = Fichier TOML
+export-toml = Exporter en TOML
+import-toml = Importer du TOML
+
+# Comps
+comp-name = Nom de la compo
+comp-new = Nouvelle compo
+comp-paste-code = Coller un code
+comp-champion-to-place = Champion à placer
+comp-select-unit = Sélectionnez une unité sur le plateau
+comp-notes = Notes de la compo
+comp-copy-planner-code = Copier le code du planificateur
+comp-copy-share-code = Copier le code de partage
+comp-delete = Supprimer la compo
+comp-none-selected = Aucune compo sélectionnée
+comp-use-bis-items = Utiliser les objets BIS
+comp-remove-unit = Retirer
+comp-planner-code-copied = Code du planificateur copié
+comp-share-code-copied = Code de partage copié
+comp-imported = { $name } importée
+
+# Patch changes
+changelog-previous-snapshot = Instantané précédent
+changelog-compare = Comparer
+changelog-prompt = Comparez un instantané pour voir ce qui a changé
+changelog-no-changes = Aucun changement
+changelog-added = Nouveau : { $name }
+changelog-removed = Retiré : { $name }
+changelog-change = { $name } { $field } : { $old } -> { $new } ({ $direction })
+category-champions = Champions
+category-traits = Traits
+category-items = Objets
+direction-buff = buff
+direction-nerf = nerf
+direction-adjusted = ajusté
+
+# Game log
+game-log-date = Date
+game-log-patch = Patch
+game-log-final-comp = Compo finale
+game-log-augments = Augments, séparés par des virgules
+game-log-log = Enregistrer la partie
+game-log-csv-file = Fichier CSV
+game-log-export-csv = Exporter en CSV
+game-log-bad-date = Les dates s'écrivent 2023-06-30
+game-log-pick-placement = Choisissez un classement
+game-log-logged = Partie enregistrée
+game-log-unreadable = Le journal des parties n'a pas pu être lu, les nouvelles parties y sont quand même ajoutées : { $error }
+game-log-not-rewritten = Impossible de supprimer des parties tant que le journal ne peut pas être lu
+
+# Analytics
+analytics-from = Du AAAA-MM-JJ
+analytics-to = Au AAAA-MM-JJ
+analytics-summary = { $games } parties | moy. { $average } | top 4 { $top-four } % | victoires { $wins } %
+analytics-by-comp = Classement moyen par compo
+analytics-by-carry = Classement moyen par carry
+analytics-top-four-over-time = Taux de top 4 sur les { $games } dernières parties
+analytics-top-four-by-item = Taux de top 4 par objet (%)
+analytics-by-patch = Classement moyen par patch
+
+# Item audit
+audit-removed = { $item } a été retiré
+audit-recipe-changed = La recette de { $item } est passée de { $old } à { $new }
+audit-effect-changed = { $item } : { $effect } est passé de { $old } à { $new }
+audit-use = Utiliser { $item }
+audit-keep = Garder
+
+# Calculator
+calc-title = Calculateur (objets BIS et bonus de traits)
+calc-trait-bonus = Trait : { $stat }
+stat-ability-power = Puissance
+calc-stats = AD { $ad } | AP { $ap } | VA { $as } | Critique { $crit } %
+calc-attack-dps = DPS d'attaque : { $dps }
+calc-ability-damage = { $ability } par lancement : { $damage }
+calc-effective-hp = PV effectifs : { $physical } physiques, { $magic } magiques
+
+# Live game
+live-read-game = Lire la partie en cours
+live-client-url = URL du Live Client
+live-disconnected = Déconnecté : { $reason }
+live-level = Niveau { $level }
+live-gold = Or { $gold }
+live-interest = Intérêts +{ $interest }
+live-next-interest = { $gold } or avant le prochain intérêt
+live-max-interest = Intérêts maximaux
+
+# Match stats
+match-folder = Dossier des fichiers de parties
+match-import = Importer les parties
+match-imported = { $added } nouvelles parties importées sur { $total }
+match-stats-save-failed = Impossible d'enregistrer les statistiques de parties : { $error }
+match-no-stats = Aucune statistique de partie
+match-stats-title = Statistiques de parties ({ $games } parties)
+match-item-frequency = { $item } - { $percent } %
+match-combo = moy. { $average } | top 4 { $top-four } % | { $games } parties
+match-use-as-bis = Utiliser comme BIS
+
+# Tier lists
+tier-list-default-name = Tier list { $number }
+tier-list-name = Nom de la tier list
+tier-new-list = Nouvelle
+tier-rename-list = Renommer
+tier-rename-row = Renommer
+tier-row-label = Nom de la ligne
+tier-add-row = Ajouter une ligne
+tier-unranked = Non classés
+tier-moving = Déplacement de { $champion }
+tier-no-lists = Aucune tier list pour l'instant
+tier-export-file = Fichier d'export
+tier-export-text = Exporter en texte
+tier-export-png = Exporter en PNG
+
+# Augments
+search-augments = Rechercher des augments
+augment-tier-list = Tier list de { $notebook }
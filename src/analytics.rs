@@ -0,0 +1,319 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::Serialize;
+
+use crate::game_log::GameRecord;
+
+/// Which logged games the aggregates look at.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GameFilter {
+    /// First date included, `YYYY-MM-DD`.
+    pub from: Option<String>,
+    /// Last date included, `YYYY-MM-DD`.
+    pub to: Option<String>,
+    /// Games of any of these patches are included. Empty means every patch.
+    pub patches: BTreeSet<String>,
+}
+
+impl GameFilter {
+    pub fn matches(&self, game: &GameRecord) -> bool {
+        // ISO dates compare correctly as strings
+        self.from.iter().all(|from| &game.date >= from)
+            && self.to.iter().all(|to| &game.date <= to)
+            && (self.patches.is_empty() || self.patches.contains(&game.patch))
+    }
+
+    pub fn apply<'a>(&self, games: &'a [GameRecord]) -> Vec<&'a GameRecord> {
+        games.iter().filter(|game| self.matches(game)).collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct PlacementStats {
+    pub games: u32,
+    pub placement_total: u32,
+    pub top_four: u32,
+    pub wins: u32,
+}
+
+impl PlacementStats {
+    pub fn add(&mut self, placement: u8) {
+        self.games += 1;
+        self.placement_total += placement as u32;
+        self.top_four += u32::from(placement <= 4);
+        self.wins += u32::from(placement == 1);
+    }
+
+    pub fn average_placement(&self) -> f64 {
+        self.placement_total as f64 / self.games.max(1) as f64
+    }
+
+    pub fn top_four_rate(&self) -> f64 {
+        self.top_four as f64 / self.games.max(1) as f64
+    }
+
+    pub fn win_rate(&self) -> f64 {
+        self.wins as f64 / self.games.max(1) as f64
+    }
+}
+
+/// Placement stats of the games sharing a key, such as a comp or an item.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Group {
+    pub key: String,
+    pub stats: PlacementStats,
+}
+
+/// Groups games under every key `keys` returns for them, best average placement first.
+fn group_by(games: &[&GameRecord], keys: impl Fn(&GameRecord) -> Vec<String>) -> Vec<Group> {
+    let mut groups: BTreeMap<String, PlacementStats> = BTreeMap::new();
+    for game in games {
+        let game_keys: BTreeSet<String> = keys(game).into_iter().collect();
+        for key in game_keys {
+            groups.entry(key).or_default().add(game.placement);
+        }
+    }
+    let mut groups: Vec<Group> = groups
+        .into_iter()
+        .map(|(key, stats)| Group { key, stats })
+        .collect();
+    groups.sort_by(|a, b| {
+        a.stats
+            .average_placement()
+            .total_cmp(&b.stats.average_placement())
+    });
+    groups
+}
+
+pub fn overall(games: &[&GameRecord]) -> PlacementStats {
+    let mut stats = PlacementStats::default();
+    for game in games {
+        stats.add(game.placement);
+    }
+    stats
+}
+
+/// By the name of the notebook comp the game was logged with.
+pub fn by_comp(games: &[&GameRecord]) -> Vec<Group> {
+    group_by(games, |game| {
+        (!game.comp_name.is_empty())
+            .then(|| game.comp_name.clone())
+            .into_iter()
+            .collect()
+    })
+}
+
+/// The carry is the unit holding the most items, the earlier unit on ties.
+pub fn carry(game: &GameRecord) -> Option<&str> {
+    game.comp
+        .iter()
+        .filter(|unit| !unit.items.is_empty())
+        .rev()
+        .max_by_key(|unit| unit.items.len())
+        .map(|unit| unit.champion.as_str())
+}
+
+/// By carry champion api name.
+pub fn by_carry(games: &[&GameRecord]) -> Vec<Group> {
+    group_by(games, |game| {
+        carry(game).map(String::from).into_iter().collect()
+    })
+}
+
+/// By item api name, counting an item once per game however many units held it.
+pub fn by_item(games: &[&GameRecord]) -> Vec<Group> {
+    group_by(games, |game| {
+        game.comp
+            .iter()
+            .flat_map(|unit| unit.items.iter().cloned())
+            .collect()
+    })
+}
+
+pub fn by_patch(games: &[&GameRecord]) -> Vec<Group> {
+    group_by(games, |game| vec![game.patch.clone()])
+}
+
+/// Top 4 rate over the last `window` games, after every game in date order.
+pub fn top_four_over_time(games: &[&GameRecord], window: usize) -> Vec<(String, f64)> {
+    let mut games = games.to_vec();
+    // stable, so games of the same day keep the order they were logged in
+    games.sort_by(|a, b| a.date.cmp(&b.date));
+    (0..games.len())
+        .map(|end| {
+            let recent = &games[(end + 1).saturating_sub(window.max(1))..=end];
+            let top_four = recent.iter().filter(|game| game.placement <= 4).count();
+            (
+                games[end].date.clone(),
+                top_four as f64 / recent.len() as f64,
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::comp::CompUnit;
+
+    fn game(date: &str, patch: &str, placement: u8, comp_name: &str) -> GameRecord {
+        GameRecord {
+            date: date.to_string(),
+            patch: patch.to_string(),
+            placement,
+            comp_name: comp_name.to_string(),
+            comp: vec![],
+            augments: vec![],
+            notes: String::new(),
+        }
+    }
+
+    fn unit(champion: &str, items: &[&str]) -> CompUnit {
+        CompUnit {
+            champion: champion.to_string(),
+            star: 2,
+            items: items.iter().map(|item| item.to_string()).collect(),
+            position: None,
+        }
+    }
+
+    fn keys(groups: &[Group]) -> Vec<(&str, u32)> {
+        groups
+            .iter()
+            .map(|group| (group.key.as_str(), group.stats.games))
+            .collect()
+    }
+
+    #[test]
+    fn groups_are_sorted_by_average_placement() {
+        let games = [
+            game("2023-06-01", "13.11", 5, "Sorcs"),
+            game("2023-06-02", "13.11", 1, "Bruisers"),
+            game("2023-06-03", "13.12", 3, "Sorcs"),
+            game("2023-06-04", "13.12", 7, ""),
+            game("2023-06-05", "13.12", 3, "Bruisers"),
+        ];
+        let games: Vec<&GameRecord> = games.iter().collect();
+        let comps = by_comp(&games);
+        // games without a comp aren't grouped
+        assert_eq!(keys(&comps), [("Bruisers", 2), ("Sorcs", 2)]);
+        assert_eq!(comps[0].stats.average_placement(), 2.0);
+        assert_eq!(comps[1].stats.average_placement(), 4.0);
+        assert_eq!(comps[0].stats.top_four_rate(), 1.0);
+        assert_eq!(comps[1].stats.win_rate(), 0.0);
+        assert_eq!(keys(&by_patch(&games)), [("13.11", 2), ("13.12", 3)]);
+
+        let overall = overall(&games);
+        assert_eq!(
+            overall,
+            PlacementStats {
+                games: 5,
+                placement_total: 19,
+                top_four: 3,
+                wins: 1,
+            }
+        );
+        assert_eq!(PlacementStats::default().average_placement(), 0.0);
+    }
+
+    #[test]
+    fn the_carry_holds_the_most_items() {
+        let mut record = game("2023-06-01", "13.12", 2, "");
+        assert_eq!(carry(&record), None);
+        record.comp = vec![
+            unit("TFT9_Ahri", &[]),
+            unit("TFT9_Sona", &["TFT_Item_BlueBuff"]),
+            unit("TFT9_Lux", &["TFT_Item_JeweledGauntlet"]),
+        ];
+        // ties go to the earlier unit
+        assert_eq!(carry(&record), Some("TFT9_Sona"));
+        record.comp[2].items.push(String::from("TFT_Item_BlueBuff"));
+        assert_eq!(carry(&record), Some("TFT9_Lux"));
+        record.comp[0].items = vec![String::from("TFT_Item_Deathcap"); 2];
+        assert_eq!(carry(&record), Some("TFT9_Ahri"));
+
+        let games = [record, game("2023-06-02", "13.12", 4, "")];
+        let games: Vec<&GameRecord> = games.iter().collect();
+        assert_eq!(keys(&by_carry(&games)), [("TFT9_Ahri", 1)]);
+    }
+
+    #[test]
+    fn items_count_once_per_game() {
+        let mut first = game("2023-06-01", "13.12", 1, "");
+        first.comp = vec![
+            unit("TFT9_Sona", &["TFT_Item_BlueBuff", "TFT_Item_BlueBuff"]),
+            unit("TFT9_Lux", &["TFT_Item_BlueBuff", "TFT_Item_Deathcap"]),
+        ];
+        let mut second = game("2023-06-02", "13.12", 8, "");
+        second.comp = vec![unit("TFT9_Lux", &["TFT_Item_BlueBuff"])];
+        let games = [first, second];
+        let games: Vec<&GameRecord> = games.iter().collect();
+        let items = by_item(&games);
+        assert_eq!(
+            keys(&items),
+            [("TFT_Item_Deathcap", 1), ("TFT_Item_BlueBuff", 2)]
+        );
+        assert_eq!(items[1].stats.placement_total, 9);
+    }
+
+    #[test]
+    fn filters_include_their_bounds() {
+        let games = [
+            game("2023-05-31", "13.11", 1, ""),
+            game("2023-06-01", "13.11", 2, ""),
+            game("2023-06-15", "13.12", 3, ""),
+            game("2023-06-30", "13.13", 4, ""),
+            game("2023-07-01", "13.13", 5, ""),
+        ];
+        let placements = |filter: &GameFilter| -> Vec<u8> {
+            filter
+                .apply(&games)
+                .into_iter()
+                .map(|game| game.placement)
+                .collect()
+        };
+        assert_eq!(placements(&GameFilter::default()), [1, 2, 3, 4, 5]);
+        let june = GameFilter {
+            from: Some(String::from("2023-06-01")),
+            to: Some(String::from("2023-06-30")),
+            ..GameFilter::default()
+        };
+        assert_eq!(placements(&june), [2, 3, 4]);
+        let patches = GameFilter {
+            patches: BTreeSet::from([String::from("13.11"), String::from("13.13")]),
+            ..june
+        };
+        assert_eq!(placements(&patches), [2, 4]);
+    }
+
+    #[test]
+    fn top_four_rate_uses_the_last_games() {
+        let games = [
+            game("2023-06-03", "13.12", 8, ""),
+            game("2023-06-01", "13.12", 1, ""),
+            game("2023-06-02", "13.12", 2, ""),
+            game("2023-06-03", "13.12", 4, ""),
+        ];
+        let games: Vec<&GameRecord> = games.iter().collect();
+        // sorted by date, keeping the logged order within a day: 1, 2, 8, 4
+        assert_eq!(
+            top_four_over_time(&games, 2),
+            [
+                (String::from("2023-06-01"), 1.0),
+                (String::from("2023-06-02"), 1.0),
+                (String::from("2023-06-03"), 0.5),
+                (String::from("2023-06-03"), 0.5),
+            ]
+        );
+        let whole = top_four_over_time(&games, 10);
+        assert_eq!(whole[2].1, 2.0 / 3.0);
+        assert_eq!(whole[3].1, 0.75);
+        // a window of 0 is treated as 1
+        let last: Vec<f64> = top_four_over_time(&games, 0)
+            .into_iter()
+            .map(|(_, rate)| rate)
+            .collect();
+        assert_eq!(last, [1.0, 1.0, 0.0, 1.0]);
+        assert_eq!(top_four_over_time(&[], 5), []);
+    }
+}
@@ -0,0 +1,426 @@
+use std::{collections::BTreeMap, fmt::Display};
+
+use serde::Serialize;
+
+use crate::{
+    database::TftDatabase,
+    tft_data::{format_number, Item},
+};
+
+/// How much an effect may change, as a fraction of its old value, before the item is flagged.
+pub const DEFAULT_THRESHOLD: f64 = 0.25;
+
+/// Replacements suggested per flagged item.
+const REPLACEMENTS: usize = 3;
+
+/// What happened to a saved item in the new data.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum Issue {
+    /// The item is no longer in the data.
+    Removed,
+    /// Component names of the old and the new recipe.
+    RecipeChanged { old: Vec<String>, new: Vec<String> },
+    /// The effect that changed the most.
+    EffectChanged { effect: String, old: f64, new: f64 },
+}
+
+impl Display for Issue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Issue::Removed => write!(f, "was removed"),
+            Issue::RecipeChanged { old, new } => write!(
+                f,
+                "recipe changed from {} to {}",
+                old.join(" + "),
+                new.join(" + ")
+            ),
+            Issue::EffectChanged { effect, old, new } => write!(
+                f,
+                "{effect} changed from {} to {}",
+                format_number(*old),
+                format_number(*new)
+            ),
+        }
+    }
+}
+
+/// A saved BIS item affected by a data refresh.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Finding {
+    /// Champion api name.
+    pub champion: String,
+    /// Item api name.
+    pub item: String,
+    /// Name of the item in the data it was saved with, if known.
+    pub item_name: String,
+    pub issue: Issue,
+    /// Api names of items to use instead, best first.
+    pub replacements: Vec<String>,
+}
+
+/// Saved BIS items that were removed or reworked since the previous data.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Audit {
+    pub findings: Vec<Finding>,
+}
+
+impl Audit {
+    pub fn is_empty(&self) -> bool {
+        self.findings.is_empty()
+    }
+
+    pub fn for_champion<'a>(&'a self, champion: &'a str) -> impl Iterator<Item = &'a Finding> {
+        self.findings
+            .iter()
+            .filter(move |finding| finding.champion == champion)
+    }
+
+    /// Drops the finding for an item once it was replaced or kept.
+    pub fn resolve(&mut self, champion: &str, item: &str) {
+        self.findings
+            .retain(|finding| finding.champion != champion || finding.item != item);
+    }
+
+    pub fn to_text(&self, db: &TftDatabase) -> String {
+        let mut text = String::new();
+        for finding in self.findings.iter() {
+            let champion = db
+                .champion(&finding.champion)
+                .map_or(finding.champion.as_str(), |champ| &champ.name);
+            text.push_str(&format!(
+                "{champion}: {} {}\n",
+                finding.item_name, finding.issue
+            ));
+            if !finding.replacements.is_empty() {
+                let names: Vec<&str> = finding
+                    .replacements
+                    .iter()
+                    .map(|api_name| {
+                        db.item(api_name)
+                            .map_or(api_name.as_str(), |item| &item.name)
+                    })
+                    .collect();
+                text.push_str(&format!("  try {}\n", names.join(", ")));
+            }
+        }
+        text
+    }
+}
+
+fn component_names(db: &TftDatabase, item: &Item) -> Vec<String> {
+    item.composition
+        .iter()
+        .map(|api_name| db.item(api_name).map_or(api_name, |c| &c.name).clone())
+        .collect()
+}
+
+fn issue(
+    old_db: &TftDatabase,
+    new_db: &TftDatabase,
+    old: Option<&Item>,
+    new: Option<&Item>,
+    threshold: f64,
+) -> Option<Issue> {
+    let Some(new) = new else {
+        return Some(Issue::Removed);
+    };
+    // without the previous data there's nothing to compare to
+    let old = old?;
+
+    let mut old_recipe = old.composition.clone();
+    let mut new_recipe = new.composition.clone();
+    old_recipe.sort();
+    new_recipe.sort();
+    if old_recipe != new_recipe {
+        return Some(Issue::RecipeChanged {
+            old: component_names(old_db, old),
+            new: component_names(new_db, new),
+        });
+    }
+
+    let (effect, old_value, new_value, change) = old
+        .effect_values()
+        .into_iter()
+        .filter(|(_, value)| *value != 0.0)
+        .map(|(effect, old_value)| {
+            let new_value = new.effect(&effect).unwrap_or_default();
+            let change = ((new_value - old_value) / old_value).abs();
+            (effect, old_value, new_value, change)
+        })
+        .max_by(|a, b| a.3.total_cmp(&b.3))?;
+    (change > threshold).then_some(Issue::EffectChanged {
+        effect,
+        old: old_value,
+        new: new_value,
+    })
+}
+
+/// Completed items of `db` most like `item`: sharing components counts the most, then sharing
+/// effects. Items in `exclude` are skipped.
+pub fn replacements(item: &Item, db: &TftDatabase, exclude: &[String]) -> Vec<String> {
+    let effects: Vec<String> = item
+        .effect_values()
+        .into_iter()
+        .filter(|(_, value)| *value != 0.0)
+        .map(|(name, _)| name)
+        .collect();
+    let mut scored: Vec<(usize, &Item)> = db
+        .items
+        .iter()
+        .filter(|candidate| candidate.api_name != item.api_name)
+        .filter(|candidate| !exclude.contains(&candidate.api_name))
+        .map(|candidate| {
+            let components = candidate
+                .composition
+                .iter()
+                .filter(|component| item.composition.contains(component))
+                .count();
+            let shared_effects = effects
+                .iter()
+                .filter(|effect| candidate.effect(effect).is_some_and(|value| value != 0.0))
+                .count();
+            (components * 2 + shared_effects, candidate)
+        })
+        .filter(|(score, _)| *score > 0)
+        .collect();
+    scored.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.name.cmp(&b.1.name)));
+    scored
+        .into_iter()
+        .take(REPLACEMENTS)
+        .map(|(_, candidate)| candidate.api_name.clone())
+        .collect()
+}
+
+/// Checks saved BIS lists, champion api name to item api names, against refreshed data. `old` is
+/// the data before the refresh; without it only removed items are found.
+pub fn audit(
+    saved: &BTreeMap<String, Vec<String>>,
+    old: Option<&TftDatabase>,
+    new: &TftDatabase,
+    threshold: f64,
+) -> Audit {
+    let empty = TftDatabase::default();
+    let old_db = old.unwrap_or(&empty);
+    let mut findings = vec![];
+    for (champion, items) in saved.iter() {
+        for api_name in items.iter() {
+            let old_item = old_db.item(api_name);
+            let new_item = new.item(api_name);
+            let Some(issue) = issue(old_db, new, old_item, new_item, threshold) else {
+                continue;
+            };
+            let replacements = old_item
+                .or(new_item)
+                .map(|item| replacements(item, new, items))
+                .unwrap_or_default();
+            findings.push(Finding {
+                champion: champion.clone(),
+                item: api_name.clone(),
+                item_name: old_item
+                    .or(new_item)
+                    .map_or(api_name, |item| &item.name)
+                    .clone(),
+                issue,
+                replacements,
+            });
+        }
+    }
+    Audit { findings }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use super::*;
+
+    const SHOJIN: &str = "TFT_Item_SpearOfShojin";
+    const BLUE_BUFF: &str = "TFT_Item_BlueBuff";
+    const DEATHBLADE: &str = "TFT_Item_Deathblade";
+    const RABADON: &str = "TFT_Item_RabadonsDeathcap";
+    const GUINSOO: &str = "TFT_Item_GuinsoosRageblade";
+    const TEAR: &str = "TFT_Item_TearOfTheGoddess";
+    const SWORD: &str = "TFT_Item_BFSword";
+    const ROD: &str = "TFT_Item_NeedlesslyLargeRod";
+    const BOW: &str = "TFT_Item_RecurveBow";
+
+    fn item(api_name: &str, name: &str, composition: &[&str], effects: Value) -> Item {
+        let mut item = Item::default();
+        item.api_name = api_name.to_string();
+        item.name = name.to_string();
+        item.composition = composition.iter().map(|c| c.to_string()).collect();
+        item.effects = effects;
+        item
+    }
+
+    fn db(shojin: Option<Item>) -> TftDatabase {
+        let mut items = vec![
+            item(
+                BLUE_BUFF,
+                "Blue Buff",
+                &[TEAR, TEAR],
+                json!({"ManaRestore": 10.0}),
+            ),
+            item(
+                DEATHBLADE,
+                "Deathblade",
+                &[SWORD, SWORD],
+                json!({"AD": 50.0}),
+            ),
+            item(
+                RABADON,
+                "Rabadon's Deathcap",
+                &[ROD, ROD],
+                json!({"AP": 50.0}),
+            ),
+            item(
+                GUINSOO,
+                "Guinsoo's Rageblade",
+                &[BOW, ROD],
+                json!({"AS": 10.0}),
+            ),
+        ];
+        items.extend(shojin);
+        TftDatabase {
+            items,
+            components: vec![
+                item(TEAR, "Tear of the Goddess", &[], json!({})),
+                item(SWORD, "B.F. Sword", &[], json!({})),
+                item(ROD, "Needlessly Large Rod", &[], json!({})),
+                item(BOW, "Recurve Bow", &[], json!({})),
+            ],
+            ..TftDatabase::default()
+        }
+    }
+
+    fn shojin(composition: &[&str], mana: f64) -> Item {
+        item(
+            SHOJIN,
+            "Spear of Shojin",
+            composition,
+            json!({"AD": 15.0, "ManaRestore": mana}),
+        )
+    }
+
+    fn saved(items: &[&str]) -> BTreeMap<String, Vec<String>> {
+        BTreeMap::from([(
+            String::from("TFT9_Ahri"),
+            items.iter().map(|item| item.to_string()).collect(),
+        )])
+    }
+
+    fn issues(old: Option<&TftDatabase>, new: &TftDatabase) -> Vec<Issue> {
+        audit(&saved(&[SHOJIN]), old, new, DEFAULT_THRESHOLD)
+            .findings
+            .into_iter()
+            .map(|finding| finding.issue)
+            .collect()
+    }
+
+    #[test]
+    fn removed_items_are_flagged() {
+        let old = db(Some(shojin(&[TEAR, SWORD], 5.0)));
+        let audit = audit(
+            &saved(&[SHOJIN, BLUE_BUFF]),
+            Some(&old),
+            &db(None),
+            DEFAULT_THRESHOLD,
+        );
+        let [finding] = &audit.findings[..] else {
+            panic!("{audit:?}");
+        };
+        assert_eq!(finding.item, SHOJIN);
+        assert_eq!(finding.item_name, "Spear of Shojin");
+        assert_eq!(finding.issue, Issue::Removed);
+        // Blue Buff is already in the list
+        assert_eq!(finding.replacements, [DEATHBLADE]);
+    }
+
+    #[test]
+    fn recipes_are_compared_regardless_of_order() {
+        let old = db(Some(shojin(&[TEAR, SWORD], 5.0)));
+        assert_eq!(
+            issues(Some(&old), &db(Some(shojin(&[SWORD, TEAR], 5.0)))),
+            []
+        );
+        assert_eq!(
+            issues(Some(&old), &db(Some(shojin(&[SWORD, ROD], 5.0)))),
+            [Issue::RecipeChanged {
+                old: vec![
+                    String::from("Tear of the Goddess"),
+                    String::from("B.F. Sword")
+                ],
+                new: vec![
+                    String::from("B.F. Sword"),
+                    String::from("Needlessly Large Rod")
+                ],
+            }]
+        );
+    }
+
+    #[test]
+    fn effects_are_flagged_over_the_threshold() {
+        let old = db(Some(shojin(&[TEAR, SWORD], 100.0)));
+        let new = |mana| db(Some(shojin(&[TEAR, SWORD], mana)));
+        assert_eq!(issues(Some(&old), &new(125.0)), []);
+        assert_eq!(issues(Some(&old), &new(75.5)), []);
+        assert_eq!(issues(Some(&old), &new(124.0)), []);
+        assert_eq!(
+            issues(Some(&old), &new(126.0)),
+            [Issue::EffectChanged {
+                effect: String::from("ManaRestore"),
+                old: 100.0,
+                new: 126.0,
+            }]
+        );
+        assert_eq!(
+            issues(Some(&old), &new(74.0)),
+            [Issue::EffectChanged {
+                effect: String::from("ManaRestore"),
+                old: 100.0,
+                new: 74.0,
+            }]
+        );
+        // the biggest change is reported
+        let mut reworked = shojin(&[TEAR, SWORD], 100.0);
+        reworked.effects = json!({"AD": 30.0, "ManaRestore": 200.0});
+        assert!(matches!(
+            &issues(Some(&old), &db(Some(reworked)))[..],
+            [Issue::EffectChanged { effect, .. }] if effect == "ManaRestore"
+        ));
+    }
+
+    #[test]
+    fn without_previous_data_only_removed_items_are_flagged() {
+        assert_eq!(issues(None, &db(Some(shojin(&[SWORD, ROD], 50.0)))), []);
+        let audit = audit(&saved(&[SHOJIN]), None, &db(None), DEFAULT_THRESHOLD);
+        assert_eq!(audit.findings[0].issue, Issue::Removed);
+        // nothing is known about the item, so there's nothing to suggest
+        assert_eq!(audit.findings[0].item_name, SHOJIN);
+        assert!(audit.findings[0].replacements.is_empty());
+    }
+
+    #[test]
+    fn replacements_share_components_then_effects() {
+        let db = db(None);
+        // Tear + Sword: Blue Buff and Deathblade share both components and an effect each, so
+        // the name decides; Rabadon's shares neither and isn't suggested
+        let spear = shojin(&[TEAR, SWORD], 5.0);
+        assert_eq!(replacements(&spear, &db, &[]), [BLUE_BUFF, DEATHBLADE]);
+        // one shared component beats one shared effect
+        let hybrid = item(
+            "TFT_Item_Hybrid",
+            "Hybrid",
+            &[BOW, TEAR],
+            json!({"AP": 10.0}),
+        );
+        assert_eq!(
+            replacements(&hybrid, &db, &[]),
+            [BLUE_BUFF, GUINSOO, RABADON]
+        );
+        assert_eq!(
+            replacements(&hybrid, &db, &[String::from(BLUE_BUFF)]),
+            [GUINSOO, RABADON]
+        );
+    }
+}
@@ -0,0 +1,791 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use serde::Serialize;
+
+use tft::analytics::*;
+use tft::audit::{audit, DEFAULT_THRESHOLD};
+use tft::comp::{BoardPosition, BOARD_COLS};
+use tft::config::{config_path, Config};
+use tft::database::{previous_data_path, LoadOptions, TftDatabase};
+use tft::diff::{diff, load_snapshot, Direction};
+use tft::game_log::{is_valid_date, to_csv, GameLog};
+use tft::match_stats::*;
+use tft::notebook::{NotebookStore, DEFAULT_NOTEBOOK};
+use tft::save::SaveFile;
+use tft::search::closest_match;
+use tft::serde_help::{project_dirs, set_image_downloads};
+use tft::sim::{compare_items, SimConfig, Team, UnitSpec};
+use tft::tft_data::*;
+use tft::toml_format;
+
+/// Query TFT champions, items and traits, and export saved notebooks.
+#[derive(Debug, Parser)]
+#[command(name = "tft-cli", version)]
+struct Cli {
+    #[command(flatten)]
+    global: GlobalArgs,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Args)]
+struct GlobalArgs {
+    /// Print JSON instead of text
+    #[arg(long, global = true)]
+    json: bool,
+    /// Set mutator (TFTSet8_Stage2) or number (8), defaults to the set in the config
+    #[arg(long, global = true)]
+    set: Option<String>,
+    /// CommunityDragon locale, defaults to the locale in the config
+    #[arg(long, global = true)]
+    locale: Option<String>,
+    /// Use the data cached by the last online run
+    #[arg(long, global = true)]
+    offline: bool,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Show a champion's stats and ability
+    Champ {
+        name: String,
+        /// Star level of the shown stats and ability values
+        #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u8).range(1..=3))]
+        star: u8,
+    },
+    /// Show an item or component
+    Item { name: String },
+    /// Show the item built from two components
+    Recipe { first: String, second: String },
+    /// Show a trait and its champions
+    Trait { name: String },
+    /// List champions, optionally filtered by cost and traits
+    Champs {
+        /// Keep champions of this cost, can be repeated
+        #[arg(long)]
+        cost: Vec<u8>,
+        /// Keep champions with this trait, can be repeated
+        #[arg(long = "trait")]
+        traits: Vec<String>,
+    },
+    /// Simulate a champion fighting an opponent, once per item set
+    Sim {
+        champion: String,
+        /// Comma separated items of one set, repeat to compare sets
+        #[arg(long = "items")]
+        item_sets: Vec<String>,
+        #[arg(long, default_value_t = 2, value_parser = clap::value_parser!(u8).range(1..=3))]
+        star: u8,
+        /// The opponent
+        #[arg(long)]
+        vs: String,
+        /// Comma separated items of the opponent
+        #[arg(long, default_value = "")]
+        vs_items: String,
+        #[arg(long, default_value_t = 2, value_parser = clap::value_parser!(u8).range(1..=3))]
+        vs_star: u8,
+        /// Seed of the crit rolls
+        #[arg(long, default_value_t = 1)]
+        seed: u64,
+        /// Print the combat log of every fight
+        #[arg(long)]
+        log: bool,
+    },
+    /// Compare two data snapshots and list buffs, nerfs and other changes
+    Diff {
+        /// The earlier snapshot, defaults to the data the last download replaced
+        old: Option<PathBuf>,
+        /// The later snapshot, defaults to the current data
+        new: Option<PathBuf>,
+        /// Only show changes in this direction
+        #[arg(long)]
+        only: Option<DirectionArg>,
+    },
+    /// Saved BIS lists
+    Bis {
+        #[command(subcommand)]
+        command: BisCommand,
+    },
+    /// Print a notebook's champion tier lists, or save one as a PNG image
+    Tiers {
+        /// Defaults to the last notebook opened in the app
+        #[arg(long)]
+        notebook: Option<String>,
+        /// Only this tier list
+        #[arg(long)]
+        list: Option<String>,
+        /// Save the tier list as a PNG image instead, needs --list with several lists
+        #[arg(long)]
+        png: Option<PathBuf>,
+    },
+    /// The personal game log
+    Games {
+        #[command(subcommand)]
+        command: GamesCommand,
+    },
+    /// Riot match history
+    Matches {
+        #[command(subcommand)]
+        command: MatchesCommand,
+    },
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum DirectionArg {
+    Buff,
+    Nerf,
+    Adjusted,
+}
+
+impl From<DirectionArg> for Direction {
+    fn from(direction: DirectionArg) -> Self {
+        match direction {
+            DirectionArg::Buff => Direction::Buff,
+            DirectionArg::Nerf => Direction::Nerf,
+            DirectionArg::Adjusted => Direction::Adjusted,
+        }
+    }
+}
+
+#[derive(Debug, Subcommand)]
+enum BisCommand {
+    /// Print a notebook's BIS lists and comps as TOML, or JSON with --json
+    Export {
+        /// Defaults to the last notebook opened in the app
+        #[arg(long)]
+        notebook: Option<String>,
+    },
+    /// List saved BIS items removed or reworked since the data the last download replaced
+    Audit {
+        /// Defaults to the last notebook opened in the app
+        #[arg(long)]
+        notebook: Option<String>,
+        /// Smallest change of an item effect to flag, as a fraction of its old value
+        #[arg(long, default_value_t = DEFAULT_THRESHOLD)]
+        threshold: f64,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum GamesCommand {
+    /// Print the logged games as CSV, or JSON lines with --json
+    Csv,
+    /// Placement stats of the logged games
+    Stats {
+        /// First date included, YYYY-MM-DD
+        #[arg(long)]
+        from: Option<String>,
+        /// Last date included, YYYY-MM-DD
+        #[arg(long)]
+        to: Option<String>,
+        /// Keep games of this patch, can be repeated
+        #[arg(long)]
+        patch: Vec<String>,
+        /// Group the games instead of showing the overall stats
+        #[arg(long)]
+        by: Option<GroupBy>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum GroupBy {
+    Comp,
+    Carry,
+    Item,
+    Patch,
+}
+
+#[derive(Debug, Subcommand)]
+enum MatchesCommand {
+    /// Download a player's recent matches as JSON files
+    Fetch {
+        /// Player to fetch matches of
+        #[arg(long)]
+        puuid: String,
+        #[arg(long)]
+        api_key: String,
+        #[arg(long, default_value_t = 20)]
+        count: usize,
+        /// Riot API host, or a mock serving the same routes
+        #[arg(long, default_value = RIOT_API_URL)]
+        base_url: String,
+        /// Folder to write the match files to
+        #[arg(long)]
+        out: PathBuf,
+    },
+    /// Item statistics of the match files in a folder
+    Stats {
+        dir: PathBuf,
+        /// Only show this champion
+        #[arg(long)]
+        champion: Option<String>,
+        /// Hide item sets played fewer times
+        #[arg(long, default_value_t = 3)]
+        min_games: u32,
+    },
+}
+
+/// Whether `name` is the name, English name or api name of a champion, trait or item.
+fn is_named(db: &TftDatabase, name: &str, entry_name: &str, api_name: &str) -> bool {
+    entry_name.to_lowercase() == name.to_lowercase()
+        || db
+            .english_name(api_name)
+            .is_some_and(|english_name| english_name.eq_ignore_ascii_case(name))
+        || api_name.eq_ignore_ascii_case(name)
+}
+
+fn find_champion<'a>(db: &'a TftDatabase, name: &str) -> Result<&'a Champion, String> {
+    db.champions
+        .iter()
+        .find(|champ| is_named(db, name, &champ.name, &champ.api_name))
+        .ok_or_else(|| {
+            not_found(
+                "champion",
+                name,
+                db.champions.iter().map(|champ| champ.name.as_str()),
+            )
+        })
+}
+
+fn find_item<'a>(db: &'a TftDatabase, name: &str) -> Result<&'a Item, String> {
+    let mut all_items = db.items.iter().chain(db.components.iter());
+    all_items
+        .find(|item| is_named(db, name, &item.name, &item.api_name))
+        .ok_or_else(|| {
+            not_found(
+                "item",
+                name,
+                db.items
+                    .iter()
+                    .chain(db.components.iter())
+                    .map(|item| item.name.as_str()),
+            )
+        })
+}
+
+/// Items of a comma separated list of names.
+fn find_items(db: &TftDatabase, names: &str) -> Result<Vec<Item>, String> {
+    names
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(|name| find_item(db, name).cloned())
+        .collect()
+}
+
+fn find_trait<'a>(db: &'a TftDatabase, name: &str) -> Result<&'a Trait, String> {
+    db.traits
+        .iter()
+        .find(|t| is_named(db, name, &t.name, &t.api_name))
+        .ok_or_else(|| not_found("trait", name, db.traits.iter().map(|t| t.name.as_str())))
+}
+
+fn not_found<'a>(kind: &str, name: &str, candidates: impl IntoIterator<Item = &'a str>) -> String {
+    match closest_match(name, candidates) {
+        Some(suggestion) => format!("no {kind} named \"{name}\", did you mean \"{suggestion}\"?"),
+        None => format!("no {kind} named \"{name}\""),
+    }
+}
+
+/// The named notebook, or the one last opened in the app, with its name.
+fn load_notebook(name: Option<String>) -> Result<(String, SaveFile), String> {
+    let dir = project_dirs();
+    let notebooks = NotebookStore::open(dir.data_dir()).map_err(|e| e.to_string())?;
+    let name = name
+        .or_else(|| notebooks.last_used())
+        .unwrap_or_else(|| String::from(DEFAULT_NOTEBOOK));
+    if !notebooks.exists(&name) {
+        return Err(format!("no notebook named \"{name}\""));
+    }
+    let save = notebooks.load(&name).map_err(|e| e.to_string())?;
+    Ok((name, save))
+}
+
+fn print_json(value: &impl Serialize) {
+    println!("{}", serde_json::to_string_pretty(value).unwrap());
+}
+
+fn print_champion(champ: &Champion, star: u8) {
+    let stats = champ.stats.at_star(star);
+    let stat = |value: Option<f64>| value.map_or(String::from("-"), format_number);
+    println!("{} ({}) - {} gold", champ.name, champ.api_name, champ.cost);
+    println!("Traits: {}", champ.traits.join(", "));
+    println!(
+        "{star}★ Health {}, Damage {}, Armor {}, MR {}, Attack speed {}, Range {}, Mana {}/{}",
+        stat(stats.hp),
+        stat(stats.damage),
+        stat(stats.armor),
+        stat(stats.magic_resist),
+        stat(stats.attack_speed),
+        format_number(stats.range),
+        format_number(stats.initial_mana),
+        format_number(stats.mana)
+    );
+    println!(
+        "{}: {}",
+        champ.ability.name,
+        champ.ability.rendered_description(star)
+    );
+}
+
+fn print_item(db: &TftDatabase, item: &Item) {
+    println!("{} ({}) - {}", item.name, item.api_name, item.kind());
+    println!("{}", item.rendered_description());
+    if !item.composition.is_empty() {
+        let recipe: Vec<&str> = item
+            .composition
+            .iter()
+            .map(|api_name| {
+                db.item(api_name)
+                    .map_or(api_name.as_str(), |c| c.name.as_str())
+            })
+            .collect();
+        println!("Recipe: {}", recipe.join(" + "));
+    }
+}
+
+fn fetch_matches(
+    client: &MatchClient,
+    puuid: &str,
+    count: usize,
+    out: &Path,
+) -> Result<(), MatchError> {
+    fs::create_dir_all(out)?;
+    for match_id in client.match_ids(puuid, count)? {
+        let path = out.join(format!("{match_id}.json"));
+        if path.exists() {
+            continue;
+        }
+        fs::write(&path, client.match_json(&match_id)?)?;
+        println!("{match_id} has been saved");
+    }
+    Ok(())
+}
+
+fn run(cli: Cli) -> Result<(), String> {
+    // fetching doesn't need the game data
+    if let Command::Matches {
+        command:
+            MatchesCommand::Fetch {
+                puuid,
+                api_key,
+                count,
+                base_url,
+                out,
+            },
+    } = &cli.command
+    {
+        let client = MatchClient::new(base_url, api_key);
+        return fetch_matches(&client, puuid, *count, out).map_err(|e| e.to_string());
+    }
+
+    // icons are only shown by the GUI
+    set_image_downloads(false);
+    // the flags win over the config
+    let config = Config::load(&config_path()).unwrap_or_default();
+    let locale = cli.global.locale.unwrap_or(config.locale);
+    let set = cli.global.set.or(config.set);
+    let db = TftDatabase::load_with(&LoadOptions {
+        locale: locale.clone(),
+        set: set.clone(),
+        offline: cli.global.offline,
+        data_url: config.data_url,
+    })
+    .map_err(|e| e.to_string())?;
+    let json = cli.global.json;
+
+    match cli.command {
+        Command::Champ { name, star } => {
+            let champ = find_champion(&db, &name)?;
+            if json {
+                print_json(champ);
+            } else {
+                print_champion(champ, star);
+            }
+        }
+        Command::Item { name } => {
+            let item = find_item(&db, &name)?;
+            if json {
+                print_json(item);
+            } else {
+                print_item(&db, item);
+            }
+        }
+        Command::Recipe { first, second } => {
+            let first = find_item(&db, &first)?;
+            let second = find_item(&db, &second)?;
+            let item = db
+                .items
+                .iter()
+                .find(|item| {
+                    let mut composition: Vec<&str> =
+                        item.composition.iter().map(String::as_str).collect();
+                    composition.sort();
+                    let mut recipe = vec![first.api_name.as_str(), second.api_name.as_str()];
+                    recipe.sort();
+                    composition == recipe
+                })
+                .ok_or_else(|| format!("{} and {} don't combine", first.name, second.name))?;
+            if json {
+                print_json(item);
+            } else {
+                print_item(&db, item);
+            }
+        }
+        Command::Trait { name } => {
+            let t = find_trait(&db, &name)?;
+            let champions: Vec<&Champion> = db
+                .champions
+                .iter()
+                .filter(|champ| champ.traits.contains(&t.name))
+                .collect();
+            if json {
+                #[derive(Serialize)]
+                struct TraitOutput<'a> {
+                    #[serde(flatten)]
+                    t: &'a Trait,
+                    champions: Vec<&'a str>,
+                }
+                print_json(&TraitOutput {
+                    t,
+                    champions: champions
+                        .iter()
+                        .map(|champ| champ.api_name.as_str())
+                        .collect(),
+                });
+            } else {
+                println!("{} ({})", t.name, t.api_name);
+                println!("{}", render_description(&t.desc, |_| None));
+                let breakpoints: Vec<String> = t
+                    .effects
+                    .iter()
+                    .map(|effect| effect.min_units.to_string())
+                    .collect();
+                println!("Breakpoints: {}", breakpoints.join("/"));
+                for champ in champions {
+                    println!("  {} ({} gold)", champ.name, champ.cost);
+                }
+            }
+        }
+        Command::Champs { cost, traits } => {
+            let traits = traits
+                .iter()
+                .map(|name| find_trait(&db, name).map(|t| t.name.clone()))
+                .collect::<Result<Vec<_>, _>>()?;
+            let mut champions: Vec<&Champion> = db
+                .champions
+                .iter()
+                .filter(|champ| cost.is_empty() || cost.contains(&champ.cost))
+                .filter(|champ| traits.iter().all(|t| champ.traits.contains(t)))
+                .collect();
+            champions.sort_by(|a, b| a.cost.cmp(&b.cost).then_with(|| a.name.cmp(&b.name)));
+            if json {
+                print_json(&champions);
+            } else {
+                for champ in champions {
+                    println!(
+                        "{} ({} gold) - {}",
+                        champ.name,
+                        champ.cost,
+                        champ.traits.join(", ")
+                    );
+                }
+            }
+        }
+        Command::Sim {
+            champion,
+            item_sets,
+            star,
+            vs,
+            vs_items,
+            vs_star,
+            seed,
+            log,
+        } => {
+            let champ = find_champion(&db, &champion)?;
+            let mut sets = item_sets
+                .iter()
+                .map(|names| find_items(&db, names))
+                .collect::<Result<Vec<_>, _>>()?;
+            if sets.is_empty() {
+                sets.push(vec![]);
+            }
+            let opponent = UnitSpec::new(
+                find_champion(&db, &vs)?,
+                vs_star,
+                &find_items(&db, &vs_items)?,
+                BoardPosition {
+                    row: 0,
+                    col: BOARD_COLS / 2,
+                },
+            );
+            let config = SimConfig {
+                seed,
+                ..SimConfig::default()
+            };
+            let results = compare_items(champ, star, &sets, &opponent, &config);
+            if json {
+                print_json(&results);
+                return Ok(());
+            }
+            for (items, result) in sets.iter().zip(results.iter()) {
+                let unit = &result.units[0];
+                let outcome = match result.winner {
+                    Some(Team::Blue) => "wins",
+                    Some(Team::Red) => "loses",
+                    None => "draws",
+                };
+                println!(
+                    "{} with {}: {outcome} after {}s",
+                    champ.name,
+                    ItemsDisplay(items.clone()),
+                    format_number(result.seconds)
+                );
+                println!(
+                    "  damage {} (attacks {}, ability {}) | taken {} | healed {} | {} attacks, {} casts | {} health left",
+                    format_number(unit.total_damage().round()),
+                    format_number(unit.attack_damage.round()),
+                    format_number(unit.ability_damage.round()),
+                    format_number(unit.damage_taken.round()),
+                    format_number(unit.healing.round()),
+                    unit.attacks,
+                    unit.casts,
+                    format_number(unit.health.round())
+                );
+                if log {
+                    for line in result.log_lines() {
+                        println!("    {line}");
+                    }
+                }
+            }
+        }
+        Command::Diff { old, new, only } => {
+            let set = set.as_deref();
+            let old_path = old.unwrap_or_else(|| previous_data_path(&locale));
+            if !old_path.exists() {
+                return Err(format!(
+                    "{} doesn't exist, the data hasn't changed since it was first downloaded",
+                    old_path.display()
+                ));
+            }
+            let old = load_snapshot(&old_path, set).map_err(|e| e.to_string())?;
+            let mut patch = match new {
+                Some(path) => diff(&old, &load_snapshot(&path, set).map_err(|e| e.to_string())?),
+                None => diff(&old, &db),
+            };
+            if let Some(only) = only {
+                let only = Direction::from(only);
+                patch.changes.retain(|change| change.direction == only);
+            }
+            if json {
+                print_json(&patch);
+            } else {
+                print!("{patch}");
+            }
+        }
+        Command::Bis {
+            command: BisCommand::Export { notebook },
+        } => {
+            let (_, save) = load_notebook(notebook)?;
+            if json {
+                print_json(&save);
+            } else {
+                print!("{}", toml_format::export(&save.champions, &save.comps, &db));
+            }
+        }
+        Command::Bis {
+            command:
+                BisCommand::Audit {
+                    notebook,
+                    threshold,
+                },
+        } => {
+            let (_, save) = load_notebook(notebook)?;
+            let previous_path = previous_data_path(&locale);
+            let previous = if previous_path.exists() {
+                Some(load_snapshot(&previous_path, set.as_deref()).map_err(|e| e.to_string())?)
+            } else {
+                None
+            };
+            let audit = audit(&save.champions, previous.as_ref(), &db, threshold);
+            if json {
+                print_json(&audit);
+            } else if audit.is_empty() {
+                println!("no saved items changed");
+            } else {
+                print!("{}", audit.to_text(&db));
+            }
+        }
+        Command::Tiers {
+            notebook,
+            list,
+            png,
+        } => {
+            let (name, save) = load_notebook(notebook)?;
+            let mut tier_lists = save.tier_lists;
+            if let Some(list) = list {
+                tier_lists.retain(|tier_list| tier_list.name.eq_ignore_ascii_case(&list));
+                if tier_lists.is_empty() {
+                    return Err(format!("no tier list named \"{list}\" in {name}"));
+                }
+            }
+            if let Some(path) = png {
+                let [tier_list] = tier_lists.as_slice() else {
+                    return Err(format!(
+                        "{name} has {} tier lists, pick one with --list",
+                        tier_lists.len()
+                    ));
+                };
+                tier_list
+                    .to_image(&db)
+                    .save(&path)
+                    .map_err(|e| e.to_string())?;
+            } else if json {
+                print_json(&tier_lists);
+            } else {
+                for tier_list in tier_lists.iter() {
+                    print!("{}", tier_list.to_text(&db));
+                }
+            }
+        }
+        Command::Games {
+            command: GamesCommand::Csv,
+        } => {
+            let dir = project_dirs();
+            let games = GameLog::new(&dir.data_dir().join("games.jsonl"))
+                .read()
+                .map_err(|e| e.to_string())?;
+            if json {
+                for game in games {
+                    println!("{}", serde_json::to_string(&game).unwrap());
+                }
+            } else {
+                print!("{}", to_csv(&games, &db));
+            }
+        }
+        Command::Games {
+            command:
+                GamesCommand::Stats {
+                    from,
+                    to,
+                    patch,
+                    by,
+                },
+        } => {
+            if let Some(date) = from
+                .iter()
+                .chain(to.iter())
+                .find(|date| !is_valid_date(date))
+            {
+                return Err(format!("\"{date}\" is not a YYYY-MM-DD date"));
+            }
+            let dir = project_dirs();
+            let games = GameLog::new(&dir.data_dir().join("games.jsonl"))
+                .read()
+                .map_err(|e| e.to_string())?;
+            let filter = GameFilter {
+                from,
+                to,
+                patches: patch.into_iter().collect(),
+            };
+            let games = filter.apply(&games);
+            let print_stats = |label: &str, stats: &PlacementStats| {
+                println!(
+                    "{label}: avg {} | top 4 {}% | wins {}% | {} games",
+                    format_number(stats.average_placement()),
+                    format_number(stats.top_four_rate() * 100.0),
+                    format_number(stats.win_rate() * 100.0),
+                    stats.games
+                );
+            };
+            let groups = match by {
+                None => {
+                    let stats = overall(&games);
+                    if json {
+                        print_json(&stats);
+                    } else {
+                        print_stats("all games", &stats);
+                    }
+                    return Ok(());
+                }
+                Some(GroupBy::Comp) => by_comp(&games),
+                Some(GroupBy::Carry) => by_carry(&games),
+                Some(GroupBy::Item) => by_item(&games),
+                Some(GroupBy::Patch) => by_patch(&games),
+            };
+            if json {
+                print_json(&groups);
+                return Ok(());
+            }
+            for group in groups.iter() {
+                let name = match by {
+                    Some(GroupBy::Carry) => db
+                        .champion(&group.key)
+                        .map_or(group.key.as_str(), |champ| &champ.name),
+                    Some(GroupBy::Item) => db
+                        .item(&group.key)
+                        .map_or(group.key.as_str(), |item| &item.name),
+                    _ => group.key.as_str(),
+                };
+                print_stats(name, &group.stats);
+            }
+        }
+        Command::Matches {
+            command:
+                MatchesCommand::Stats {
+                    dir,
+                    champion,
+                    min_games,
+                },
+        } => {
+            let matches = read_match_dir(&dir).map_err(|e| e.to_string())?;
+            let mut stats = MatchStats::default();
+            stats.add_matches(&matches);
+            if let Some(name) = champion {
+                let api_name = &find_champion(&db, &name)?.api_name;
+                stats.champions.retain(|champion, _| champion == api_name);
+            }
+            if json {
+                print_json(&stats.champions);
+                return Ok(());
+            }
+            let item_name = |api_name: &String| {
+                db.item(api_name)
+                    .map_or(api_name.clone(), |item| item.name.clone())
+            };
+            for (api_name, champion_stats) in stats.champions.iter() {
+                let name = db.champion(api_name).map_or(api_name.as_str(), |c| &c.name);
+                println!("{name} ({} games)", champion_stats.games);
+                for combo in champion_stats.best_combos(min_games) {
+                    let items: Vec<String> = combo.items.iter().map(item_name).collect();
+                    println!(
+                        "  avg {} | top 4 {}% | {} games | {}",
+                        format_number(combo.average_placement()),
+                        format_number(combo.top_four_rate() * 100.0),
+                        combo.games,
+                        items.join(", ")
+                    );
+                }
+            }
+        }
+        Command::Matches {
+            command: MatchesCommand::Fetch { .. },
+        } => unreachable!("fetched before loading the data"),
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use std::process::ExitCode;
+
+use clap::Parser;
+
+use tft::config::{config_path, Config};
+use tft::database::{LoadOptions, TftDatabase};
+use tft::notebook::NotebookStore;
+use tft::serde_help::{project_dirs, set_image_downloads};
+use tft::server::{ApiServer, ApiState};
+
+/// Serves the TFT data and the saved notebooks as JSON for overlays and stream widgets.
+#[derive(Debug, Parser)]
+#[command(name = "tft-server", version)]
+struct Args {
+    /// Address to listen on
+    #[arg(long, default_value = "127.0.0.1:7878")]
+    addr: String,
+    /// Set mutator (TFTSet8_Stage2) or number (8), defaults to the set in the config
+    #[arg(long)]
+    set: Option<String>,
+    /// CommunityDragon locale, defaults to the locale in the config
+    #[arg(long)]
+    locale: Option<String>,
+    /// Use the data cached by the last online run
+    #[arg(long)]
+    offline: bool,
+}
+
+fn main() -> ExitCode {
+    env_logger::builder().format_timestamp(None).init();
+    let args = Args::parse();
+
+    // icons are served as urls, the images themselves aren't needed
+    set_image_downloads(false);
+    // the flags win over the config
+    let config = Config::load(&config_path()).unwrap_or_default();
+    let db = match TftDatabase::load_with(&LoadOptions {
+        locale: args.locale.unwrap_or(config.locale),
+        set: args.set.or(config.set),
+        offline: args.offline,
+        data_url: config.data_url,
+    }) {
+        Ok(db) => db,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let dir = project_dirs();
+    let notebooks = match NotebookStore::open(dir.data_dir()) {
+        Ok(notebooks) => notebooks,
+        Err(e) => {
+            eprintln!("error: could not open the notebooks: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let server = match ApiServer::bind(&args.addr) {
+        Ok(server) => server,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    println!("serving the TFT API on http://{}", server.addr());
+
+    // the state lives as long as the server
+    let state: &'static ApiState = Box::leak(Box::new(ApiState::new(db, notebooks)));
+    server.serve(state);
+    ExitCode::SUCCESS
+}
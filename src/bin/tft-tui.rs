@@ -0,0 +1,552 @@
+use std::fmt::Display;
+use std::io::{self, Stdout};
+
+use clap::Parser;
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use ratatui::{
+    backend::CrosstermBackend,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::Spans,
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Tabs, Wrap},
+    Frame, Terminal,
+};
+
+use tft::config::{config_path, Config};
+use tft::database::{LoadOptions, TftDatabase};
+use tft::determiner::rank_champions;
+use tft::notebook::{validate_name, NotebookStore, DEFAULT_NOTEBOOK};
+use tft::save::{SaveFile, UnmatchedBis};
+use tft::search::matches_query;
+use tft::serde_help::{project_dirs, set_image_downloads};
+use tft::state::*;
+use tft::tft_data::*;
+
+type Backend = CrosstermBackend<Stdout>;
+
+/// Keyboard-driven terminal front-end for the CharacterBuilder and the ItemDeterminer.
+#[derive(Debug, Parser)]
+#[command(name = "tft-tui", version)]
+struct Args {
+    /// Notebook to open, defaults to the last one opened
+    #[arg(long)]
+    notebook: Option<String>,
+    /// CommunityDragon locale, defaults to the locale in the config
+    #[arg(long)]
+    locale: Option<String>,
+    /// Use the data cached by the last online run
+    #[arg(long)]
+    offline: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tab {
+    Builder,
+    Determiner,
+}
+
+/// List receiving the keys on the Builder tab.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Focus {
+    Champions,
+    Items,
+}
+
+const HELP_BUILDER: &str = "Tab focus  / search  Enter add item  x remove last  c clear  \
+                            s save  2 determiner  q quit";
+const HELP_DETERMINER: &str = "+/→ add  -/← remove  r reset  s save  1 builder  q quit";
+
+struct App {
+    db: TftDatabase,
+    notebooks: NotebookStore,
+    notebook: String,
+    /// The opened save, kept so comps and notes are written back unchanged.
+    save: SaveFile,
+    champs: Vec<ChampionState>,
+    /// Saved BIS entries the data doesn't know about, written back unchanged.
+    unmatched_bis: UnmatchedBis,
+    components: Vec<ComponentState>,
+    tab: Tab,
+    focus: Focus,
+    /// Whether typed keys go to the query of the focused list.
+    searching: bool,
+    champion_query: String,
+    item_query: String,
+    champion_list: ListState,
+    item_list: ListState,
+    component_list: ListState,
+    dirty: bool,
+    status: String,
+    quit: bool,
+}
+
+impl App {
+    fn new(db: TftDatabase, notebooks: NotebookStore, notebook: String, save: SaveFile) -> Self {
+        let (mut champs, report) = save.reconcile(&db);
+        let unmatched_bis = save.unmatched(&db);
+        champs.sort_by(|a, b| a.champ.name.cmp(&b.champ.name));
+        let components = db
+            .components
+            .iter()
+            .cloned()
+            .map(|component| ComponentState {
+                component,
+                count: 0,
+            })
+            .collect();
+        let mut app = App {
+            db,
+            notebooks,
+            status: if report.is_empty() {
+                format!("Opened {notebook}")
+            } else {
+                report.to_string()
+            },
+            notebook,
+            save,
+            champs,
+            unmatched_bis,
+            components,
+            tab: Tab::Builder,
+            focus: Focus::Champions,
+            searching: false,
+            champion_query: String::new(),
+            item_query: String::new(),
+            champion_list: ListState::default(),
+            item_list: ListState::default(),
+            component_list: ListState::default(),
+            dirty: false,
+            quit: false,
+        };
+        app.champion_list.select(Some(0));
+        app.item_list.select(Some(0));
+        app.component_list.select(Some(0));
+        app
+    }
+
+    fn visible_champions(&self) -> Vec<usize> {
+        self.champs
+            .iter()
+            .enumerate()
+            .filter(|(_, champ_state)| {
+                matches_query(
+                    &self.champion_query,
+                    &champ_state.champ.name,
+                    self.db.english_name(&champ_state.champ.api_name),
+                    &champ_state.champ.api_name,
+                )
+            })
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    fn visible_items(&self) -> Vec<&Item> {
+        self.db
+            .items
+            .iter()
+            .filter(|item| {
+                matches_query(
+                    &self.item_query,
+                    &item.name,
+                    self.db.english_name(&item.api_name),
+                    &item.api_name,
+                )
+            })
+            .collect()
+    }
+
+    /// Index into `champs` of the highlighted champion.
+    fn selected_champion(&self) -> Option<usize> {
+        let visible = self.visible_champions();
+        self.champion_list
+            .selected()
+            .and_then(|selected| visible.get(selected).copied())
+    }
+
+    fn save(&mut self) {
+        let save = SaveFile {
+            comps: self.save.comps.clone(),
+            notes: self.save.notes.clone(),
+            augment_tiers: self.save.augment_tiers.clone(),
+            tier_lists: self.save.tier_lists.clone(),
+            ..SaveFile::from_champions(&self.champs).with_unmatched(&self.unmatched_bis)
+        };
+        self.status = match self.notebooks.save(&self.notebook, &save) {
+            Ok(()) => {
+                self.dirty = false;
+                format!("Saved {}", self.notebook)
+            }
+            Err(e) => e.to_string(),
+        };
+    }
+
+    fn focused_list(&mut self) -> (&mut ListState, usize) {
+        match (self.tab, self.focus) {
+            (Tab::Determiner, _) => (&mut self.component_list, self.components.len()),
+            (Tab::Builder, Focus::Champions) => {
+                let len = self.visible_champions().len();
+                (&mut self.champion_list, len)
+            }
+            (Tab::Builder, Focus::Items) => {
+                let len = self.visible_items().len();
+                (&mut self.item_list, len)
+            }
+        }
+    }
+
+    fn move_selection(&mut self, offset: isize) {
+        let (list, len) = self.focused_list();
+        if len == 0 {
+            list.select(None);
+            return;
+        }
+        let selected = list.selected().unwrap_or_default() as isize + offset;
+        list.select(Some(selected.clamp(0, len as isize - 1) as usize));
+    }
+
+    fn focused_query(&mut self) -> &mut String {
+        match self.focus {
+            Focus::Champions => &mut self.champion_query,
+            Focus::Items => &mut self.item_query,
+        }
+    }
+
+    fn handle_search_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Enter => self.searching = false,
+            KeyCode::Esc => {
+                self.searching = false;
+                self.focused_query().clear();
+            }
+            KeyCode::Backspace => {
+                self.focused_query().pop();
+            }
+            KeyCode::Char(c) => self.focused_query().push(c),
+            _ => return,
+        }
+        // keep the highlight inside the filtered list
+        let (list, _) = self.focused_list();
+        list.select(Some(0));
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) {
+        if self.searching {
+            self.handle_search_key(key);
+            return;
+        }
+        match key.code {
+            KeyCode::Char('q') => self.quit = true,
+            KeyCode::Char('s') => self.save(),
+            KeyCode::Char('1') => self.tab = Tab::Builder,
+            KeyCode::Char('2') => self.tab = Tab::Determiner,
+            KeyCode::Up | KeyCode::Char('k') => self.move_selection(-1),
+            KeyCode::Down | KeyCode::Char('j') => self.move_selection(1),
+            _ => match self.tab {
+                Tab::Builder => self.handle_builder_key(key),
+                Tab::Determiner => self.handle_determiner_key(key),
+            },
+        }
+    }
+
+    fn handle_builder_key(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Tab | KeyCode::BackTab => {
+                self.focus = match self.focus {
+                    Focus::Champions => Focus::Items,
+                    Focus::Items => Focus::Champions,
+                };
+            }
+            KeyCode::Char('/') => self.searching = true,
+            KeyCode::Enter if self.focus == Focus::Items => {
+                let item = self
+                    .item_list
+                    .selected()
+                    .and_then(|selected| self.visible_items().get(selected).copied().cloned());
+                if let (Some(index), Some(item)) = (self.selected_champion(), item) {
+                    self.status = format!("{} got added to {}", item, self.champs[index].champ);
+                    self.champs[index].items.push(item);
+                    self.dirty = true;
+                }
+            }
+            KeyCode::Char('x') => {
+                if let Some(index) = self.selected_champion() {
+                    if let Some(item) = self.champs[index].items.pop() {
+                        self.status =
+                            format!("{} got removed from {}", item, self.champs[index].champ);
+                        self.dirty = true;
+                    }
+                }
+            }
+            KeyCode::Char('c') => {
+                if let Some(index) = self.selected_champion() {
+                    self.champs[index].items.clear();
+                    // the items the data doesn't know about are cleared too
+                    self.unmatched_bis
+                        .remove(&self.champs[index].champ.api_name);
+                    self.dirty = true;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_determiner_key(&mut self, key: KeyEvent) {
+        let Some(selected) = self.component_list.selected() else {
+            return;
+        };
+        match key.code {
+            KeyCode::Right | KeyCode::Char('+') | KeyCode::Char('l') => {
+                self.components[selected].count += 1;
+            }
+            KeyCode::Left | KeyCode::Char('-') | KeyCode::Char('h') => {
+                let component = &mut self.components[selected];
+                component.count = component.count.saturating_sub(1);
+            }
+            KeyCode::Char('r') => {
+                for component in self.components.iter_mut() {
+                    component.count = 0;
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn block(title: &str, focused: bool) -> Block<'_> {
+    let block = Block::default().borders(Borders::ALL).title(title);
+    if focused {
+        block.border_style(Style::default().add_modifier(Modifier::BOLD))
+    } else {
+        block
+    }
+}
+
+fn highlight() -> Style {
+    Style::default().add_modifier(Modifier::REVERSED)
+}
+
+fn champion_text(champ_state: &ChampionState) -> String {
+    let champ = &champ_state.champ;
+    let mut lines = vec![
+        format!("{} - {} gold", champ.name, champ.cost),
+        champ.traits.join(", "),
+        String::new(),
+        String::from("BIS:"),
+    ];
+    for item in champ_state.items.iter() {
+        lines.push(format!("  {item}"));
+    }
+    lines.push(String::new());
+    lines.push(format!(
+        "{}: {}",
+        champ.ability.name,
+        champ.ability.rendered_description(1)
+    ));
+    lines.join("\n")
+}
+
+fn draw_builder(f: &mut Frame<Backend>, app: &mut App, area: Rect) {
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage(30),
+            Constraint::Percentage(40),
+            Constraint::Percentage(30),
+        ])
+        .split(area);
+
+    let champions: Vec<ListItem> = app
+        .visible_champions()
+        .into_iter()
+        .map(|index| {
+            let champ_state = &app.champs[index];
+            let marker = if champ_state.items.is_empty() {
+                ""
+            } else {
+                " *"
+            };
+            ListItem::new(format!("{}{marker}", champ_state.champ.name))
+        })
+        .collect();
+    let title = format!("Champions /{}", app.champion_query);
+    let list = List::new(champions)
+        .block(block(&title, app.focus == Focus::Champions))
+        .highlight_style(highlight());
+    f.render_stateful_widget(list, columns[0], &mut app.champion_list);
+
+    let detail = app
+        .selected_champion()
+        .map(|index| champion_text(&app.champs[index]))
+        .unwrap_or_default();
+    f.render_widget(
+        Paragraph::new(detail)
+            .block(block("BIS", false))
+            .wrap(Wrap { trim: true }),
+        columns[1],
+    );
+
+    let items: Vec<ListItem> = app
+        .visible_items()
+        .into_iter()
+        .map(|item| ListItem::new(item.name.clone()))
+        .collect();
+    let title = format!("Items /{}", app.item_query);
+    let list = List::new(items)
+        .block(block(&title, app.focus == Focus::Items))
+        .highlight_style(highlight());
+    f.render_stateful_widget(list, columns[2], &mut app.item_list);
+}
+
+fn draw_determiner(f: &mut Frame<Backend>, app: &mut App, area: Rect) {
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(35), Constraint::Percentage(65)])
+        .split(area);
+
+    let components: Vec<ListItem> = app
+        .components
+        .iter()
+        .map(|component| {
+            ListItem::new(format!(
+                "{:<24} x{}",
+                component.component.name, component.count
+            ))
+        })
+        .collect();
+    let list = List::new(components)
+        .block(block("Components", true))
+        .highlight_style(highlight());
+    f.render_stateful_widget(list, columns[0], &mut app.component_list);
+
+    let ranking: Vec<ListItem> = rank_champions(&app.champs, &app.components)
+        .into_iter()
+        .filter(|(champ_state, _)| !champ_state.items.is_empty())
+        .map(|(champ_state, held)| {
+            ListItem::new(format!(
+                "{:>2}  {} - {}",
+                held,
+                champ_state.champ.name,
+                ItemsDisplay(champ_state.items.clone())
+            ))
+        })
+        .collect();
+    f.render_widget(
+        List::new(ranking).block(block("Held BIS components", false)),
+        columns[1],
+    );
+}
+
+fn draw(f: &mut Frame<Backend>, app: &mut App) {
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(0),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .split(f.size());
+
+    let dirty = if app.dirty { " (unsaved)" } else { "" };
+    let title = format!("{}{dirty}", app.notebook);
+    let tabs = Tabs::new(vec![
+        Spans::from("1 Character Builder"),
+        Spans::from("2 Item Determiner"),
+    ])
+    .block(block(&title, false))
+    .select(match app.tab {
+        Tab::Builder => 0,
+        Tab::Determiner => 1,
+    })
+    .highlight_style(highlight());
+    f.render_widget(tabs, rows[0]);
+
+    match app.tab {
+        Tab::Builder => draw_builder(f, app, rows[1]),
+        Tab::Determiner => draw_determiner(f, app, rows[1]),
+    }
+
+    f.render_widget(Paragraph::new(app.status.as_str()), rows[2]);
+    let help = match (app.tab, app.searching) {
+        (_, true) => "Enter done  Esc clear",
+        (Tab::Builder, false) => HELP_BUILDER,
+        (Tab::Determiner, false) => HELP_DETERMINER,
+    };
+    f.render_widget(Paragraph::new(help), rows[3]);
+}
+
+fn run(terminal: &mut Terminal<Backend>, app: &mut App) -> io::Result<()> {
+    while !app.quit {
+        terminal.draw(|f| draw(f, app))?;
+        if let Event::Key(key) = event::read()? {
+            // windows also reports key releases
+            if key.kind == KeyEventKind::Press {
+                app.handle_key(key);
+            }
+        }
+    }
+    if app.dirty {
+        app.save();
+    }
+    Ok(())
+}
+
+fn exit_with(e: impl Display) -> ! {
+    eprintln!("error: {e}");
+    std::process::exit(1)
+}
+
+fn main() -> io::Result<()> {
+    let args = Args::parse();
+    if let Some(name) = &args.notebook {
+        validate_name(name).unwrap_or_else(|e| exit_with(e));
+    }
+
+    // icons are only shown by the GUI
+    set_image_downloads(false);
+    // the set has to match the GUI's, or saving would drop the other set's BIS lists
+    let config = Config::load(&config_path()).unwrap_or_else(|e| {
+        eprintln!("warning: {e}, using the default settings");
+        Config::default()
+    });
+    let db = TftDatabase::load_with(&LoadOptions {
+        locale: args.locale.unwrap_or_else(|| config.locale.clone()),
+        ..config.load_options(args.offline)
+    })
+    .unwrap_or_else(|e| exit_with(e));
+    let dir = project_dirs();
+    let notebooks = NotebookStore::open(dir.data_dir()).unwrap_or_else(|e| exit_with(e));
+    let notebook = args
+        .notebook
+        .or_else(|| notebooks.last_used())
+        .unwrap_or_else(|| String::from(DEFAULT_NOTEBOOK));
+    // an unreadable notebook would be overwritten by the save on quit
+    let save = notebooks
+        .load(&notebook)
+        .unwrap_or_else(|e| exit_with(format!("could not open {notebook}: {e}")));
+    let mut app = App::new(db, notebooks, notebook, save);
+
+    // a panic would leave the shell raw and on the alternate screen, with the message hidden
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let _ = disable_raw_mode();
+        let _ = execute!(io::stdout(), LeaveAlternateScreen, cursor::Show);
+        default_hook(info);
+    }));
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen)?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(stdout))?;
+
+    let result = run(&mut terminal, &mut app);
+
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    terminal.show_cursor()?;
+    result
+}
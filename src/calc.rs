@@ -0,0 +1,402 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+use crate::tft_data::{Champion, Item};
+
+/// Ability power every unit starts with. Ability values scale with AP / 100.
+const BASE_ABILITY_POWER: f64 = 100.0;
+
+/// A stat that items and traits add to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+pub enum Stat {
+    AttackDamage,
+    AbilityPower,
+    /// Percent of the base attack speed.
+    AttackSpeed,
+    /// Percentage points.
+    CritChance,
+    Armor,
+    MagicResist,
+    Health,
+}
+
+impl Stat {
+    pub const ALL: [Stat; 7] = [
+        Stat::AttackDamage,
+        Stat::AbilityPower,
+        Stat::AttackSpeed,
+        Stat::CritChance,
+        Stat::Armor,
+        Stat::MagicResist,
+        Stat::Health,
+    ];
+
+    /// Name of the stat in CommunityDragon item effects.
+    fn effect_name(&self) -> &'static str {
+        match self {
+            Stat::AttackDamage => "AD",
+            Stat::AbilityPower => "AP",
+            Stat::AttackSpeed => "AS",
+            Stat::CritChance => "CritChance",
+            Stat::Armor => "Armor",
+            Stat::MagicResist => "MagicResist",
+            Stat::Health => "Health",
+        }
+    }
+}
+
+impl Display for Stat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Stat::AttackDamage => write!(f, "Attack Damage"),
+            Stat::AbilityPower => write!(f, "Ability Power"),
+            Stat::AttackSpeed => write!(f, "Attack Speed %"),
+            Stat::CritChance => write!(f, "Crit Chance %"),
+            Stat::Armor => write!(f, "Armor"),
+            Stat::MagicResist => write!(f, "Magic Resist"),
+            Stat::Health => write!(f, "Health"),
+        }
+    }
+}
+
+/// Stats added on top of a unit's base stats.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Bonus {
+    pub attack_damage: f64,
+    pub ability_power: f64,
+    pub attack_speed: f64,
+    pub crit_chance: f64,
+    pub armor: f64,
+    pub magic_resist: f64,
+    pub health: f64,
+}
+
+impl Bonus {
+    pub fn get(&self, stat: Stat) -> f64 {
+        match stat {
+            Stat::AttackDamage => self.attack_damage,
+            Stat::AbilityPower => self.ability_power,
+            Stat::AttackSpeed => self.attack_speed,
+            Stat::CritChance => self.crit_chance,
+            Stat::Armor => self.armor,
+            Stat::MagicResist => self.magic_resist,
+            Stat::Health => self.health,
+        }
+    }
+
+    pub fn get_mut(&mut self, stat: Stat) -> &mut f64 {
+        match stat {
+            Stat::AttackDamage => &mut self.attack_damage,
+            Stat::AbilityPower => &mut self.ability_power,
+            Stat::AttackSpeed => &mut self.attack_speed,
+            Stat::CritChance => &mut self.crit_chance,
+            Stat::Armor => &mut self.armor,
+            Stat::MagicResist => &mut self.magic_resist,
+            Stat::Health => &mut self.health,
+        }
+    }
+
+    /// The stats an item gives. Effects other than flat stats are ignored.
+    pub fn from_item(item: &Item) -> Self {
+        let mut bonus = Bonus::default();
+        for stat in Stat::ALL {
+            *bonus.get_mut(stat) = item.effect(stat.effect_name()).unwrap_or_default();
+        }
+        bonus
+    }
+
+    pub fn add(&mut self, other: &Bonus) {
+        for stat in Stat::ALL {
+            *self.get_mut(stat) += other.get(stat);
+        }
+    }
+}
+
+/// A unit's stats after star level, items and trait bonuses.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct UnitStats {
+    pub attack_damage: f64,
+    pub ability_power: f64,
+    /// Attacks per second.
+    pub attack_speed: f64,
+    /// Between 0 and 1.
+    pub crit_chance: f64,
+    pub crit_multiplier: f64,
+    pub armor: f64,
+    pub magic_resist: f64,
+    pub health: f64,
+}
+
+impl UnitStats {
+    pub fn new(champ: &Champion, star: u8, items: &[Item], traits: &Bonus) -> Self {
+        let base = champ.stats.at_star(star);
+        let mut bonus = *traits;
+        for item in items {
+            bonus.add(&Bonus::from_item(item));
+        }
+        Self {
+            attack_damage: base.damage.unwrap_or_default() + bonus.attack_damage,
+            ability_power: BASE_ABILITY_POWER + bonus.ability_power,
+            attack_speed: base.attack_speed.unwrap_or_default()
+                * (1.0 + bonus.attack_speed / 100.0),
+            crit_chance: (base.crit_chance.unwrap_or_default() + bonus.crit_chance / 100.0)
+                .clamp(0.0, 1.0),
+            crit_multiplier: base.crit_multiplier,
+            armor: base.armor.unwrap_or_default() + bonus.armor,
+            magic_resist: base.magic_resist.unwrap_or_default() + bonus.magic_resist,
+            health: base.hp.unwrap_or_default() + bonus.health,
+        }
+    }
+
+    /// Average auto-attack damage per second against a target without resistances.
+    pub fn attack_dps(&self) -> f64 {
+        let crit_factor = 1.0 + self.crit_chance * (self.crit_multiplier - 1.0);
+        self.attack_damage * self.attack_speed * crit_factor
+    }
+
+    /// Physical damage needed to kill the unit.
+    pub fn physical_ehp(&self) -> f64 {
+        self.health / mitigation(self.armor)
+    }
+
+    /// Magic damage needed to kill the unit.
+    pub fn magic_ehp(&self) -> f64 {
+        self.health / mitigation(self.magic_resist)
+    }
+}
+
+/// The share of damage that gets through armor or magic resist, `100 / (100 + resist)`.
+/// Negative resistances count as none rather than adding damage.
+pub fn mitigation(resist: f64) -> f64 {
+    100.0 / (100.0 + resist.max(0.0))
+}
+
+/// The damage values of a champion's ability at a star level, scaled by ability power.
+///
+/// Every ability variable with `Damage` in its name counts, e.g. `Damage` and `BonusDamage`.
+pub fn ability_damage(champ: &Champion, star: u8, ability_power: f64) -> Vec<(String, f64)> {
+    champ
+        .ability
+        .variables
+        .iter()
+        .filter(|variable| variable.name.to_lowercase().contains("damage"))
+        .filter_map(|variable| {
+            let value = champ.ability.variable(&variable.name, star)?;
+            Some((
+                variable.name.clone(),
+                value * ability_power / BASE_ABILITY_POWER,
+            ))
+        })
+        .collect()
+}
+
+/// Everything the calculator shows for a unit.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Calculation {
+    pub stats: UnitStats,
+    pub attack_dps: f64,
+    pub ability_damage: Vec<(String, f64)>,
+    pub physical_ehp: f64,
+    pub magic_ehp: f64,
+}
+
+pub fn calculate(champ: &Champion, star: u8, items: &[Item], traits: &Bonus) -> Calculation {
+    let stats = UnitStats::new(champ, star, items, traits);
+    Calculation {
+        stats,
+        attack_dps: stats.attack_dps(),
+        ability_damage: ability_damage(champ, star, stats.ability_power),
+        physical_ehp: stats.physical_ehp(),
+        magic_ehp: stats.magic_ehp(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::tft_data::{Ability, Stats, Variable};
+
+    fn champion() -> Champion {
+        Champion {
+            api_name: String::from("TFT9_Ahri"),
+            name: String::from("Ahri"),
+            ability: Ability {
+                variables: vec![
+                    Variable {
+                        name: String::from("Damage"),
+                        value: vec![0.0, 200.0, 300.0, 450.0],
+                    },
+                    Variable {
+                        name: String::from("BonusDamage"),
+                        value: vec![0.0, 50.0, 75.0, 100.0],
+                    },
+                    Variable {
+                        name: String::from("ManaReave"),
+                        value: vec![0.0, 20.0, 20.0, 20.0],
+                    },
+                ],
+                ..Ability::default()
+            },
+            stats: Stats {
+                hp: Some(500.0),
+                damage: Some(50.0),
+                attack_speed: Some(0.8),
+                crit_chance: Some(0.25),
+                crit_multiplier: 1.4,
+                armor: Some(20.0),
+                magic_resist: Some(20.0),
+                ..Stats::default()
+            },
+            ..Champion::default()
+        }
+    }
+
+    fn item(effects: serde_json::Value) -> Item {
+        let mut item = Item::default();
+        item.effects = effects;
+        item
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-9, "{actual} != {expected}");
+    }
+
+    #[test]
+    fn stars_scale_health_and_damage() {
+        let one = UnitStats::new(&champion(), 1, &[], &Bonus::default());
+        let two = UnitStats::new(&champion(), 2, &[], &Bonus::default());
+        let three = UnitStats::new(&champion(), 3, &[], &Bonus::default());
+        assert_close(one.health, 500.0);
+        assert_close(two.health, 900.0);
+        assert_close(three.health, 1620.0);
+        assert_close(two.attack_damage, 90.0);
+        // the rest doesn't scale
+        assert_eq!(
+            UnitStats {
+                health: 0.0,
+                attack_damage: 0.0,
+                ..one
+            },
+            UnitStats {
+                health: 0.0,
+                attack_damage: 0.0,
+                ..three
+            }
+        );
+        assert_close(one.ability_power, 100.0);
+    }
+
+    #[test]
+    fn items_and_traits_add_up() {
+        let items = [
+            item(json!({"AD": 10.0, "AS": 25.0, "Health": 150.0})),
+            item(json!({"AP": 20.0, "CritChance": 15.0, "ManaRegen": 2.0})),
+        ];
+        let traits = Bonus {
+            armor: 30.0,
+            magic_resist: 10.0,
+            attack_speed: 25.0,
+            ..Bonus::default()
+        };
+        let stats = UnitStats::new(&champion(), 1, &items, &traits);
+        assert_eq!(
+            stats,
+            UnitStats {
+                attack_damage: 60.0,
+                ability_power: 120.0,
+                // percent of the base attack speed
+                attack_speed: 0.8 * 1.5,
+                crit_chance: 0.4,
+                crit_multiplier: 1.4,
+                armor: 50.0,
+                magic_resist: 30.0,
+                health: 650.0,
+            }
+        );
+    }
+
+    #[test]
+    fn crit_chance_is_clamped() {
+        let items = [
+            item(json!({"CritChance": 50.0})),
+            item(json!({"CritChance": 50.0})),
+        ];
+        assert_close(
+            UnitStats::new(&champion(), 1, &items, &Bonus::default()).crit_chance,
+            1.0,
+        );
+        let traits = Bonus {
+            crit_chance: -50.0,
+            ..Bonus::default()
+        };
+        assert_close(
+            UnitStats::new(&champion(), 1, &[], &traits).crit_chance,
+            0.0,
+        );
+    }
+
+    #[test]
+    fn attack_dps_averages_crits() {
+        let stats = UnitStats {
+            attack_damage: 100.0,
+            attack_speed: 0.5,
+            crit_chance: 0.5,
+            crit_multiplier: 2.0,
+            ..UnitStats::default()
+        };
+        assert_close(stats.attack_dps(), 75.0);
+        assert_close(
+            UnitStats {
+                crit_chance: 0.0,
+                ..stats
+            }
+            .attack_dps(),
+            50.0,
+        );
+    }
+
+    #[test]
+    fn effective_health_matches_the_simulator() {
+        let stats = UnitStats {
+            health: 1000.0,
+            armor: 100.0,
+            magic_resist: 0.0,
+            ..UnitStats::default()
+        };
+        assert_close(stats.physical_ehp(), 2000.0);
+        assert_close(stats.magic_ehp(), 1000.0);
+        // negative resistances don't make the unit easier to kill than no resistances
+        let shredded = UnitStats {
+            armor: -150.0,
+            magic_resist: -20.0,
+            ..stats
+        };
+        assert_close(shredded.physical_ehp(), 1000.0);
+        assert_close(shredded.magic_ehp(), 1000.0);
+        assert_close(mitigation(50.0), 100.0 / 150.0);
+        assert_close(mitigation(-50.0), 1.0);
+    }
+
+    #[test]
+    fn ability_damage_scales_with_ability_power() {
+        assert_eq!(
+            ability_damage(&champion(), 2, 150.0),
+            [
+                (String::from("Damage"), 450.0),
+                (String::from("BonusDamage"), 112.5)
+            ]
+        );
+        // star levels without a value are left out
+        assert_eq!(ability_damage(&champion(), 4, 100.0), []);
+        let calculation = calculate(&champion(), 1, &[], &Bonus::default());
+        assert_eq!(
+            calculation.ability_damage[0],
+            (String::from("Damage"), 200.0)
+        );
+        assert_close(calculation.physical_ehp, 600.0);
+    }
+}
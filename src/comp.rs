@@ -0,0 +1,71 @@
+use serde::{Deserialize, Serialize};
+
+pub const BOARD_ROWS: u8 = 4;
+pub const BOARD_COLS: u8 = 7;
+
+/// A hex on the player's half of the board. Odd rows are shifted half a hex to the right.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+pub struct BoardPosition {
+    pub row: u8,
+    pub col: u8,
+}
+
+impl BoardPosition {
+    /// Every hex of the board, row by row.
+    pub fn all() -> impl Iterator<Item = BoardPosition> {
+        (0..BOARD_ROWS).flat_map(|row| (0..BOARD_COLS).map(move |col| BoardPosition { row, col }))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompUnit {
+    /// Champion api name.
+    pub champion: String,
+    pub star: u8,
+    /// Item api names.
+    #[serde(default)]
+    pub items: Vec<String>,
+    #[serde(default)]
+    pub position: Option<BoardPosition>,
+}
+
+impl CompUnit {
+    pub fn new(champion: String) -> Self {
+        Self {
+            champion,
+            star: 1,
+            items: vec![],
+            position: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Comp {
+    pub name: String,
+    #[serde(default)]
+    pub units: Vec<CompUnit>,
+    #[serde(default)]
+    pub notes: String,
+}
+
+impl Comp {
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            ..Self::default()
+        }
+    }
+
+    pub fn unit_at(&self, position: BoardPosition) -> Option<&CompUnit> {
+        self.units
+            .iter()
+            .find(|unit| unit.position == Some(position))
+    }
+
+    pub fn unit_at_mut(&mut self, position: BoardPosition) -> Option<&mut CompUnit> {
+        self.units
+            .iter_mut()
+            .find(|unit| unit.position == Some(position))
+    }
+}
@@ -0,0 +1,290 @@
+use std::{
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    database::{LoadOptions, CDRAGON_DATA_URL, ENGLISH, LOCALES},
+    i18n::LANGUAGES,
+    save::write_atomic,
+    serde_help::project_dirs,
+    sort::SortChampMethod,
+};
+
+/// Range of the UI scale factor.
+pub const MIN_SCALE: f64 = 0.5;
+pub const MAX_SCALE: f64 = 3.0;
+
+/// Where the config is stored.
+pub fn config_path() -> PathBuf {
+    project_dirs().config_dir().join("config.toml")
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ThemeName {
+    #[default]
+    Light,
+    Dark,
+}
+
+impl ThemeName {
+    pub const ALL: [ThemeName; 2] = [ThemeName::Light, ThemeName::Dark];
+}
+
+impl Display for ThemeName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ThemeName::Light => write!(f, "Light"),
+            ThemeName::Dark => write!(f, "Dark"),
+        }
+    }
+}
+
+/// Settings of the app, kept between sessions. Keys missing from the file get their default.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub theme: ThemeName,
+    /// Scale factor of the whole UI.
+    pub scale: f64,
+    /// CommunityDragon locale of the game data.
+    pub locale: String,
+    /// Language tag of the UI strings, `None` to follow `locale`.
+    pub ui_language: Option<String>,
+    /// Base URL the `{locale}.json` data files are downloaded from.
+    pub data_url: String,
+    /// Megabytes of cached icons to keep, 0 for no limit.
+    pub cache_size_mb: u64,
+    /// How the champion grid is laid out on start.
+    pub default_sort: SortChampMethod,
+    /// Set mutator or number, `None` for the default set.
+    pub set: Option<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            theme: ThemeName::default(),
+            scale: 1.0,
+            locale: String::from(ENGLISH),
+            ui_language: None,
+            data_url: String::from(CDRAGON_DATA_URL),
+            cache_size_mb: 0,
+            default_sort: SortChampMethod::default(),
+            set: None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(io::Error),
+    Parse(toml::de::Error),
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigError::Io(e) => write!(f, "could not access config file: {e}"),
+            ConfigError::Parse(e) => write!(f, "config file is malformed: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl From<io::Error> for ConfigError {
+    fn from(e: io::Error) -> Self {
+        ConfigError::Io(e)
+    }
+}
+
+impl From<toml::de::Error> for ConfigError {
+    fn from(e: toml::de::Error) -> Self {
+        ConfigError::Parse(e)
+    }
+}
+
+/// A setting that keeps the config from being saved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigProblem {
+    ScaleOutOfRange,
+    UnknownLocale(String),
+    UnknownUiLanguage(String),
+    DataUrlScheme,
+    BlankSet,
+}
+
+impl Display for ConfigProblem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigProblem::ScaleOutOfRange => {
+                write!(f, "scale must be between {MIN_SCALE} and {MAX_SCALE}")
+            }
+            ConfigProblem::UnknownLocale(locale) => write!(f, "unknown locale {locale}"),
+            ConfigProblem::UnknownUiLanguage(language) => {
+                write!(f, "no interface language {language}")
+            }
+            ConfigProblem::DataUrlScheme => {
+                write!(f, "data URL must start with http:// or https://")
+            }
+            ConfigProblem::BlankSet => write!(f, "set can't be blank, leave it unset instead"),
+        }
+    }
+}
+
+impl Config {
+    /// Reads the config, or the defaults if there's no config file yet.
+    pub fn load(path: &Path) -> Result<Self, ConfigError> {
+        match fs::read_to_string(path) {
+            Ok(s) => Ok(toml::from_str(&s)?),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), ConfigError> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let s = toml::to_string_pretty(self).unwrap();
+        write_atomic(path, s.as_bytes())?;
+        Ok(())
+    }
+
+    /// What's wrong with the settings, empty if they're valid. Whether `set` exists is only
+    /// known once the data loads.
+    pub fn problems(&self) -> Vec<ConfigProblem> {
+        let mut problems = vec![];
+        if !(MIN_SCALE..=MAX_SCALE).contains(&self.scale) {
+            problems.push(ConfigProblem::ScaleOutOfRange);
+        }
+        if !LOCALES.contains(&self.locale.as_str()) {
+            problems.push(ConfigProblem::UnknownLocale(self.locale.clone()));
+        }
+        if let Some(language) = &self.ui_language {
+            if !LANGUAGES.iter().any(|(tag, _)| tag == language) {
+                problems.push(ConfigProblem::UnknownUiLanguage(language.clone()));
+            }
+        }
+        if !(self.data_url.starts_with("https://") || self.data_url.starts_with("http://")) {
+            problems.push(ConfigProblem::DataUrlScheme);
+        }
+        if self.set.as_ref().is_some_and(|set| set.trim().is_empty()) {
+            problems.push(ConfigProblem::BlankSet);
+        }
+        problems
+    }
+
+    /// Loads the data the config points to.
+    pub fn load_options(&self, offline: bool) -> LoadOptions {
+        LoadOptions {
+            locale: self.locale.clone(),
+            set: self.set.clone(),
+            offline,
+            data_url: self.data_url.clone(),
+        }
+    }
+
+    /// Limit of the icon cache in bytes, `None` for no limit.
+    pub fn cache_size_bytes(&self) -> Option<u64> {
+        // any size is accepted, one too big to count in bytes is as good as no limit
+        (self.cache_size_mb > 0).then_some(self.cache_size_mb.saturating_mul(1024 * 1024))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path(test: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("tft_config_{}_{test}.toml", std::process::id()))
+    }
+
+    #[test]
+    fn defaults_are_valid() {
+        assert_eq!(Config::default().problems(), []);
+    }
+
+    #[test]
+    fn every_problem_is_reported() {
+        let config = Config {
+            scale: 3.5,
+            locale: String::from("xx_xx"),
+            ui_language: Some(String::from("tlh")),
+            data_url: String::from("ftp://example.com/"),
+            set: Some(String::from("  ")),
+            ..Config::default()
+        };
+        assert_eq!(
+            config.problems(),
+            [
+                ConfigProblem::ScaleOutOfRange,
+                ConfigProblem::UnknownLocale(String::from("xx_xx")),
+                ConfigProblem::UnknownUiLanguage(String::from("tlh")),
+                ConfigProblem::DataUrlScheme,
+                ConfigProblem::BlankSet,
+            ]
+        );
+        let edges = Config {
+            scale: MIN_SCALE,
+            data_url: String::from("http://localhost:8000/"),
+            ..Config::default()
+        };
+        assert_eq!(edges.problems(), []);
+    }
+
+    #[test]
+    fn missing_keys_get_their_default() {
+        let path = path("missing_keys");
+        fs::write(&path, "theme = \"Dark\"\nscale = 1.5\n").unwrap();
+        assert_eq!(
+            Config::load(&path).unwrap(),
+            Config {
+                theme: ThemeName::Dark,
+                scale: 1.5,
+                ..Config::default()
+            }
+        );
+        fs::write(&path, "scale = \"big\"").unwrap();
+        assert!(matches!(Config::load(&path), Err(ConfigError::Parse(_))));
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(Config::load(&path).unwrap(), Config::default());
+    }
+
+    #[test]
+    fn configs_round_trip() {
+        let path = path("round_trip");
+        let config = Config {
+            theme: ThemeName::Dark,
+            scale: 1.25,
+            locale: String::from("de_de"),
+            ui_language: Some(String::from("fr-FR")),
+            cache_size_mb: 200,
+            default_sort: SortChampMethod::Cost,
+            set: Some(String::from("TFTSet9")),
+            ..Config::default()
+        };
+        config.save(&path).unwrap();
+        assert_eq!(Config::load(&path).unwrap(), config);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn cache_sizes_never_overflow() {
+        let cache_size = |cache_size_mb| {
+            Config {
+                cache_size_mb,
+                ..Config::default()
+            }
+            .cache_size_bytes()
+        };
+        assert_eq!(cache_size(0), None);
+        assert_eq!(cache_size(2), Some(2 * 1024 * 1024));
+        assert_eq!(cache_size(u64::MAX), Some(u64::MAX));
+    }
+}
@@ -0,0 +1,420 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    fs, io,
+    path::PathBuf,
+};
+
+use serde_json::Value;
+
+use crate::{
+    serde_help::{cache_dir, cached_image_path},
+    share_code::PlannerIndex,
+    tft_data::*,
+};
+
+/// Where the `{locale}.json` data files are downloaded from by default.
+pub const CDRAGON_DATA_URL: &str = "https://raw.communitydragon.org/latest/cdragon/tft/";
+const CDRAGON_TEAM_PLANNER_URL: &str = "https://raw.communitydragon.org/latest/plugins/rcp-be-lol-game-data/global/default/v1/tftchampions-teamplanner.json";
+
+/// Locales CommunityDragon publishes the data in.
+pub const LOCALES: &[&str] = &[
+    "en_us", "cs_cz", "de_de", "el_gr", "es_es", "es_mx", "fr_fr", "hu_hu", "it_it", "ja_jp",
+    "ko_kr", "pl_pl", "pt_br", "ro_ro", "ru_ru", "th_th", "tr_tr", "vi_vn", "zh_cn", "zh_tw",
+];
+
+/// The locale the game data is written in, and that names are matched in next to the loaded one.
+pub const ENGLISH: &str = "en_us";
+
+/// Where and how to load the data from.
+#[derive(Debug, Clone)]
+pub struct LoadOptions {
+    /// CommunityDragon locale, e.g. `en_us` or `de_de`.
+    pub locale: String,
+    /// Set mutator (`TFTSet8_Stage2`) or set number (`8`). The default is set 8 stage 2.
+    pub set: Option<String>,
+    /// Only read the data cached by an earlier online load.
+    pub offline: bool,
+    /// Base URL of the data files, e.g. a mirror or a specific patch of CommunityDragon.
+    pub data_url: String,
+}
+
+impl Default for LoadOptions {
+    fn default() -> Self {
+        Self {
+            locale: String::from(ENGLISH),
+            set: None,
+            offline: false,
+            data_url: String::from(CDRAGON_DATA_URL),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum LoadError {
+    Network(String),
+    Io(io::Error),
+    Json(serde_json::Error),
+    /// `--offline` without a cached copy of the locale.
+    NotCached(String),
+    UnknownSet(String),
+    /// Valid JSON that isn't a CommunityDragon TFT dump.
+    Malformed(String),
+}
+
+impl Display for LoadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LoadError::Network(e) => write!(f, "could not download the data: {e}"),
+            LoadError::Io(e) => write!(f, "{e}"),
+            LoadError::Json(e) => write!(f, "invalid data: {e}"),
+            LoadError::NotCached(locale) => {
+                write!(f, "no cached data for locale {locale}, load it online once")
+            }
+            LoadError::UnknownSet(set) => write!(f, "no set named {set}"),
+            LoadError::Malformed(reason) => {
+                write!(f, "not a CommunityDragon TFT data file: {reason}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for LoadError {}
+
+impl From<io::Error> for LoadError {
+    fn from(e: io::Error) -> Self {
+        LoadError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for LoadError {
+    fn from(e: serde_json::Error) -> Self {
+        LoadError::Json(e)
+    }
+}
+
+/// Where the last online load of `locale` is cached.
+pub fn cached_data_path(locale: &str) -> PathBuf {
+    cache_dir().join(format!("cdragon_{locale}.json"))
+}
+
+/// The cached data an online load replaced when the download differed, i.e. the previous patch.
+pub fn previous_data_path(locale: &str) -> PathBuf {
+    cache_dir().join(format!("cdragon_{locale}.previous.json"))
+}
+
+/// Downloads the data of a locale and caches it, or reads the cache when offline. A download that
+/// differs from the cache moves the cached copy to `previous_data_path`.
+fn fetch(data_url: &str, locale: &str, offline: bool) -> Result<String, LoadError> {
+    let cache_path = cached_data_path(locale);
+    if offline {
+        return fs::read_to_string(&cache_path)
+            .map_err(|_| LoadError::NotCached(locale.to_string()));
+    }
+    let base = data_url.trim_end_matches('/');
+    let f = ureq::get(&format!("{base}/{locale}.json"))
+        .call()
+        .map_err(|e| LoadError::Network(e.to_string()))?
+        .into_string()?;
+    if fs::read_to_string(&cache_path).is_ok_and(|cached| cached != f) {
+        fs::rename(&cache_path, previous_data_path(locale))?;
+    }
+    fs::write(&cache_path, &f)?;
+    Ok(f)
+}
+
+/// Api name to name of every champion, trait and item in the data, across all sets.
+fn names(json: &Value) -> HashMap<String, String> {
+    fn entries(value: Option<&Value>) -> impl Iterator<Item = &Value> {
+        value.and_then(Value::as_array).into_iter().flatten()
+    }
+    let set_entries = entries(json.get("setData"))
+        .flat_map(|set| entries(set.get("champions")).chain(entries(set.get("traits"))));
+    entries(json.get("items"))
+        .chain(set_entries)
+        .filter_map(|entry| {
+            let api_name = entry.get("apiName")?.as_str()?;
+            let name = entry.get("name")?.as_str()?;
+            Some((api_name.to_string(), name.to_string()))
+        })
+        .collect()
+}
+
+/// The augments in the set's own list when the data has one. Otherwise the ones named after the
+/// set, plus the set-less `TFT_Augment_` ones.
+fn set_augments(set: &Value, set_id: &str, all_items: &[Item]) -> Vec<Augment> {
+    let listed: Option<HashSet<&str>> = set
+        .get("augments")
+        .and_then(Value::as_array)
+        .map(|augments| augments.iter().filter_map(Value::as_str).collect());
+    let set_prefix = format!("{set_id}_").replace("Set", "");
+    let mut augments: Vec<Augment> = all_items
+        .iter()
+        .filter(|item| Augment::is_augment(item))
+        .filter(|item| match &listed {
+            Some(listed) => listed.contains(item.api_name.as_str()),
+            None => {
+                item.api_name.starts_with(&set_prefix) || item.api_name.starts_with("TFT_Augment")
+            }
+        })
+        .cloned()
+        .map(Augment::from_item)
+        .collect();
+    augments.sort_by(|a, b| a.name.cmp(&b.name).then(a.tier.cmp(&b.tier)));
+    augments.dedup_by(|a, b| a.api_name == b.api_name);
+    augments
+}
+
+/// Champions, items and traits parsed out of CommunityDragon's TFT data dump.
+#[derive(Debug, Default, Clone)]
+pub struct TftDatabase {
+    pub champions: Vec<Champion>,
+    /// Standard completed items.
+    pub items: Vec<Item>,
+    /// Components used by `items`.
+    pub components: Vec<Item>,
+    pub traits: Vec<Trait>,
+    /// Augments of the set, sorted by name.
+    pub augments: Vec<Augment>,
+    /// Team planner codes of `champions`.
+    pub planner: PlannerIndex,
+    /// Api name to English name, when the data is in another locale.
+    pub english_names: HashMap<String, String>,
+}
+
+impl TftDatabase {
+    /// Cache paths of every icon the data shows.
+    pub fn icon_paths(&self) -> HashSet<PathBuf> {
+        let champion_icons = self
+            .champions
+            .iter()
+            .flat_map(|champ| [&champ.square_icon, &champ.ability.icon]);
+        let item_icons = self
+            .items
+            .iter()
+            .chain(self.components.iter())
+            .map(|item| &item.icon);
+        let trait_icons = self.traits.iter().map(|t| &t.icon);
+        let augment_icons = self.augments.iter().map(|augment| &augment.icon);
+        champion_icons
+            .chain(item_icons)
+            .chain(trait_icons)
+            .chain(augment_icons)
+            .map(|icon| cached_image_path(&icon.url))
+            .collect()
+    }
+
+    /// Downloads the data and caches it for offline use, or reads the cache when offline.
+    pub fn load_with(options: &LoadOptions) -> Result<Self, LoadError> {
+        let f = fetch(&options.data_url, &options.locale, options.offline)?;
+        let json: Value = serde_json::from_str(&f)?;
+        let mut db = Self::from_json_set(&json, options.set.as_deref())?;
+        if options.locale != ENGLISH {
+            // searching by English name is a nicety, the data loads without it
+            db.english_names = fetch(&options.data_url, ENGLISH, options.offline)
+                .ok()
+                .and_then(|english| serde_json::from_str::<Value>(&english).ok())
+                .map(|english| names(&english))
+                .unwrap_or_default();
+        }
+        if options.offline {
+            return Ok(db);
+        }
+
+        // the planner json is optional, without it planner codes are refused
+        let planner = ureq::get(CDRAGON_TEAM_PLANNER_URL)
+            .call()
+            .ok()
+            .and_then(|response| response.into_string().ok())
+            .and_then(|planner| serde_json::from_str::<Value>(&planner).ok())
+            .and_then(|planner| PlannerIndex::from_json(&planner, &db.planner.set));
+        if let Some(planner) = planner {
+            db.planner = planner;
+        }
+        Ok(db)
+    }
+
+    /// Parses the given set, matched by mutator or number.
+    pub fn from_json_set(json: &Value, set: Option<&str>) -> Result<Self, LoadError> {
+        let malformed = |reason: &str| LoadError::Malformed(reason.to_string());
+        let obj = json
+            .as_object()
+            .ok_or_else(|| malformed("expected an object"))?;
+
+        let set_data = obj
+            .get("setData")
+            .and_then(Value::as_array)
+            .ok_or_else(|| malformed("no setData list"))?;
+        let unknown_set = || LoadError::UnknownSet(set.unwrap_or("8 stage 2").to_string());
+        let set = match set {
+            // access tft set 8 stage 2
+            None => set_data.get(18).ok_or_else(unknown_set)?,
+            // several entries can share a number, the last one is used
+            Some(set) => set_data
+                .iter()
+                .rev()
+                .find(|set_entry| {
+                    set_entry
+                        .get("mutator")
+                        .and_then(Value::as_str)
+                        .is_some_and(|mutator| mutator.eq_ignore_ascii_case(set))
+                        || set_entry
+                            .get("number")
+                            .and_then(Value::as_u64)
+                            .is_some_and(|number| number.to_string() == set)
+                })
+                .ok_or_else(unknown_set)?,
+        };
+
+        let set_id = format!(
+            "TFTSet{}",
+            set.get("number")
+                .and_then(Value::as_u64)
+                .unwrap_or_default()
+        );
+
+        let field = |value: &Value, name: &str| {
+            value
+                .get(name)
+                .cloned()
+                .ok_or_else(|| malformed(&format!("no {name} list")))
+        };
+        let mut champions: Vec<Champion> = serde_json::from_value(field(set, "champions")?)?;
+        // remove champions that have no traits (eggs, creeps, etc.)
+        champions.retain(|champ| !champ.traits.is_empty());
+
+        let traits: Vec<Trait> = serde_json::from_value(field(set, "traits")?)?;
+
+        let all_items: Vec<Item> = serde_json::from_value(field(json, "items")?)?;
+        let augments = set_augments(set, &set_id, &all_items);
+        let mut items = all_items.clone();
+        // only keep items composed of other items (standard completed items)
+        items.retain(|item| !item.composition.is_empty());
+        // remove items that are exclusive to particular sets
+        items.retain(|item| {
+            !(item.api_name.contains('5') // remove set 5 exclusives
+                || item.api_name.contains('6') // remove set 6 exclusives
+                || item.api_name.contains('7') // remove set 7 exclusives
+                || item.name.contains("tft_item_name") // remove special tft items
+                || item.composition.iter().any(|component| component.contains("Tutorial")))
+        });
+
+        let mut set = HashSet::new();
+        for item in items.iter() {
+            for component in item.composition.iter() {
+                set.insert(component.clone());
+            }
+        }
+        let components = set
+            .into_iter()
+            .map(|component_api_name| {
+                all_items
+                    .iter()
+                    .find(|item| item.api_name == component_api_name)
+                    .cloned()
+                    .ok_or_else(|| malformed(&format!("unknown component {component_api_name}")))
+            })
+            .collect::<Result<_, _>>()?;
+
+        let planner = PlannerIndex::unavailable(&set_id);
+
+        Ok(TftDatabase {
+            champions,
+            items,
+            components,
+            traits,
+            augments,
+            planner,
+            english_names: HashMap::new(),
+        })
+    }
+
+    pub fn champion(&self, api_name: &str) -> Option<&Champion> {
+        self.champions
+            .iter()
+            .find(|champ| champ.api_name == api_name)
+    }
+
+    /// Looks up a completed item or a component.
+    pub fn item(&self, api_name: &str) -> Option<&Item> {
+        self.items
+            .iter()
+            .chain(self.components.iter())
+            .find(|item| item.api_name == api_name)
+    }
+
+    pub fn augment(&self, api_name: &str) -> Option<&Augment> {
+        self.augments
+            .iter()
+            .find(|augment| augment.api_name == api_name)
+    }
+
+    /// The English name of a champion, trait or item when the data is in another locale.
+    pub fn english_name(&self, api_name: &str) -> Option<&str> {
+        self.english_names.get(api_name).map(String::as_str)
+    }
+
+    /// Champions list their traits by display name, so traits are looked up the same way.
+    pub fn trait_named(&self, name: &str) -> Option<&Trait> {
+        self.traits.iter().find(|t| t.name == name)
+    }
+}
+
+#[cfg(test)]
+impl TftDatabase {
+    /// Data with just the given champions and completed items, named after their api names.
+    pub(crate) fn with_names(champions: &[&str], items: &[&str]) -> Self {
+        let champions = champions
+            .iter()
+            .map(|api_name| Champion {
+                api_name: api_name.to_string(),
+                name: api_name.to_string(),
+                ..Champion::default()
+            })
+            .collect();
+        let items = items
+            .iter()
+            .map(|api_name| {
+                let mut item = Item::default();
+                item.api_name = api_name.to_string();
+                item.name = api_name.to_string();
+                item
+            })
+            .collect();
+        Self {
+            champions,
+            items,
+            ..Self::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn wrong_shaped_json_is_an_error() {
+        for json in [
+            json!([1, 2, 3]),
+            json!({ "items": [] }),
+            json!({ "setData": "8" }),
+            json!({ "setData": [{ "number": 8 }], "items": [] }),
+            json!({ "setData": [{ "number": 8, "champions": 3, "traits": [] }], "items": [] }),
+        ] {
+            let result = TftDatabase::from_json_set(&json, Some("8"));
+            assert!(
+                matches!(result, Err(LoadError::Malformed(_) | LoadError::Json(_))),
+                "{json} was accepted"
+            );
+        }
+    }
+
+    #[test]
+    fn missing_sets_are_unknown() {
+        let json = json!({ "setData": [], "items": [] });
+        let result = TftDatabase::from_json_set(&json, Some("8"));
+        assert!(matches!(result, Err(LoadError::UnknownSet(set)) if set == "8"));
+    }
+}
@@ -0,0 +1,105 @@
+use std::collections::HashMap;
+
+use crate::state::{ChampionState, ComponentState};
+
+/// How many of the components in a champion's BIS items are among the held components.
+pub fn held_components(champ: &ChampionState, held: &[ComponentState]) -> usize {
+    let needed = champ
+        .items
+        .iter()
+        .flat_map(|item| item.composition.iter())
+        .fold(HashMap::new(), |mut acc, component| {
+            *acc.entry(component).or_insert(0usize) += 1;
+            acc
+        });
+    held.iter()
+        .filter_map(|component| {
+            needed
+                .get(&component.component.api_name)
+                .map(|count| component.count.min(*count))
+        })
+        .sum()
+}
+
+/// Champions ordered by how many of their BIS components are held, best first. Ties keep the
+/// order of `champs`.
+pub fn rank_champions<'a>(
+    champs: &'a [ChampionState],
+    held: &[ComponentState],
+) -> Vec<(&'a ChampionState, usize)> {
+    let mut ranked: Vec<(&ChampionState, usize)> = champs
+        .iter()
+        .map(|champ| (champ, held_components(champ, held)))
+        .collect();
+    ranked.sort_by(|(_, a), (_, b)| b.cmp(a));
+    ranked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tft_data::{Champion, Item};
+
+    const TEAR: &str = "TFT_Item_TearOfTheGoddess";
+    const SWORD: &str = "TFT_Item_BFSword";
+    const ROD: &str = "TFT_Item_NeedlesslyLargeRod";
+
+    fn item(composition: &[&str]) -> Item {
+        let mut item = Item::default();
+        item.composition = composition.iter().map(|c| c.to_string()).collect();
+        item
+    }
+
+    fn champ(api_name: &str, items: Vec<Item>) -> ChampionState {
+        ChampionState {
+            champ: Champion {
+                api_name: api_name.to_string(),
+                ..Champion::default()
+            },
+            items,
+        }
+    }
+
+    fn held(components: &[(&str, usize)]) -> Vec<ComponentState> {
+        components
+            .iter()
+            .map(|(api_name, count)| {
+                let mut component = Item::default();
+                component.api_name = api_name.to_string();
+                ComponentState {
+                    component,
+                    count: *count,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn champions_needing_the_held_components_come_first() {
+        let champs = [
+            champ("TFT9_Ahri", vec![item(&[TEAR, ROD]), item(&[ROD, ROD])]),
+            champ("TFT9_Zed", vec![item(&[SWORD, SWORD])]),
+            champ("TFT9_Sona", vec![item(&[TEAR, TEAR])]),
+            champ("TFT9_Vi", vec![]),
+            champ("TFT9_Lux", vec![item(&[TEAR, ROD])]),
+        ];
+        // a component counts at most as often as the BIS items need it
+        let held = held(&[(TEAR, 2), (ROD, 1), (SWORD, 0)]);
+        let ranked: Vec<(&str, usize)> = rank_champions(&champs, &held)
+            .into_iter()
+            .map(|(champ, count)| (champ.champ.api_name.as_str(), count))
+            .collect();
+        // ties keep the order they came in
+        assert_eq!(
+            ranked,
+            [
+                ("TFT9_Ahri", 2),
+                ("TFT9_Sona", 2),
+                ("TFT9_Lux", 2),
+                ("TFT9_Zed", 0),
+                ("TFT9_Vi", 0),
+            ]
+        );
+        assert!(rank_champions(&[], &held).is_empty());
+    }
+}
@@ -0,0 +1,576 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Display,
+    fs,
+    path::Path,
+};
+
+use itertools::Itertools;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{
+    database::{LoadError, TftDatabase},
+    tft_data::{format_number, Champion, Item, Trait},
+};
+
+/// Variable names where a lower value is the better one.
+const LOWER_IS_BETTER: &[&str] = &["cooldown", "delay", "casttime", "manacost"];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+pub enum Direction {
+    Buff,
+    Nerf,
+    /// Both, or no obvious better value.
+    Adjusted,
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Direction::Buff => write!(f, "buff"),
+            Direction::Nerf => write!(f, "nerf"),
+            Direction::Adjusted => write!(f, "adjusted"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+pub enum Category {
+    Champion,
+    Trait,
+    Item,
+}
+
+impl Display for Category {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Category::Champion => write!(f, "Champion"),
+            Category::Trait => write!(f, "Trait"),
+            Category::Item => write!(f, "Item"),
+        }
+    }
+}
+
+/// One changed value of a champion, trait or item.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Change {
+    pub category: Category,
+    /// Display name of the champion, trait or item.
+    pub name: String,
+    /// What changed, e.g. `Health` or `Ability Damage`.
+    pub field: String,
+    pub old: String,
+    pub new: String,
+    pub direction: Direction,
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {}: {} -> {} ({})",
+            self.name, self.field, self.old, self.new, self.direction
+        )
+    }
+}
+
+/// Everything that changed between two data snapshots.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct PatchDiff {
+    pub changes: Vec<Change>,
+    pub added: Vec<(Category, String)>,
+    pub removed: Vec<(Category, String)>,
+}
+
+impl PatchDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty() && self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+impl Display for PatchDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "no changes");
+        }
+        for (category, name) in self.added.iter() {
+            writeln!(f, "+ {category} {name}")?;
+        }
+        for (category, name) in self.removed.iter() {
+            writeln!(f, "- {category} {name}")?;
+        }
+        for change in self.changes.iter() {
+            writeln!(f, "  {change}")?;
+        }
+        Ok(())
+    }
+}
+
+fn format_values(values: &[f64]) -> String {
+    if values.is_empty() {
+        return String::from("-");
+    }
+    values
+        .iter()
+        .map(|value| format_number(*value))
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Whether values went up or down, `None` if they are the same.
+fn direction(old: &[f64], new: &[f64], lower_is_better: bool) -> Option<Direction> {
+    let same = old.len() == new.len()
+        && old
+            .iter()
+            .zip(new.iter())
+            .all(|(a, b)| (a - b).abs() < 1e-9);
+    if same {
+        return None;
+    }
+    if old.len() != new.len() {
+        return Some(Direction::Adjusted);
+    }
+    let up = old.iter().zip(new.iter()).any(|(a, b)| b > a);
+    let down = old.iter().zip(new.iter()).any(|(a, b)| b < a);
+    Some(match (up != lower_is_better, up && down) {
+        (_, true) => Direction::Adjusted,
+        (true, false) => Direction::Buff,
+        (false, false) => Direction::Nerf,
+    })
+}
+
+fn lower_is_better(name: &str) -> bool {
+    let name = name.to_lowercase();
+    LOWER_IS_BETTER.iter().any(|part| name.contains(part))
+}
+
+/// Collects the changes of one champion, trait or item.
+struct Changes<'a> {
+    changes: &'a mut Vec<Change>,
+    category: Category,
+    name: &'a str,
+}
+
+impl Changes<'_> {
+    fn push(&mut self, field: &str, old: String, new: String, direction: Direction) {
+        self.changes.push(Change {
+            category: self.category,
+            name: self.name.to_string(),
+            field: field.to_string(),
+            old,
+            new,
+            direction,
+        });
+    }
+
+    fn values(&mut self, field: &str, old: &[f64], new: &[f64], lower_is_better: bool) {
+        if let Some(direction) = direction(old, new, lower_is_better) {
+            self.push(field, format_values(old), format_values(new), direction);
+        }
+    }
+
+    fn value(&mut self, field: &str, old: Option<f64>, new: Option<f64>, lower_is_better: bool) {
+        let old: Vec<f64> = old.into_iter().collect();
+        let new: Vec<f64> = new.into_iter().collect();
+        self.values(field, &old, &new, lower_is_better);
+    }
+
+    /// Named value lists, such as ability variables, compared name by name.
+    fn named_values(
+        &mut self,
+        prefix: &str,
+        old: &BTreeMap<String, Vec<f64>>,
+        new: &BTreeMap<String, Vec<f64>>,
+    ) {
+        let names: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+        for name in names {
+            let field = format!("{prefix}{name}");
+            let old = old.get(name).map_or(&[][..], Vec::as_slice);
+            let new = new.get(name).map_or(&[][..], Vec::as_slice);
+            self.values(&field, old, new, lower_is_better(name));
+        }
+    }
+}
+
+fn diff_champion(changes: &mut Changes<'_>, old: &Champion, new: &Champion) {
+    if old.cost != new.cost {
+        changes.push(
+            "Cost",
+            old.cost.to_string(),
+            new.cost.to_string(),
+            Direction::Adjusted,
+        );
+    }
+    let (a, b) = (&old.stats, &new.stats);
+    changes.value("Health", a.hp, b.hp, false);
+    changes.value("Attack Damage", a.damage, b.damage, false);
+    changes.value("Attack Speed", a.attack_speed, b.attack_speed, false);
+    changes.value("Crit Chance", a.crit_chance, b.crit_chance, false);
+    changes.value("Armor", a.armor, b.armor, false);
+    changes.value("Magic Resist", a.magic_resist, b.magic_resist, false);
+    changes.value(
+        "Initial Mana",
+        Some(a.initial_mana),
+        Some(b.initial_mana),
+        false,
+    );
+    changes.value("Mana", Some(a.mana), Some(b.mana), true);
+    changes.value("Range", Some(a.range), Some(b.range), false);
+
+    // star levels 1 to 3, CommunityDragon's index 0 is unused
+    let variables = |champ: &Champion| -> BTreeMap<String, Vec<f64>> {
+        champ
+            .ability
+            .variables
+            .iter()
+            .map(|variable| {
+                let values = variable.value.iter().skip(1).take(3).copied().collect();
+                (variable.name.clone(), values)
+            })
+            .collect()
+    };
+    changes.named_values("Ability ", &variables(old), &variables(new));
+}
+
+/// Numeric variables of a trait, keyed by name and breakpoint, e.g. `Damage (3)`. A variable
+/// missing from one breakpoint then can't shift the values of the others.
+fn trait_variables(t: &Trait) -> BTreeMap<String, Vec<f64>> {
+    let mut variables = BTreeMap::new();
+    for effect in t.effects.iter() {
+        for (name, value) in effect.variables.as_object().into_iter().flatten() {
+            if let Some(value) = value.as_f64() {
+                variables.insert(format!("{name} ({})", effect.min_units), vec![value]);
+            }
+        }
+    }
+    variables
+}
+
+fn diff_trait(changes: &mut Changes<'_>, old: &Trait, new: &Trait) {
+    let breakpoints = |t: &Trait| -> Vec<f64> {
+        t.effects
+            .iter()
+            .map(|effect| effect.min_units as f64)
+            .collect()
+    };
+    changes.values("Breakpoints", &breakpoints(old), &breakpoints(new), true);
+    changes.named_values("", &trait_variables(old), &trait_variables(new));
+}
+
+fn diff_item(changes: &mut Changes<'_>, old: &Item, new: &Item) {
+    // components in another order are the same recipe
+    let recipe =
+        |item: &Item| -> Vec<String> { item.composition.iter().cloned().sorted().collect() };
+    if recipe(old) != recipe(new) {
+        changes.push(
+            "Recipe",
+            old.composition.join(" + "),
+            new.composition.join(" + "),
+            Direction::Adjusted,
+        );
+    }
+    let effects = |item: &Item| -> BTreeMap<String, Vec<f64>> {
+        item.effect_values()
+            .into_iter()
+            .map(|(name, value)| (name, vec![value]))
+            .collect()
+    };
+    changes.named_values("", &effects(old), &effects(new));
+}
+
+/// Pairs up entries by api name, recording the ones only one side has.
+fn diff_entries<T>(
+    diff: &mut PatchDiff,
+    category: Category,
+    old: &[T],
+    new: &[T],
+    api_name: impl Fn(&T) -> &str,
+    name: impl Fn(&T) -> &str,
+    compare: impl Fn(&mut Changes<'_>, &T, &T),
+) {
+    for old_entry in old {
+        match new
+            .iter()
+            .find(|entry| api_name(entry) == api_name(old_entry))
+        {
+            Some(new_entry) => {
+                let mut changes = Changes {
+                    changes: &mut diff.changes,
+                    category,
+                    name: name(new_entry),
+                };
+                compare(&mut changes, old_entry, new_entry);
+            }
+            None => diff.removed.push((category, name(old_entry).to_string())),
+        }
+    }
+    for new_entry in new {
+        if !old
+            .iter()
+            .any(|entry| api_name(entry) == api_name(new_entry))
+        {
+            diff.added.push((category, name(new_entry).to_string()));
+        }
+    }
+}
+
+/// Compares two snapshots of the data, `old` being the earlier patch.
+pub fn diff(old: &TftDatabase, new: &TftDatabase) -> PatchDiff {
+    let mut diff = PatchDiff::default();
+    diff_entries(
+        &mut diff,
+        Category::Champion,
+        &old.champions,
+        &new.champions,
+        |champ| &champ.api_name,
+        |champ| &champ.name,
+        diff_champion,
+    );
+    diff_entries(
+        &mut diff,
+        Category::Trait,
+        &old.traits,
+        &new.traits,
+        |t| &t.api_name,
+        |t| &t.name,
+        diff_trait,
+    );
+    let items = |db: &TftDatabase| -> Vec<Item> {
+        db.items
+            .iter()
+            .chain(db.components.iter())
+            .cloned()
+            .collect()
+    };
+    diff_entries(
+        &mut diff,
+        Category::Item,
+        &items(old),
+        &items(new),
+        |item| &item.api_name,
+        |item| &item.name,
+        diff_item,
+    );
+    diff
+}
+
+/// Parses a saved CommunityDragon dump, such as `database::previous_data_path`.
+pub fn load_snapshot(path: &Path, set: Option<&str>) -> Result<TftDatabase, LoadError> {
+    let json: Value = serde_json::from_str(&fs::read_to_string(path)?)?;
+    TftDatabase::from_json_set(&json, set)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::tft_data::{Ability, Stats, TraitEffect, Variable};
+
+    #[test]
+    fn directions() {
+        use Direction::*;
+        /// Old and new values, whether lower is better, and the direction.
+        type Case = (&'static [f64], &'static [f64], bool, Option<Direction>);
+        let cases: &[Case] = &[
+            (&[1.0], &[1.0], false, None),
+            (&[1.0, 2.0], &[1.0, 2.0 + 1e-12], false, None),
+            (&[], &[], false, None),
+            (&[1.0], &[2.0], false, Some(Buff)),
+            (&[2.0], &[1.0], false, Some(Nerf)),
+            (&[1.0, 2.0], &[1.0, 3.0], false, Some(Buff)),
+            (&[1.0, 2.0], &[2.0, 1.0], false, Some(Adjusted)),
+            // a shorter cooldown is a buff
+            (&[10.0], &[8.0], true, Some(Buff)),
+            (&[8.0], &[10.0], true, Some(Nerf)),
+            (&[1.0, 2.0], &[2.0, 1.0], true, Some(Adjusted)),
+            // a value appearing or disappearing has no better side
+            (&[], &[1.0], false, Some(Adjusted)),
+            (&[1.0], &[], false, Some(Adjusted)),
+        ];
+        for (old, new, lower_is_better, expected) in cases {
+            assert_eq!(
+                direction(old, new, *lower_is_better),
+                *expected,
+                "{old:?} -> {new:?}"
+            );
+        }
+        assert!(lower_is_better("ManaCost"));
+        assert!(lower_is_better("AttackCooldown"));
+        assert!(!lower_is_better("Damage"));
+    }
+
+    fn champion(api_name: &str, hp: f64, damage: [f64; 4], cooldown: [f64; 4]) -> Champion {
+        Champion {
+            api_name: api_name.to_string(),
+            name: api_name.trim_start_matches("TFT9_").to_string(),
+            cost: 2,
+            ability: Ability {
+                variables: vec![
+                    Variable {
+                        name: String::from("Damage"),
+                        value: damage.to_vec(),
+                    },
+                    Variable {
+                        name: String::from("Cooldown"),
+                        value: cooldown.to_vec(),
+                    },
+                ],
+                ..Ability::default()
+            },
+            stats: Stats {
+                hp: Some(hp),
+                mana: 60.0,
+                ..Stats::default()
+            },
+            ..Champion::default()
+        }
+    }
+
+    fn breakpoint(min_units: u32, variables: Value) -> TraitEffect {
+        TraitEffect {
+            min_units,
+            variables,
+            ..TraitEffect::default()
+        }
+    }
+
+    fn item(api_name: &str, composition: &[&str], effects: Value) -> Item {
+        let mut item = Item::default();
+        item.api_name = api_name.to_string();
+        item.name = api_name.trim_start_matches("TFT_Item_").to_string();
+        item.composition = composition.iter().map(|c| c.to_string()).collect();
+        item.effects = effects;
+        item
+    }
+
+    fn change(diff: &PatchDiff, name: &str, field: &str) -> Option<(String, String, Direction)> {
+        diff.changes
+            .iter()
+            .find(|change| change.name == name && change.field == field)
+            .map(|change| (change.old.clone(), change.new.clone(), change.direction))
+    }
+
+    fn changed(old: &str, new: &str, direction: Direction) -> Option<(String, String, Direction)> {
+        Some((old.to_string(), new.to_string(), direction))
+    }
+
+    #[test]
+    fn snapshots_are_compared() {
+        let old = TftDatabase {
+            champions: vec![
+                champion("TFT9_Ahri", 500.0, [0.0, 200.0, 300.0, 450.0], [0.0; 4]),
+                champion("TFT9_Gone", 500.0, [0.0; 4], [0.0; 4]),
+            ],
+            traits: vec![Trait {
+                api_name: String::from("Set9_Sorcerer"),
+                name: String::from("Sorcerer"),
+                effects: vec![
+                    breakpoint(2, json!({"AP": 20.0, "Shield": 100.0})),
+                    breakpoint(4, json!({"AP": 50.0})),
+                    breakpoint(6, json!({"AP": 80.0, "Shield": 300.0})),
+                ],
+                ..Trait::default()
+            }],
+            items: vec![item(
+                "TFT_Item_Shojin",
+                &["TFT_Item_Tear", "TFT_Item_Sword"],
+                json!({"AD": 15.0, "ManaRestore": 5.0}),
+            )],
+            ..TftDatabase::default()
+        };
+        let new = TftDatabase {
+            champions: vec![
+                champion("TFT9_Ahri", 550.0, [0.0, 200.0, 280.0, 500.0], [0.0; 4]),
+                champion("TFT9_New", 500.0, [0.0; 4], [0.0; 4]),
+            ],
+            traits: vec![Trait {
+                api_name: String::from("Set9_Sorcerer"),
+                name: String::from("Sorcerer"),
+                effects: vec![
+                    breakpoint(2, json!({"AP": 20.0, "Shield": 100.0})),
+                    breakpoint(4, json!({"AP": 50.0})),
+                    breakpoint(7, json!({"AP": 80.0, "Shield": 250.0})),
+                ],
+                ..Trait::default()
+            }],
+            // the same recipe in another order is no change
+            items: vec![item(
+                "TFT_Item_Shojin",
+                &["TFT_Item_Sword", "TFT_Item_Tear"],
+                json!({"AD": 15.0, "ManaRestore": 10.0}),
+            )],
+            ..TftDatabase::default()
+        };
+        let diff = diff(&old, &new);
+        assert_eq!(diff.added, [(Category::Champion, String::from("New"))]);
+        assert_eq!(diff.removed, [(Category::Champion, String::from("Gone"))]);
+
+        use Direction::*;
+        assert_eq!(change(&diff, "Ahri", "Health"), changed("500", "550", Buff));
+        // one value per star level
+        assert_eq!(
+            change(&diff, "Ahri", "Ability Damage"),
+            changed("200/300/450", "200/280/500", Adjusted)
+        );
+        assert_eq!(change(&diff, "Ahri", "Ability Cooldown"), None);
+        assert_eq!(
+            change(&diff, "Sorcerer", "Breakpoints"),
+            changed("2/4/6", "2/4/7", Nerf)
+        );
+        // the 4 breakpoint has no shield, the values are still matched per breakpoint
+        assert_eq!(change(&diff, "Sorcerer", "Shield (2)"), None);
+        assert_eq!(
+            change(&diff, "Sorcerer", "Shield (6)"),
+            changed("300", "-", Adjusted)
+        );
+        assert_eq!(
+            change(&diff, "Sorcerer", "Shield (7)"),
+            changed("-", "250", Adjusted)
+        );
+        assert_eq!(change(&diff, "Shojin", "Recipe"), None);
+        assert_eq!(
+            change(&diff, "Shojin", "ManaRestore"),
+            changed("5", "10", Buff)
+        );
+        assert_eq!(change(&diff, "Shojin", "AD"), None);
+    }
+
+    #[test]
+    fn recipes_and_lower_is_better_values() {
+        let old = TftDatabase {
+            champions: vec![champion("TFT9_Ahri", 500.0, [0.0; 4], [0.0, 4.0, 4.0, 3.0])],
+            items: vec![item(
+                "TFT_Item_Shojin",
+                &["TFT_Item_Tear", "TFT_Item_Sword"],
+                json!({}),
+            )],
+            ..TftDatabase::default()
+        };
+        let new = TftDatabase {
+            champions: vec![champion("TFT9_Ahri", 500.0, [0.0; 4], [0.0, 3.0, 3.0, 3.0])],
+            items: vec![item(
+                "TFT_Item_Shojin",
+                &["TFT_Item_Tear", "TFT_Item_Bow"],
+                json!({}),
+            )],
+            ..TftDatabase::default()
+        };
+        let diff = diff(&old, &new);
+        assert_eq!(
+            change(&diff, "Ahri", "Ability Cooldown"),
+            changed("4/4/3", "3/3/3", Direction::Buff)
+        );
+        assert_eq!(
+            change(&diff, "Shojin", "Recipe"),
+            changed(
+                "TFT_Item_Tear + TFT_Item_Sword",
+                "TFT_Item_Tear + TFT_Item_Bow",
+                Direction::Adjusted
+            )
+        );
+        assert_eq!(diff.changes.len(), 2, "{diff}");
+        assert!(super::diff(&old, &old).is_empty());
+    }
+}
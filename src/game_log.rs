@@ -0,0 +1,315 @@
+use std::{
+    fmt::Display,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{comp::CompUnit, database::TftDatabase, save::write_atomic};
+
+/// One finished game.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameRecord {
+    /// `YYYY-MM-DD`.
+    pub date: String,
+    pub patch: String,
+    pub placement: u8,
+    /// Name of the notebook comp that was played, empty if none was picked.
+    #[serde(default)]
+    pub comp_name: String,
+    /// The final board.
+    #[serde(default)]
+    pub comp: Vec<CompUnit>,
+    /// Augment names in the order they were picked.
+    #[serde(default)]
+    pub augments: Vec<String>,
+    #[serde(default)]
+    pub notes: String,
+}
+
+#[derive(Debug)]
+pub enum GameLogError {
+    Io(io::Error),
+    /// A line of the log that isn't a game record.
+    Json {
+        line: usize,
+        error: serde_json::Error,
+    },
+}
+
+impl Display for GameLogError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GameLogError::Io(e) => write!(f, "{e}"),
+            GameLogError::Json { line, error } => write!(f, "game log line {line}: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for GameLogError {}
+
+impl From<io::Error> for GameLogError {
+    fn from(e: io::Error) -> Self {
+        GameLogError::Io(e)
+    }
+}
+
+/// Games stored as JSON lines, one record per line, oldest first.
+#[derive(Debug, Clone)]
+pub struct GameLog {
+    path: PathBuf,
+}
+
+impl GameLog {
+    pub fn new(path: &Path) -> Self {
+        Self {
+            path: path.to_path_buf(),
+        }
+    }
+
+    /// A missing log has no games.
+    pub fn read(&self) -> Result<Vec<GameRecord>, GameLogError> {
+        let s = match fs::read_to_string(&self.path) {
+            Ok(s) => s,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(e) => return Err(e.into()),
+        };
+        s.lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| {
+                serde_json::from_str(line).map_err(|error| GameLogError::Json {
+                    line: index + 1,
+                    error,
+                })
+            })
+            .collect()
+    }
+
+    pub fn append(&self, record: &GameRecord) -> io::Result<()> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{}", serde_json::to_string(record).unwrap())?;
+        file.sync_all()
+    }
+
+    /// Replaces the whole log, e.g. after deleting a game.
+    pub fn write_all(&self, records: &[GameRecord]) -> io::Result<()> {
+        let s: String = records
+            .iter()
+            .map(|record| serde_json::to_string(record).unwrap() + "\n")
+            .collect();
+        write_atomic(&self.path, s.as_bytes())
+    }
+}
+
+/// Today's UTC date as `YYYY-MM-DD`.
+pub fn today() -> String {
+    let days = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since_epoch| since_epoch.as_secs() / 86_400) as i64;
+    date_from_days(days)
+}
+
+/// The date `days` after 1970-01-01 as `YYYY-MM-DD`.
+fn date_from_days(days: i64) -> String {
+    // civil date from days since 1970-01-01, after Howard Hinnant's `civil_from_days`
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+/// Whether `date` looks like `YYYY-MM-DD`.
+pub fn is_valid_date(date: &str) -> bool {
+    let parts: Vec<&str> = date.split('-').collect();
+    let [year, month, day] = parts.as_slice() else {
+        return false;
+    };
+    let number = |s: &str, len: usize| {
+        (s.len() == len && s.chars().all(|c| c.is_ascii_digit())).then(|| s.parse::<u32>().ok())
+    };
+    matches!(
+        (number(year, 4), number(month, 2), number(day, 2)),
+        (Some(Some(_)), Some(Some(1..=12)), Some(Some(1..=31)))
+    )
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// The games as CSV with one row per game. Champions and items use display names where known.
+pub fn to_csv(records: &[GameRecord], db: &TftDatabase) -> String {
+    let champion_name = |api_name: &str| {
+        db.champion(api_name)
+            .map_or(api_name.to_string(), |champ| champ.name.clone())
+    };
+    let item_name = |api_name: &str| {
+        db.item(api_name)
+            .map_or(api_name.to_string(), |item| item.name.clone())
+    };
+
+    let mut csv = String::from("date,patch,placement,comp,champions,items,augments,notes\n");
+    for record in records {
+        let champions: Vec<String> = record
+            .comp
+            .iter()
+            .map(|unit| format!("{} {}*", champion_name(&unit.champion), unit.star))
+            .collect();
+        let items: Vec<String> = record
+            .comp
+            .iter()
+            .filter(|unit| !unit.items.is_empty())
+            .map(|unit| {
+                let items: Vec<String> = unit.items.iter().map(|item| item_name(item)).collect();
+                format!("{}: {}", champion_name(&unit.champion), items.join(" + "))
+            })
+            .collect();
+        let row = [
+            record.date.clone(),
+            record.patch.clone(),
+            record.placement.to_string(),
+            record.comp_name.clone(),
+            champions.join("; "),
+            items.join("; "),
+            record.augments.join("; "),
+            record.notes.clone(),
+        ];
+        let row: Vec<String> = row.iter().map(|field| csv_field(field)).collect();
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tft_data::Champion;
+
+    use super::*;
+
+    fn record(placement: u8, notes: &str) -> GameRecord {
+        GameRecord {
+            date: String::from("2023-06-30"),
+            patch: String::from("13.12"),
+            placement,
+            comp_name: String::new(),
+            comp: vec![],
+            augments: vec![],
+            notes: notes.to_string(),
+        }
+    }
+
+    fn log(test: &str) -> GameLog {
+        let path =
+            std::env::temp_dir().join(format!("tft_games_{}_{test}.jsonl", std::process::id()));
+        let _ = fs::remove_file(&path);
+        GameLog::new(&path)
+    }
+
+    #[test]
+    fn games_round_trip() {
+        let log = log("round_trip");
+        assert!(log.read().unwrap().is_empty());
+        log.append(&record(1, "first")).unwrap();
+        log.append(&record(5, "second")).unwrap();
+        assert_eq!(
+            log.read().unwrap(),
+            [record(1, "first"), record(5, "second")]
+        );
+
+        log.write_all(&[record(5, "second")]).unwrap();
+        assert_eq!(log.read().unwrap(), [record(5, "second")]);
+        fs::remove_file(&log.path).unwrap();
+    }
+
+    #[test]
+    fn bad_lines_report_their_line_number() {
+        let log = log("bad_lines");
+        let game = serde_json::to_string(&record(2, "")).unwrap();
+        fs::write(&log.path, format!("{game}\n\n  \n{game}\n")).unwrap();
+        assert_eq!(log.read().unwrap().len(), 2, "blank lines are skipped");
+
+        fs::write(&log.path, format!("{game}\n\n{{\"date\": 3}}\n{game}\n")).unwrap();
+        assert!(matches!(
+            log.read(),
+            Err(GameLogError::Json { line: 3, .. })
+        ));
+        fs::remove_file(&log.path).unwrap();
+    }
+
+    #[test]
+    fn dates_are_checked() {
+        assert!(is_valid_date("2023-06-30"));
+        for date in [
+            "",
+            "2023-6-30",
+            "2023-13-01",
+            "2023-00-10",
+            "2023-06-32",
+            "23-06-30",
+            "2023-06-30-01",
+            "2023/06/30",
+            "2023-+6-30",
+        ] {
+            assert!(!is_valid_date(date), "{date}");
+        }
+    }
+
+    #[test]
+    fn days_become_dates() {
+        assert_eq!(date_from_days(0), "1970-01-01");
+        assert_eq!(date_from_days(-1), "1969-12-31");
+        assert_eq!(date_from_days(11_017), "2000-03-01");
+        assert_eq!(date_from_days(19_538), "2023-06-30");
+        assert_eq!(date_from_days(19_782), "2024-02-29");
+        assert!(is_valid_date(&today()));
+    }
+
+    #[test]
+    fn csv_fields_are_quoted() {
+        let db = TftDatabase {
+            champions: vec![Champion {
+                api_name: String::from("TFT9_Ahri"),
+                name: String::from("Ahri"),
+                ..Champion::default()
+            }],
+            ..TftDatabase::default()
+        };
+        let mut game = record(1, "said \"gg\", then\nleft");
+        game.comp_name = String::from("Ahri, reroll");
+        game.comp = vec![CompUnit {
+            star: 3,
+            items: vec![
+                String::from("TFT_Item_BlueBuff"),
+                String::from("TFT_Item_Gone"),
+            ],
+            ..CompUnit::new(String::from("TFT9_Ahri"))
+        }];
+        game.augments = vec![String::from("Jeweled Lotus")];
+        assert_eq!(
+            to_csv(&[game], &db),
+            "date,patch,placement,comp,champions,items,augments,notes\n\
+             2023-06-30,13.12,1,\"Ahri, reroll\",Ahri 3*,\
+             Ahri: TFT_Item_BlueBuff + TFT_Item_Gone,Jeweled Lotus,\
+             \"said \"\"gg\"\", then\nleft\"\n"
+        );
+    }
+}
@@ -0,0 +1,342 @@
+use crate::comp::{BoardPosition, CompUnit};
+
+/// How many edits can be undone.
+const HISTORY_LIMIT: usize = 200;
+
+/// A reversible edit. Champions, items and components are referred to by api name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Edit {
+    AddItem {
+        champion: String,
+        item: String,
+        index: usize,
+    },
+    RemoveItem {
+        champion: String,
+        item: String,
+        index: usize,
+    },
+    ClearItems {
+        champion: String,
+        items: Vec<String>,
+    },
+    /// Undoes `ClearItems`.
+    RestoreItems {
+        champion: String,
+        items: Vec<String>,
+    },
+    /// Swaps a champion's whole BIS list, e.g. for one backed by match stats.
+    ReplaceItems {
+        champion: String,
+        from: Vec<String>,
+        to: Vec<String>,
+    },
+    ComponentCount {
+        component: String,
+        from: usize,
+        to: usize,
+    },
+    /// Moves the unit of the comp at index `comp` between two hexes.
+    BoardMove {
+        comp: usize,
+        from: BoardPosition,
+        to: BoardPosition,
+    },
+    /// Puts a unit on its hex of the comp at index `comp`.
+    PlaceUnit { comp: usize, unit: CompUnit },
+    /// Takes a unit off the board. Undoes `PlaceUnit`.
+    RemoveUnit { comp: usize, unit: CompUnit },
+    /// Changes the star level of the unit on a hex.
+    UnitStar {
+        comp: usize,
+        position: BoardPosition,
+        from: u8,
+        to: u8,
+    },
+    /// Swaps the items of the unit on a hex.
+    UnitItems {
+        comp: usize,
+        position: BoardPosition,
+        from: Vec<String>,
+        to: Vec<String>,
+    },
+}
+
+impl Edit {
+    /// The edit that undoes this one.
+    pub fn inverse(&self) -> Edit {
+        match self.clone() {
+            Edit::AddItem {
+                champion,
+                item,
+                index,
+            } => Edit::RemoveItem {
+                champion,
+                item,
+                index,
+            },
+            Edit::RemoveItem {
+                champion,
+                item,
+                index,
+            } => Edit::AddItem {
+                champion,
+                item,
+                index,
+            },
+            Edit::ClearItems { champion, items } => Edit::RestoreItems { champion, items },
+            Edit::RestoreItems { champion, items } => Edit::ClearItems { champion, items },
+            Edit::ReplaceItems { champion, from, to } => Edit::ReplaceItems {
+                champion,
+                from: to,
+                to: from,
+            },
+            Edit::ComponentCount {
+                component,
+                from,
+                to,
+            } => Edit::ComponentCount {
+                component,
+                from: to,
+                to: from,
+            },
+            Edit::BoardMove { comp, from, to } => Edit::BoardMove {
+                comp,
+                from: to,
+                to: from,
+            },
+            Edit::PlaceUnit { comp, unit } => Edit::RemoveUnit { comp, unit },
+            Edit::RemoveUnit { comp, unit } => Edit::PlaceUnit { comp, unit },
+            Edit::UnitStar {
+                comp,
+                position,
+                from,
+                to,
+            } => Edit::UnitStar {
+                comp,
+                position,
+                from: to,
+                to: from,
+            },
+            Edit::UnitItems {
+                comp,
+                position,
+                from,
+                to,
+            } => Edit::UnitItems {
+                comp,
+                position,
+                from: to,
+                to: from,
+            },
+        }
+    }
+}
+
+/// Undo and redo stacks of applied edits.
+#[derive(Debug, Clone, Default)]
+pub struct History {
+    undo: Vec<Edit>,
+    redo: Vec<Edit>,
+}
+
+impl History {
+    /// Records an edit that was just applied. Anything that was undone can't be redone anymore.
+    pub fn record(&mut self, edit: Edit) {
+        self.redo.clear();
+        self.undo.push(edit);
+        if self.undo.len() > HISTORY_LIMIT {
+            self.undo.remove(0);
+        }
+    }
+
+    /// Returns the edit to apply to undo the last edit.
+    pub fn undo(&mut self) -> Option<Edit> {
+        let edit = self.undo.pop()?;
+        let inverse = edit.inverse();
+        self.redo.push(edit);
+        Some(inverse)
+    }
+
+    /// Returns the edit to apply to redo the last undone edit.
+    pub fn redo(&mut self) -> Option<Edit> {
+        let edit = self.redo.pop()?;
+        self.undo.push(edit.clone());
+        Some(edit)
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const A1: BoardPosition = BoardPosition { row: 0, col: 0 };
+    const D7: BoardPosition = BoardPosition { row: 3, col: 6 };
+
+    /// Which variant an edit is. Without a wildcard arm, a new variant fails to compile here
+    /// until `one_of_each` covers it.
+    fn variant(edit: &Edit) -> usize {
+        match edit {
+            Edit::AddItem { .. } => 0,
+            Edit::RemoveItem { .. } => 1,
+            Edit::ClearItems { .. } => 2,
+            Edit::RestoreItems { .. } => 3,
+            Edit::ReplaceItems { .. } => 4,
+            Edit::ComponentCount { .. } => 5,
+            Edit::BoardMove { .. } => 6,
+            Edit::PlaceUnit { .. } => 7,
+            Edit::RemoveUnit { .. } => 8,
+            Edit::UnitStar { .. } => 9,
+            Edit::UnitItems { .. } => 10,
+        }
+    }
+
+    fn one_of_each() -> Vec<Edit> {
+        let champion = || String::from("TFT9_Ahri");
+        let items = || {
+            vec![
+                String::from("TFT_Item_BlueBuff"),
+                String::from("TFT_Item_Morellonomicon"),
+            ]
+        };
+        let unit = || CompUnit {
+            star: 2,
+            items: items(),
+            position: Some(D7),
+            ..CompUnit::new(champion())
+        };
+        vec![
+            Edit::AddItem {
+                champion: champion(),
+                item: String::from("TFT_Item_BlueBuff"),
+                index: 1,
+            },
+            Edit::RemoveItem {
+                champion: champion(),
+                item: String::from("TFT_Item_BlueBuff"),
+                index: 0,
+            },
+            Edit::ClearItems {
+                champion: champion(),
+                items: items(),
+            },
+            Edit::RestoreItems {
+                champion: champion(),
+                items: items(),
+            },
+            Edit::ReplaceItems {
+                champion: champion(),
+                from: items(),
+                to: vec![],
+            },
+            Edit::ComponentCount {
+                component: String::from("TFT_Item_TearOfTheGoddess"),
+                from: 0,
+                to: 2,
+            },
+            Edit::BoardMove {
+                comp: 1,
+                from: A1,
+                to: D7,
+            },
+            Edit::PlaceUnit {
+                comp: 0,
+                unit: unit(),
+            },
+            Edit::RemoveUnit {
+                comp: 2,
+                unit: unit(),
+            },
+            Edit::UnitStar {
+                comp: 0,
+                position: D7,
+                from: 1,
+                to: 3,
+            },
+            Edit::UnitItems {
+                comp: 0,
+                position: A1,
+                from: vec![],
+                to: items(),
+            },
+        ]
+    }
+
+    #[test]
+    fn every_variant_is_covered() {
+        let mut variants: Vec<usize> = one_of_each().iter().map(variant).collect();
+        variants.dedup();
+        assert_eq!(variants, (0..=10).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn inverses_round_trip() {
+        for edit in one_of_each() {
+            let inverse = edit.inverse();
+            assert_ne!(inverse, edit, "{edit:?} is its own inverse");
+            assert_eq!(inverse.inverse(), edit);
+        }
+    }
+
+    #[test]
+    fn inverses_undo_what_the_edit_did() {
+        let unit = CompUnit::new(String::from("TFT9_Ahri"));
+        assert_eq!(
+            Edit::PlaceUnit {
+                comp: 0,
+                unit: unit.clone()
+            }
+            .inverse(),
+            Edit::RemoveUnit { comp: 0, unit }
+        );
+        assert_eq!(
+            Edit::UnitStar {
+                comp: 0,
+                position: A1,
+                from: 1,
+                to: 2
+            }
+            .inverse(),
+            Edit::UnitStar {
+                comp: 0,
+                position: A1,
+                from: 2,
+                to: 1
+            }
+        );
+    }
+
+    #[test]
+    fn undo_and_redo() {
+        let mut history = History::default();
+        let [first, second] = [5, 6].map(|to| Edit::ComponentCount {
+            component: String::from("TFT_Item_TearOfTheGoddess"),
+            from: to - 1,
+            to,
+        });
+        history.record(first.clone());
+        history.record(second.clone());
+        assert_eq!(history.undo(), Some(second.inverse()));
+        assert_eq!(history.redo(), Some(second.clone()));
+        assert_eq!(history.undo(), Some(second.inverse()));
+        assert_eq!(history.undo(), Some(first.inverse()));
+        assert!(!history.can_undo());
+
+        // a new edit drops the undone ones
+        history.record(first);
+        assert!(!history.can_redo());
+    }
+}
@@ -0,0 +1,282 @@
+use std::fmt::Display;
+
+use fluent::{FluentArgs, FluentBundle, FluentResource, FluentValue};
+use unic_langid::LanguageIdentifier;
+
+use crate::{
+    calc::Stat,
+    config::{ConfigProblem, ThemeName, MAX_SCALE, MIN_SCALE},
+    diff::{Category, Direction},
+    sort::{GroupHeader, SortChampMethod, SortKey},
+    tft_data::ItemKind,
+};
+
+/// The language every message exists in, used for messages missing from the others.
+pub const FALLBACK: &str = "en-US";
+
+/// Languages with a message catalog, by tag and native name.
+pub const LANGUAGES: &[(&str, &str)] = &[
+    ("en-US", "English"),
+    ("de-DE", "Deutsch"),
+    ("es-ES", "Español"),
+    ("fr-FR", "Français"),
+];
+
+const CATALOGS: &[(&str, &str)] = &[
+    ("en-US", include_str!("../i18n/en-US.ftl")),
+    ("de-DE", include_str!("../i18n/de-DE.ftl")),
+    ("es-ES", include_str!("../i18n/es-ES.ftl")),
+    ("fr-FR", include_str!("../i18n/fr-FR.ftl")),
+];
+
+fn bundle(language: &str) -> Option<FluentBundle<FluentResource>> {
+    let (_, source) = CATALOGS.iter().find(|(tag, _)| *tag == language)?;
+    // the catalogs are compiled in, a broken one is a bug
+    let resource = FluentResource::try_new(source.to_string()).unwrap();
+    let id: LanguageIdentifier = language.parse().unwrap();
+    let mut bundle = FluentBundle::new(vec![id]);
+    // iced doesn't render the Unicode isolation marks around arguments
+    bundle.set_use_isolating(false);
+    bundle.add_resource(resource).unwrap();
+    Some(bundle)
+}
+
+/// The UI language matching a CommunityDragon locale, e.g. `de-DE` for `de_de` and `es-ES` for
+/// `es_mx`. Locales without a catalog get the fallback.
+pub fn language_for_locale(locale: &str) -> &'static str {
+    let tag = locale.replace('_', "-");
+    let language = locale.split('_').next().unwrap_or_default();
+    let tags = || LANGUAGES.iter().map(|(tag, _)| *tag);
+    tags()
+        .find(|candidate| candidate.eq_ignore_ascii_case(&tag))
+        .or_else(|| tags().find(|candidate| candidate.split('-').next() == Some(language)))
+        .unwrap_or(FALLBACK)
+}
+
+/// Looks up UI strings in the catalog of one language, falling back to English.
+pub struct Translator {
+    language: String,
+    bundles: Vec<FluentBundle<FluentResource>>,
+}
+
+impl Translator {
+    /// `language` is a tag of `LANGUAGES`. Other languages get the fallback only.
+    pub fn new(language: &str) -> Self {
+        let mut tags = vec![language];
+        if language != FALLBACK {
+            tags.push(FALLBACK);
+        }
+        Self {
+            language: language.to_string(),
+            bundles: tags.into_iter().filter_map(bundle).collect(),
+        }
+    }
+
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+
+    pub fn tr(&self, id: &str) -> String {
+        self.format(id, None)
+    }
+
+    pub fn tr_args(&self, id: &str, args: &[(&str, FluentValue)]) -> String {
+        let mut fluent_args = FluentArgs::new();
+        for (name, value) in args {
+            fluent_args.set(*name, value.clone());
+        }
+        self.format(id, Some(&fluent_args))
+    }
+
+    /// A message missing from every catalog shows as its id, so it stands out.
+    fn format(&self, id: &str, args: Option<&FluentArgs>) -> String {
+        for bundle in self.bundles.iter() {
+            let Some(pattern) = bundle.get_message(id).and_then(|message| message.value()) else {
+                continue;
+            };
+            let mut errors = vec![];
+            return bundle
+                .format_pattern(pattern, args, &mut errors)
+                .into_owned();
+        }
+        id.to_string()
+    }
+
+    pub fn localize<T: Localize>(&self, value: T) -> Localized<T> {
+        Localized {
+            label: self.tr(value.message_id()),
+            value,
+        }
+    }
+
+    pub fn group_header(&self, header: &GroupHeader) -> String {
+        match header {
+            GroupHeader::Cost(cost) => self.tr_args("cost-group", &[("cost", (*cost).into())]),
+            GroupHeader::Trait(name) => name.clone(),
+        }
+    }
+
+    /// Says what's wrong with a setting.
+    pub fn problem(&self, problem: &ConfigProblem) -> String {
+        match problem {
+            ConfigProblem::ScaleOutOfRange => self.tr_args(
+                "problem-scale-range",
+                &[("min", MIN_SCALE.into()), ("max", MAX_SCALE.into())],
+            ),
+            ConfigProblem::UnknownLocale(locale) => self.tr_args(
+                "problem-unknown-locale",
+                &[("locale", locale.as_str().into())],
+            ),
+            ConfigProblem::UnknownUiLanguage(language) => self.tr_args(
+                "problem-unknown-ui-language",
+                &[("language", language.as_str().into())],
+            ),
+            ConfigProblem::DataUrlScheme => self.tr("problem-data-url-scheme"),
+            ConfigProblem::BlankSet => self.tr("problem-blank-set"),
+        }
+    }
+}
+
+/// A value shown to the user under a translated label.
+pub trait Localize {
+    fn message_id(&self) -> &'static str;
+}
+
+impl Localize for SortChampMethod {
+    fn message_id(&self) -> &'static str {
+        match self {
+            SortChampMethod::Alphabetical => "sort-alphabetical",
+            SortChampMethod::Cost => "sort-by-cost",
+            SortChampMethod::Trait => "sort-by-trait",
+        }
+    }
+}
+
+impl Localize for ThemeName {
+    fn message_id(&self) -> &'static str {
+        match self {
+            ThemeName::Light => "theme-light",
+            ThemeName::Dark => "theme-dark",
+        }
+    }
+}
+
+/// Section header of the changes in a category.
+impl Localize for Category {
+    fn message_id(&self) -> &'static str {
+        match self {
+            Category::Champion => "category-champions",
+            Category::Trait => "category-traits",
+            Category::Item => "category-items",
+        }
+    }
+}
+
+impl Localize for Direction {
+    fn message_id(&self) -> &'static str {
+        match self {
+            Direction::Buff => "direction-buff",
+            Direction::Nerf => "direction-nerf",
+            Direction::Adjusted => "direction-adjusted",
+        }
+    }
+}
+
+impl Localize for Stat {
+    fn message_id(&self) -> &'static str {
+        match self {
+            Stat::AttackDamage => "stat-attack-damage",
+            Stat::AbilityPower => "stat-ability-power",
+            Stat::AttackSpeed => "stat-attack-speed",
+            Stat::CritChance => "stat-crit-chance",
+            Stat::Armor => "stat-armor",
+            Stat::MagicResist => "stat-magic-resist",
+            Stat::Health => "stat-health",
+        }
+    }
+}
+
+impl Localize for SortKey {
+    fn message_id(&self) -> &'static str {
+        match self {
+            SortKey::Name => "sort-key-name",
+            SortKey::Cost => "sort-key-cost",
+            SortKey::Health => "sort-key-health",
+            SortKey::Range => "sort-key-range",
+        }
+    }
+}
+
+impl Localize for ItemKind {
+    fn message_id(&self) -> &'static str {
+        match self {
+            ItemKind::Component => "item-kind-component",
+            ItemKind::Completed => "item-kind-completed",
+            ItemKind::Emblem => "item-kind-emblem",
+        }
+    }
+}
+
+/// A value with its translated label, for widgets that display values with `Display`, like pick
+/// lists. Compares by value only.
+#[derive(Debug, Clone)]
+pub struct Localized<T> {
+    pub value: T,
+    label: String,
+}
+
+impl<T: PartialEq> PartialEq for Localized<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<T: Eq> Eq for Localized<T> {}
+
+impl<T> Display for Localized<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.label)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Ids of the messages in a catalog, one per line starting with `id =`.
+    fn message_ids(source: &str) -> Vec<&str> {
+        source
+            .lines()
+            .filter_map(|line| line.split_once(" = "))
+            .map(|(id, _)| id)
+            .filter(|id| id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-'))
+            .collect()
+    }
+
+    #[test]
+    fn every_catalog_has_every_message() {
+        let (_, fallback) = CATALOGS.iter().find(|(tag, _)| *tag == FALLBACK).unwrap();
+        let ids = message_ids(fallback);
+        for (tag, source) in CATALOGS {
+            let bundle = bundle(tag).unwrap();
+            for id in ids.iter() {
+                assert!(bundle.has_message(id), "{tag} has no {id}");
+            }
+            assert_eq!(
+                message_ids(source).len(),
+                ids.len(),
+                "{tag} has extra messages"
+            );
+        }
+    }
+
+    #[test]
+    fn arguments_are_filled_in() {
+        let i18n = Translator::new("de-DE");
+        assert_eq!(i18n.group_header(&GroupHeader::Cost(3)), "3 Kosten");
+        assert_eq!(
+            i18n.problem(&ConfigProblem::UnknownLocale(String::from("xx_xx"))),
+            "Unbekanntes Gebietsschema xx_xx"
+        );
+    }
+}
@@ -1,2 +1,25 @@
+pub mod analytics;
+pub mod audit;
+pub mod calc;
+pub mod comp;
+pub mod config;
+pub mod database;
+pub mod determiner;
+pub mod diff;
+pub mod game_log;
+pub mod history;
+pub mod i18n;
+pub mod live_client;
+pub mod match_stats;
+pub mod notebook;
+pub mod save;
+pub mod search;
 pub mod serde_help;
+pub mod server;
+pub mod share_code;
+pub mod sim;
+pub mod sort;
+pub mod state;
 pub mod tft_data;
+pub mod tier_list;
+pub mod toml_format;
@@ -1,32 +1,79 @@
 #![windows_subsystem = "windows"]
-#![feature(drain_filter)]
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::PathBuf;
 use std::sync::OnceLock;
-
-use directories::ProjectDirs;
+use std::time::{Duration, Instant};
 
 use iced::theme::{self, Theme};
-use iced::widget::{button, column, container, pick_list, row, scrollable, text, Image};
-use iced::{Element, Length, Sandbox, Settings};
+use iced::widget::{
+    button, checkbox, column, container, pick_list, row, scrollable, text, text_input, Image,
+};
+use iced::{
+    event, executor, keyboard, subscription, time, window, Application, Command, Element, Event,
+    Length, Settings, Subscription,
+};
 
 use itertools::Itertools;
 
-use serde::{Deserialize, Serialize};
-use serde_json::Value;
-
+use tft::analytics::GameFilter;
+use tft::audit::Audit;
+use tft::comp::Comp;
+use tft::config::{config_path, Config, ThemeName, MAX_SCALE, MIN_SCALE};
+use tft::database::{previous_data_path, TftDatabase};
+use tft::determiner::rank_champions;
+use tft::diff::load_snapshot;
+use tft::game_log::{today, GameLog, GameRecord};
+use tft::history::{Edit, History};
+use tft::i18n::{language_for_locale, Translator};
+use tft::live_client::{LiveClient, LiveState, LIVE_CLIENT_URL};
+use tft::match_stats::MatchStats;
+use tft::notebook::{NotebookStore, DEFAULT_NOTEBOOK};
+use tft::save::UnmatchedBis;
+use tft::search::Filters;
+use tft::serde_help::{project_dirs, prune_image_cache, set_image_downloads};
+use tft::sort::*;
+use tft::state::*;
 use tft::tft_data::*;
+use tft::tier_list::{Rank, TierList};
+
+mod screens;
+
+use screens::analytics::{AnalyticsInputs, AnalyticsMessage};
+use screens::audit::AuditMessage;
+use screens::augments::{AugmentBrowser, AugmentMessage};
+use screens::calc::{CalcInputs, CalcMessage};
+use screens::changelog::{Changelog, ChangelogMessage};
+use screens::comps::{CompEditor, CompMessage};
+use screens::game_log::{GameEntry, GameLogMessage};
+use screens::live::LiveMessage;
+use screens::locale::LocaleMessage;
+use screens::match_stats::MatchMessage;
+use screens::notebooks::NotebookMessage;
+use screens::settings::{SettingsForm, SettingsMessage};
+use screens::tier_lists::{TierBoard, TierMessage};
 
-static DIR: OnceLock<ProjectDirs> = OnceLock::new();
 static CACHE_DIR: OnceLock<PathBuf> = OnceLock::new();
 static DATA_DIR: OnceLock<PathBuf> = OnceLock::new();
 
-#[derive(Debug, Default)]
+/// How long edits have to settle before the notebook is saved automatically.
+const AUTOSAVE_DELAY: Duration = Duration::from_secs(2);
+
+/// How often the live game is read while the poller is on.
+const LIVE_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 enum Screen {
     #[default]
     CharacterBuilder,
     ItemDeterminer,
+    Comps,
+    GameLog,
+    Analytics,
+    Augments,
+    TierLists,
+    Changelog,
+    Settings,
 }
 
 #[derive(Debug, Clone)]
@@ -36,265 +83,969 @@ enum Message {
     ClickedComponentSub(Item),
     ClickedItem(Item),
     ClickedItemRemove(Item),
+    ClickedItemDetail(String),
     ClearItems(String),
     ClickedSave,
-    ChangeScreen,
+    ChangeScreen(Screen),
     ChangeSortMethod(SortChampMethod),
+    AddSecondarySort(SortKey),
+    RemoveSecondarySort(SortKey),
+    ChangeStarLevel(u8),
+    ChampionQueryChanged(String),
+    ToggleCostFilter(u8),
+    AddTraitFilter(String),
+    RemoveTraitFilter(String),
+    ToggleBisFilter(bool),
+    ItemQueryChanged(String),
+    ToggleItemKindFilter(ItemKind),
+    Notebook(NotebookMessage),
+    Comp(CompMessage),
+    Matches(MatchMessage),
+    Live(LiveMessage),
+    GameLog(GameLogMessage),
+    Analytics(AnalyticsMessage),
+    Augments(AugmentMessage),
+    TierLists(TierMessage),
+    Calc(CalcMessage),
+    Audit(AuditMessage),
+    Changelog(ChangelogMessage),
+    Locale(LocaleMessage),
+    Settings(SettingsMessage),
+    Undo,
+    Redo,
+    AutosaveTick,
+    CloseRequested,
 }
 
 struct Model {
     screen: Screen,
+    db: TftDatabase,
+    /// CommunityDragon locale of `db`.
+    locale: String,
+    /// Progress or error of switching the locale.
+    locale_status: Option<String>,
+    /// Saved settings. The data locale may differ from `locale` while it loads.
+    config: Config,
+    settings: SettingsForm,
+    i18n: Translator,
+    /// The data the last refresh replaced, if it changed since it was first downloaded.
+    previous_db: Option<TftDatabase>,
+    /// Saved BIS items that changed in the refresh.
+    audit: Audit,
     champs: Vec<ChampionState>,
-    items: Vec<Item>,
+    /// Saved BIS entries the loaded data doesn't know about, written back on save.
+    unmatched_bis: UnmatchedBis,
     components: Vec<ComponentState>,
+    /// Api name of the champion shown in the champion detail panel.
     focused_champion: Option<String>,
+    /// Api name of the item shown in the item detail panel.
+    focused_item: Option<String>,
+    /// Star level the champion detail panel is showing.
+    star_level: u8,
     curr_sort_method: SortChampMethod,
+    /// Keys ordering champions inside each section, most significant first.
+    secondary_sort: Vec<SortKey>,
+    /// Problems found while loading the save, shown until the next save.
+    save_warnings: Option<String>,
+    filters: Filters,
+    notebooks: NotebookStore,
+    notebook_names: Vec<String>,
+    /// Name of the open notebook.
+    notebook: String,
+    /// The open notebook couldn't be read, so it's never saved over.
+    notebook_unreadable: bool,
+    notebook_name_input: String,
+    notebook_error: Option<String>,
+    confirm_notebook_delete: bool,
+    notes: String,
+    /// File used for TOML import and export.
+    toml_path: String,
+    toml_status: Option<String>,
+    comps: Vec<Comp>,
+    comp_editor: CompEditor,
+    history: History,
+    /// When the notebook was last edited without being saved.
+    last_edit: Option<Instant>,
+    /// When the filters last changed without being saved.
+    filters_changed: Option<Instant>,
+    match_stats: MatchStats,
+    match_dir: String,
+    match_status: Option<String>,
+    /// Whether the Live Client Data endpoint is polled.
+    live_enabled: bool,
+    live_client: LiveClient,
+    live_state: LiveState,
+    game_log: GameLog,
+    /// The game log couldn't be read, so it's only appended to and never rewritten.
+    game_log_unreadable: bool,
+    /// Every logged game, oldest first.
+    games: Vec<GameRecord>,
+    game_entry: GameEntry,
+    analytics_filter: GameFilter,
+    analytics_inputs: AnalyticsInputs,
+    augment_browser: AugmentBrowser,
+    /// Augment tier list of the open notebook.
+    augment_tiers: BTreeMap<String, Rank>,
+    /// Champion tier lists of the open notebook.
+    tier_lists: Vec<TierList>,
+    tier_board: TierBoard,
+    calc: CalcInputs,
+    changelog: Changelog,
 }
 
-#[derive(Debug, Default, Deserialize, Clone, Serialize)]
-struct ChampionState {
-    champ: Champion,
-    items: Vec<Item>,
+/// Style of a toggleable chip button.
+fn chip_style(selected: bool) -> theme::Button {
+    if selected {
+        theme::Button::Primary
+    } else {
+        theme::Button::Secondary
+    }
 }
 
-#[derive(Debug, Default, Deserialize, Clone, Serialize)]
-struct ComponentState {
-    component: Item,
-    count: usize,
-}
+impl Model {
+    fn find_champ(&self, api_name: &str) -> Option<&ChampionState> {
+        self.champs
+            .iter()
+            .find(|champ_state| champ_state.champ.api_name == api_name)
+    }
 
-#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
-pub enum SortChampMethod {
-    #[default]
-    Alphabetical,
-    Cost,
-    Trait,
-}
+    fn find_component(&self, api_name: &str) -> Option<&ComponentState> {
+        self.components
+            .iter()
+            .find(|component_state| component_state.component.api_name == api_name)
+    }
 
-impl ToString for SortChampMethod {
-    fn to_string(&self) -> String {
-        match self {
-            SortChampMethod::Alphabetical => String::from("Alphabetical"),
-            SortChampMethod::Cost => String::from("By Cost"),
-            SortChampMethod::Trait => String::from("By Trait"),
+    fn find_champ_mut(&mut self, api_name: &str) -> Option<&mut ChampionState> {
+        self.champs
+            .iter_mut()
+            .find(|champ_state| champ_state.champ.api_name == api_name)
+    }
+
+    fn apply_edit(&mut self, edit: &Edit) {
+        match edit {
+            Edit::AddItem {
+                champion,
+                item,
+                index,
+            } => {
+                let Some(item) = self.db.item(item).cloned() else {
+                    return;
+                };
+                if let Some(champ_state) = self.find_champ_mut(champion) {
+                    let index = (*index).min(champ_state.items.len());
+                    champ_state.items.insert(index, item);
+                }
+            }
+            Edit::RemoveItem {
+                champion, index, ..
+            } => {
+                if let Some(champ_state) = self.find_champ_mut(champion) {
+                    if *index < champ_state.items.len() {
+                        champ_state.items.remove(*index);
+                    }
+                }
+            }
+            Edit::ClearItems { champion, .. } => {
+                if let Some(champ_state) = self.find_champ_mut(champion) {
+                    champ_state.items.clear();
+                }
+            }
+            Edit::RestoreItems {
+                champion,
+                items: to,
+            }
+            | Edit::ReplaceItems { champion, to, .. } => {
+                let items = to
+                    .iter()
+                    .filter_map(|item| self.db.item(item).cloned())
+                    .collect();
+                if let Some(champ_state) = self.find_champ_mut(champion) {
+                    champ_state.items = items;
+                }
+            }
+            Edit::ComponentCount { component, to, .. } => {
+                if let Some(component_state) = self
+                    .components
+                    .iter_mut()
+                    .find(|component_state| &component_state.component.api_name == component)
+                {
+                    component_state.count = *to;
+                }
+            }
+            Edit::BoardMove { comp, from, to } => {
+                if let Some(unit) = self
+                    .comps
+                    .get_mut(*comp)
+                    .and_then(|comp| comp.unit_at_mut(*from))
+                {
+                    unit.position = Some(*to);
+                }
+            }
+            Edit::PlaceUnit { comp, unit } => {
+                if let Some(comp) = self.comps.get_mut(*comp) {
+                    comp.units.push(unit.clone());
+                }
+            }
+            Edit::RemoveUnit { comp, unit } => {
+                if let Some(comp) = self.comps.get_mut(*comp) {
+                    if let Some(index) = comp.units.iter().position(|placed| placed == unit) {
+                        comp.units.remove(index);
+                    }
+                }
+            }
+            Edit::UnitStar {
+                comp, position, to, ..
+            } => {
+                if let Some(unit) = self
+                    .comps
+                    .get_mut(*comp)
+                    .and_then(|comp| comp.unit_at_mut(*position))
+                {
+                    unit.star = *to;
+                }
+            }
+            Edit::UnitItems {
+                comp, position, to, ..
+            } => {
+                if let Some(unit) = self
+                    .comps
+                    .get_mut(*comp)
+                    .and_then(|comp| comp.unit_at_mut(*position))
+                {
+                    unit.items = to.clone();
+                }
+            }
         }
     }
+
+    /// Applies an edit and records it so it can be undone.
+    pub(crate) fn perform(&mut self, edit: Edit) {
+        self.apply_edit(&edit);
+        self.history.record(edit);
+        self.mark_dirty();
+    }
+
+    /// Schedules an autosave once editing settles down.
+    pub(crate) fn mark_dirty(&mut self) {
+        self.last_edit = Some(Instant::now());
+    }
+
+    /// Schedules saving the filters once typing settles down.
+    pub(crate) fn mark_filters_dirty(&mut self) {
+        self.filters_changed = Some(Instant::now());
+    }
+
+    fn save_filters(&mut self) {
+        self.filters_changed = None;
+        if let Err(e) = self
+            .filters
+            .save(&DATA_DIR.get().unwrap().join("filters.json"))
+        {
+            log::warn!("could not save the filters: {e}");
+            self.save_warnings = Some(
+                self.i18n
+                    .tr_args("filters-save-failed", &[("error", e.to_string().into())]),
+            );
+        }
+    }
+
+    fn champion_detail(&self, champ_state: &ChampionState) -> Element<Message> {
+        let champ = &champ_state.champ;
+        let stats = champ.stats.at_star(self.star_level);
+        let stat = |label: &str, value: Option<f64>| {
+            text(format!(
+                "{label}: {}",
+                value
+                    .map(format_number)
+                    .unwrap_or_else(|| String::from("-"))
+            ))
+        };
+
+        let star_toggle = row((1..=3)
+            .map(|star| {
+                button(text(format!("{star}★")))
+                    .on_press(Message::ChangeStarLevel(star))
+                    .style(chip_style(star == self.star_level))
+                    .into()
+            })
+            .collect::<Vec<_>>());
+
+        let traits = column(
+            champ
+                .traits
+                .iter()
+                .map(|name| {
+                    let icon = self
+                        .db
+                        .traits
+                        .iter()
+                        .find(|t| &t.name == name)
+                        .map(|t| t.icon.clone())
+                        .unwrap_or_default();
+                    row!(
+                        Image::new(icon.handle).width(Length::Fixed(24.0)),
+                        text(name)
+                    )
+                    .into()
+                })
+                .collect::<Vec<_>>(),
+        );
+
+        let bis = row(champ_state
+            .items
+            .iter()
+            .map(|item| Image::new(item.icon.handle.clone()).into())
+            .collect::<Vec<_>>());
+
+        column!(
+            row!(
+                Image::new(champ.square_icon.handle.clone()),
+                column!(
+                    text(&champ.name),
+                    text(
+                        self.i18n
+                            .tr_args("champion-cost", &[("cost", champ.cost.into())])
+                    ),
+                    star_toggle
+                )
+            ),
+            traits,
+            row!(
+                Image::new(champ.ability.icon.handle.clone()).width(Length::Fixed(48.0)),
+                text(&champ.ability.name)
+            ),
+            text(champ.ability.rendered_description(self.star_level)),
+            stat(&self.i18n.tr("stat-health"), stats.hp),
+            stat(&self.i18n.tr("stat-attack-damage"), stats.damage),
+            stat(&self.i18n.tr("stat-attack-speed"), stats.attack_speed),
+            stat(&self.i18n.tr("stat-crit-chance"), stats.crit_chance),
+            stat(&self.i18n.tr("stat-armor"), stats.armor),
+            stat(&self.i18n.tr("stat-magic-resist"), stats.magic_resist),
+            text(self.i18n.tr_args(
+                "champion-mana",
+                &[
+                    ("initial", format_number(stats.initial_mana).into()),
+                    ("max", format_number(stats.mana).into())
+                ]
+            )),
+            text(self.i18n.tr_args(
+                "champion-range",
+                &[("range", format_number(stats.range).into())]
+            )),
+            text(self.i18n.tr_args(
+                "champion-bis",
+                &[(
+                    "items",
+                    ItemsDisplay(champ_state.items.clone()).to_string().into()
+                )]
+            )),
+            bis,
+            self.audit_panel(champ_state),
+            self.calc_panel(champ_state),
+            self.match_stats_panel(champ_state)
+        )
+        .into()
+    }
+
+    fn item_detail(&self, item: &Item) -> Element<Message> {
+        let recipe = row(item
+            .composition
+            .iter()
+            .map(|api_name| {
+                let component = self
+                    .components
+                    .iter()
+                    .find(|component_state| &component_state.component.api_name == api_name)
+                    .map(|component_state| component_state.component.clone())
+                    .unwrap_or_default();
+                column!(
+                    Image::new(component.icon.handle).width(Length::Fixed(48.0)),
+                    text(component.name)
+                )
+                .into()
+            })
+            .collect::<Vec<_>>());
+
+        let effects = column(
+            item.effect_values()
+                .into_iter()
+                .map(|(name, value)| text(format!("{name}: {}", format_number(value))).into())
+                .collect::<Vec<_>>(),
+        );
+
+        // emblems and similar items list the api names of the traits they grant
+        let granted_traits = item
+            .associated_traits
+            .iter()
+            .map(|api_name| {
+                self.db
+                    .traits
+                    .iter()
+                    .find(|t| &t.api_name == api_name)
+                    .map(|t| t.name.clone())
+                    .unwrap_or_else(|| api_name.clone())
+            })
+            .join(", ");
+
+        let used_by = self
+            .champs
+            .iter()
+            .filter(|champ_state| {
+                champ_state
+                    .items
+                    .iter()
+                    .any(|champ_item| champ_item.api_name == item.api_name)
+            })
+            .map(|champ_state| champ_state.champ.name.clone())
+            .join(", ");
+
+        column!(
+            row!(
+                Image::new(item.icon.handle.clone()),
+                column!(
+                    text(&item.name),
+                    text(if item.unique {
+                        self.i18n.tr("item-unique")
+                    } else {
+                        String::new()
+                    })
+                )
+            ),
+            text(item.rendered_description()),
+            effects,
+            text(self.i18n.tr("item-recipe")),
+            recipe,
+            text(if granted_traits.is_empty() {
+                String::new()
+            } else {
+                self.i18n
+                    .tr_args("item-grants", &[("traits", granted_traits.into())])
+            }),
+            text(if used_by.is_empty() {
+                self.i18n.tr("item-unused")
+            } else {
+                self.i18n
+                    .tr_args("item-used-by", &[("champions", used_by.into())])
+            })
+        )
+        .into()
+    }
 }
 
-impl Sandbox for Model {
+impl Application for Model {
     type Message = Message;
-    // type Theme = Theme;
-    // type Executor = executor::Default;
-    // type Flags = ();
-
-    fn new() -> Self {
-        let f = ureq::get("https://raw.communitydragon.org/latest/cdragon/tft/en_us.json")
-            .call()
-            .unwrap()
-            .into_string()
-            .unwrap();
-        let json: Value = serde_json::from_str(&f).unwrap();
-        let obj = json.as_object().unwrap();
-
-        // access tft set 8 stage 2 champions
-        let mut champs: Vec<Champion> = serde_json::from_value(
-            obj.get("setData")
-                .unwrap()
-                .get(18) // tft set 8 stage 2
-                .unwrap()
-                .get("champions")
-                .unwrap()
-                .clone(),
-        )
-        .unwrap();
-
-        // remove champions that have no traits (eggs, creeps, etc.)
-        champs.drain_filter(|champ| champ.traits.is_empty());
-
-        // get items
-        let mut items: Vec<Item> =
-            serde_json::from_value(obj.get("items").unwrap().clone()).unwrap();
-        // only keep items composed of other items (standard completed items)
-        items.drain_filter(|item| item.composition.is_empty());
-        // remove items that are exclusive to particular sets
-        items.drain_filter(|item| {
-            item.api_name.contains('5') // remove set 5 exclusives
-                || item.api_name.contains('6') // remove set 6 exclusives
-                || item.api_name.contains('7') // remove set 7 exclusives
-                || item.name.contains("tft_item_name") // remove special tft items
-                || item.composition.iter().any(|component| component.contains("Tutorial"))
-        });
-        let mut set = HashSet::new();
-        for item in items.iter() {
-            for component in item.composition.iter() {
-                set.insert(component.clone());
+    type Theme = Theme;
+    type Executor = executor::Default;
+    type Flags = Config;
+
+    fn new(config: Config) -> (Self, Command<Message>) {
+        let (loaded, online) = match TftDatabase::load_with(&config.load_options(false)) {
+            Ok(db) => (Ok(db), true),
+            Err(e) => {
+                log::warn!("{e}, trying the cached data");
+                // icons that aren't cached can't be fetched either
+                set_image_downloads(false);
+                let cached = TftDatabase::load_with(&config.load_options(true)).map_err(|_| e);
+                (cached, false)
+            }
+        };
+        // without data the app still opens, so the settings can be fixed; the notebook's BIS
+        // lists are all kept aside as unknown entries until data loads
+        let (db, load_error) = match loaded {
+            Ok(db) => (db, None),
+            Err(e) => (TftDatabase::default(), Some(e.to_string())),
+        };
+        let locale = config.locale.clone();
+        if let (Some(max_bytes), None) = (config.cache_size_bytes(), &load_error) {
+            if let Err(e) = prune_image_cache(max_bytes, &db.icon_paths()) {
+                log::warn!("could not clean up the icon cache: {e}");
             }
         }
-        let components: Vec<ComponentState> = set
-            .into_iter()
-            .map(|component_api_name| {
-                let all_items: Vec<Item> =
-                    serde_json::from_value(obj.get("items").unwrap().clone()).unwrap();
-                ComponentState {
-                    component: all_items
-                        .into_iter()
-                        .find(|item| item.api_name == component_api_name)
-                        .unwrap(),
-                    count: 0,
-                }
-            })
-            .collect();
-        // println!("{components:#?}");
-
-        let champ_state = champs
-            .into_iter()
-            .map(|champ| {
-                let data_dir = DATA_DIR.get().unwrap();
-                let path = "champ_info.json";
-                if let Ok(s) = fs::read_to_string(data_dir.join(path)) {
-                    let x: Vec<ChampionState> = serde_json::from_str(&s).unwrap();
-                    let y: Vec<ChampionState> = x
-                        .into_iter()
-                        .filter(|champ_state| champ_state.champ.name == champ.name)
-                        .collect();
-                    y[0].clone()
-                } else {
-                    ChampionState {
-                        champ,
-                        items: vec![],
-                    }
-                }
+        let previous_path = previous_data_path(&locale);
+        // icons of removed items aren't shown, don't fetch them
+        set_image_downloads(false);
+        let previous_db = load_snapshot(&previous_path, config.set.as_deref()).ok();
+        set_image_downloads(online);
+        let components: Vec<ComponentState> = db
+            .components
+            .iter()
+            .cloned()
+            .map(|component| ComponentState {
+                component,
+                count: 0,
             })
             .collect();
+        let (notebooks, notebooks_error) = match NotebookStore::open(DATA_DIR.get().unwrap()) {
+            Ok(notebooks) => (notebooks, None),
+            Err(e) => {
+                log::warn!("could not open the notebooks: {e}");
+                (NotebookStore::unopened(DATA_DIR.get().unwrap()), Some(e))
+            }
+        };
+        let game_log = GameLog::new(&DATA_DIR.get().unwrap().join("games.jsonl"));
+        // a broken log mustn't keep the app from opening, the error is shown on the game log
+        // screen instead
+        let (games, game_log_error) = match game_log.read() {
+            Ok(games) => (games, None),
+            Err(e) => {
+                log::warn!("could not read the game log: {e}");
+                (vec![], Some(e))
+            }
+        };
+        let notebook = notebooks
+            .last_used()
+            .or_else(|| notebooks.list().ok()?.into_iter().next())
+            .unwrap_or_else(|| String::from(DEFAULT_NOTEBOOK));
+
         // let x = pane_grid::State::
         // let (items, _) = pane_grid::State::new(Item::default());
-        Model {
+        let mut model = Model {
             screen: Screen::default(),
-            champs: champ_state,
-            items,
+            db,
+            i18n: Translator::new(
+                config
+                    .ui_language
+                    .as_deref()
+                    .unwrap_or_else(|| language_for_locale(&locale)),
+            ),
+            locale,
+            locale_status: load_error,
+            settings: SettingsForm::new(&config),
+            previous_db,
+            audit: Audit::default(),
+            champs: vec![],
+            unmatched_bis: UnmatchedBis::new(),
             components,
             focused_champion: None,
-            curr_sort_method: SortChampMethod::default(),
+            focused_item: None,
+            star_level: 1,
+            curr_sort_method: config.default_sort,
+            secondary_sort: vec![],
+            save_warnings: None,
+            filters: Filters::load(&DATA_DIR.get().unwrap().join("filters.json")),
+            notebooks,
+            notebook_names: vec![],
+            notebook: String::new(),
+            notebook_unreadable: false,
+            notebook_name_input: String::new(),
+            notebook_error: None,
+            confirm_notebook_delete: false,
+            notes: String::new(),
+            toml_path: String::new(),
+            toml_status: None,
+            comps: vec![],
+            comp_editor: CompEditor::default(),
+            history: History::default(),
+            last_edit: None,
+            filters_changed: None,
+            match_stats: MatchStats::load(&DATA_DIR.get().unwrap().join("match_stats.json")),
+            match_dir: String::new(),
+            match_status: None,
+            live_enabled: false,
+            live_client: LiveClient::new(LIVE_CLIENT_URL),
+            live_state: LiveState::default(),
+            game_log,
+            game_log_unreadable: game_log_error.is_some(),
+            games,
+            game_entry: GameEntry {
+                date: today(),
+                csv_path: DATA_DIR
+                    .get()
+                    .unwrap()
+                    .join("games.csv")
+                    .to_string_lossy()
+                    .into_owned(),
+                ..GameEntry::default()
+            },
+            analytics_filter: GameFilter::default(),
+            analytics_inputs: AnalyticsInputs::default(),
+            augment_browser: AugmentBrowser::default(),
+            augment_tiers: BTreeMap::new(),
+            tier_lists: vec![],
+            tier_board: TierBoard::default(),
+            calc: CalcInputs::default(),
+            changelog: Changelog {
+                old_path: previous_path.to_string_lossy().into_owned(),
+                ..Changelog::default()
+            },
+            config,
+        };
+        if let Some(e) = game_log_error {
+            model.game_entry.status = Some(
+                model
+                    .i18n
+                    .tr_args("game-log-unreadable", &[("error", e.to_string().into())]),
+            );
         }
+        let opened = model.open_notebook(notebook);
+        model.notebook_error = notebooks_error.or(opened.err()).map(|e| e.to_string());
+        (model, Command::none())
     }
 
     fn title(&self) -> String {
         String::from("TFT App")
     }
 
-    fn update(&mut self, message: Message) {
+    fn update(&mut self, message: Message) -> Command<Message> {
         match message {
-            Message::ClickedChampion(name) => {
-                println!("new focused champion is {}", name);
-                self.focused_champion = Some(name);
+            Message::ClickedChampion(api_name) => {
+                log::debug!("new focused champion is {}", api_name);
+                self.focused_champion = Some(api_name);
             }
             Message::ClickedItem(item) => {
-                if let Some(ref champ) = self.focused_champion {
-                    println!("{} got added to {}", item, champ);
-                    let champ = self
-                        .champs
-                        .iter_mut()
-                        .find(|champ_state| &champ_state.champ.name == champ)
-                        .unwrap();
-                    champ.items.push(item);
+                let champ = self
+                    .focused_champion
+                    .as_ref()
+                    .and_then(|api_name| self.find_champ(api_name));
+                if let Some(champ) = champ {
+                    log::debug!("{} got added to {}", item, champ.champ.name);
+                    let edit = Edit::AddItem {
+                        champion: champ.champ.api_name.clone(),
+                        item: item.api_name,
+                        index: champ.items.len(),
+                    };
+                    self.perform(edit);
                 }
             }
             Message::ClickedItemRemove(item) => {
-                if let Some(ref champ) = self.focused_champion {
-                    println!("{} got added to {}", item, champ);
-                    let champ = self
-                        .champs
-                        .iter_mut()
-                        .find(|champ_state| &champ_state.champ.name == champ)
-                        .unwrap();
-                    if let Some(index) = champ.items.iter().position(|x| x.name == item.name) {
-                        champ.items.remove(index);
+                let champ = self
+                    .focused_champion
+                    .as_ref()
+                    .and_then(|api_name| self.find_champ(api_name));
+                if let Some(champ) = champ {
+                    log::debug!("{} got removed from {}", item, champ.champ.name);
+                    if let Some(index) =
+                        champ.items.iter().position(|x| x.api_name == item.api_name)
+                    {
+                        let edit = Edit::RemoveItem {
+                            champion: champ.champ.api_name.clone(),
+                            item: item.api_name,
+                            index,
+                        };
+                        self.perform(edit);
                     }
                 }
             }
+            Message::ClickedItemDetail(api_name) => {
+                self.focused_item = Some(api_name);
+            }
             Message::ClickedSave => {
-                let data_dir = DATA_DIR.get().unwrap();
-                let s = serde_json::to_string(&self.champs).unwrap();
-                fs::write(data_dir.join("champ_info.json"), s).unwrap();
-            }
-            Message::ChangeScreen => {
-                self.screen = match self.screen {
-                    Screen::CharacterBuilder => Screen::ItemDeterminer,
-                    Screen::ItemDeterminer => Screen::CharacterBuilder,
-                };
+                self.save_notebook();
+            }
+            Message::ChangeScreen(screen) => {
+                self.screen = screen;
             }
             Message::ClickedComponentAdd(component) => {
-                let component: &mut ComponentState = self
-                    .components
-                    .iter_mut()
-                    .find(|component_state| component_state.component.name == component.name)
-                    .unwrap();
-                component.count += 1;
+                let Some(component) = self.find_component(&component.api_name) else {
+                    log::warn!("unknown component {component}");
+                    return Command::none();
+                };
+                let edit = Edit::ComponentCount {
+                    component: component.component.api_name.clone(),
+                    from: component.count,
+                    to: component.count + 1,
+                };
+                self.perform(edit);
             }
             Message::ClickedComponentSub(component) => {
-                let component: &mut ComponentState = self
-                    .components
-                    .iter_mut()
-                    .find(|component_state| component_state.component.name == component.name)
-                    .unwrap();
-                component.count = component.count.saturating_sub(1);
+                let Some(component) = self.find_component(&component.api_name) else {
+                    log::warn!("unknown component {component}");
+                    return Command::none();
+                };
+                if component.count > 0 {
+                    let edit = Edit::ComponentCount {
+                        component: component.component.api_name.clone(),
+                        from: component.count,
+                        to: component.count - 1,
+                    };
+                    self.perform(edit);
+                }
             }
-            Message::ClearItems(champ) => {
-                let champ = self
-                    .champs
-                    .iter_mut()
-                    .find(|champ_state| champ_state.champ.name == champ)
-                    .unwrap();
-                champ.items.clear();
+            Message::ClearItems(api_name) => {
+                let Some(champ) = self.find_champ(&api_name) else {
+                    log::warn!("can't clear the items of unknown champion {api_name}");
+                    return Command::none();
+                };
+                let items: Vec<String> = champ
+                    .items
+                    .iter()
+                    .map(|item| item.api_name.clone())
+                    .collect();
+                // the items the data doesn't know about are cleared too, for good: undoing only
+                // brings back the known ones
+                if self.unmatched_bis.remove(&api_name).is_some() {
+                    self.mark_dirty();
+                }
+                if !items.is_empty() {
+                    self.perform(Edit::ClearItems {
+                        champion: api_name,
+                        items,
+                    });
+                }
             }
             Message::ChangeSortMethod(method) => {
                 self.curr_sort_method = method;
             }
+            Message::AddSecondarySort(key) => {
+                if !self.secondary_sort.contains(&key) {
+                    self.secondary_sort.push(key);
+                }
+            }
+            Message::RemoveSecondarySort(key) => {
+                self.secondary_sort.retain(|k| *k != key);
+            }
+            Message::ChangeStarLevel(star) => {
+                self.star_level = star;
+            }
+            Message::ChampionQueryChanged(query) => {
+                self.filters.champions.query = query;
+                self.mark_filters_dirty();
+            }
+            Message::ToggleCostFilter(cost) => {
+                if !self.filters.champions.costs.remove(&cost) {
+                    self.filters.champions.costs.insert(cost);
+                }
+                self.mark_filters_dirty();
+            }
+            Message::AddTraitFilter(name) => {
+                self.filters.champions.traits.insert(name);
+                self.mark_filters_dirty();
+            }
+            Message::RemoveTraitFilter(name) => {
+                self.filters.champions.traits.remove(&name);
+                self.mark_filters_dirty();
+            }
+            Message::ToggleBisFilter(has_bis) => {
+                self.filters.champions.has_bis = has_bis;
+                self.mark_filters_dirty();
+            }
+            Message::ItemQueryChanged(query) => {
+                self.filters.items.query = query;
+                self.mark_filters_dirty();
+            }
+            Message::ToggleItemKindFilter(kind) => {
+                if !self.filters.items.kinds.remove(&kind) {
+                    self.filters.items.kinds.insert(kind);
+                }
+                self.mark_filters_dirty();
+            }
+            Message::Notebook(message) => {
+                self.update_notebook(message);
+            }
+            Message::Comp(message) => {
+                return self.update_comp(message);
+            }
+            Message::Matches(message) => {
+                self.update_matches(message);
+            }
+            Message::Live(message) => {
+                return self.update_live(message);
+            }
+            Message::GameLog(message) => {
+                self.update_game_log(message);
+            }
+            Message::Analytics(message) => {
+                self.update_analytics(message);
+            }
+            Message::Augments(message) => {
+                self.update_augments(message);
+            }
+            Message::TierLists(message) => {
+                self.update_tier_lists(message);
+            }
+            Message::Calc(message) => {
+                self.update_calc(message);
+            }
+            Message::Audit(message) => {
+                self.update_audit(message);
+            }
+            Message::Changelog(message) => {
+                self.update_changelog(message);
+            }
+            Message::Locale(message) => {
+                return self.update_locale(message);
+            }
+            Message::Settings(message) => {
+                return self.update_settings(message);
+            }
+            Message::Undo => {
+                if let Some(edit) = self.history.undo() {
+                    self.apply_edit(&edit);
+                    self.mark_dirty();
+                }
+            }
+            Message::Redo => {
+                if let Some(edit) = self.history.redo() {
+                    self.apply_edit(&edit);
+                    self.mark_dirty();
+                }
+            }
+            Message::AutosaveTick => {
+                if self
+                    .last_edit
+                    .is_some_and(|last_edit| last_edit.elapsed() >= AUTOSAVE_DELAY)
+                {
+                    self.save_notebook();
+                }
+                if self
+                    .filters_changed
+                    .is_some_and(|changed| changed.elapsed() >= AUTOSAVE_DELAY)
+                {
+                    self.save_filters();
+                }
+            }
+            Message::CloseRequested => {
+                if self.last_edit.is_some() {
+                    self.save_notebook();
+                }
+                if self.filters_changed.is_some() {
+                    self.save_filters();
+                }
+                return window::close();
+            }
         }
+        Command::none()
     }
 
     fn view(&self) -> Element<Message> {
         match self.screen {
             Screen::CharacterBuilder => {
                 let mut champs_clone = self.champs.clone();
-                champs_clone.sort_by(|a, b| match self.curr_sort_method {
-                    SortChampMethod::Alphabetical => (a.champ.name).cmp(&b.champ.name),
-                    SortChampMethod::Cost => (a.champ.cost).cmp(&b.champ.cost),
-                    SortChampMethod::Trait => (a.champ.traits).cmp(&b.champ.traits),
+                champs_clone.retain(|champ_state| {
+                    self.filters.champions.matches(
+                        &champ_state.champ,
+                        self.db.english_name(&champ_state.champ.api_name),
+                        !champ_state.items.is_empty(),
+                    )
                 });
-                let chunks = champs_clone.into_iter().chunks(3);
-                let mut rows = vec![];
+                let groups = group_champions(
+                    champs_clone,
+                    |champ_state| &champ_state.champ,
+                    self.curr_sort_method,
+                    &self.secondary_sort,
+                );
+                let mut rows: Vec<Element<Message>> = vec![];
                 // let mut rows = column!(row!(Image::new(image::Handle::default())));
-                for chunk in &chunks {
-                    rows.push(row(chunk
-                        .into_iter()
-                        .map(|a| {
-                            column!(
-                                Image::new(a.champ.square_icon.handle.clone()),
-                                button(text(a.champ.name.clone()))
-                                    .on_press(Message::ClickedChampion(a.champ.name.clone())),
-                                button(text("Clear"))
-                                    .on_press(Message::ClearItems(a.champ.name))
-                                    .style(iced::theme::Button::Destructive)
-                            )
-                            .into()
-                        })
-                        .collect::<Vec<_>>()));
+                for group in groups {
+                    if let Some(header) = group.header {
+                        rows.push(text(self.i18n.group_header(&header)).into());
+                    }
+                    for chunk in &group.entries.into_iter().chunks(3) {
+                        rows.push(
+                            row(chunk
+                                .into_iter()
+                                .map(|a| {
+                                    column!(
+                                        Image::new(a.champ.square_icon.handle.clone()),
+                                        button(text(
+                                            // flag champions with items changed by the refresh
+                                            if self
+                                                .audit
+                                                .for_champion(&a.champ.api_name)
+                                                .next()
+                                                .is_some()
+                                            {
+                                                format!("{} (!)", a.champ.name)
+                                            } else {
+                                                a.champ.name.clone()
+                                            }
+                                        ))
+                                        .on_press(
+                                            Message::ClickedChampion(a.champ.api_name.clone())
+                                        ),
+                                        button(text(self.i18n.tr("clear")))
+                                            .on_press(Message::ClearItems(a.champ.api_name))
+                                            .style(iced::theme::Button::Destructive)
+                                    )
+                                    .into()
+                                })
+                                .collect::<Vec<_>>())
+                            .into(),
+                        );
+                    }
                 }
+                let champ_filter = &self.filters.champions;
+                let cost_chips = row((1..=5)
+                    .map(|cost| {
+                        button(text(cost))
+                            .on_press(Message::ToggleCostFilter(cost))
+                            .style(chip_style(champ_filter.costs.contains(&cost)))
+                            .into()
+                    })
+                    .collect::<Vec<_>>());
+                let trait_chips = row(champ_filter
+                    .traits
+                    .iter()
+                    .map(|name| {
+                        button(text(format!("{name} x")))
+                            .on_press(Message::RemoveTraitFilter(name.clone()))
+                            .style(chip_style(true))
+                            .into()
+                    })
+                    .collect::<Vec<_>>());
+                let trait_names = self
+                    .db
+                    .traits
+                    .iter()
+                    .map(|t| t.name.clone())
+                    .filter(|name| !champ_filter.traits.contains(name))
+                    .sorted()
+                    .collect::<Vec<_>>();
                 let champion_col = rows.into_iter().fold(
-                    column!(pick_list(
-                        vec![
-                            SortChampMethod::Alphabetical,
-                            SortChampMethod::Cost,
-                            SortChampMethod::Trait
-                        ],
-                        Some(self.curr_sort_method),
-                        |method| { Message::ChangeSortMethod(method) }
-                    )),
+                    column!(
+                        pick_list(
+                            SortChampMethod::ALL
+                                .map(|method| self.i18n.localize(method))
+                                .to_vec(),
+                            Some(self.i18n.localize(self.curr_sort_method)),
+                            |method| { Message::ChangeSortMethod(method.value) }
+                        ),
+                        pick_list(
+                            SortKey::ALL
+                                .into_iter()
+                                .filter(|key| !self.secondary_sort.contains(key))
+                                .map(|key| self.i18n.localize(key))
+                                .collect::<Vec<_>>(),
+                            None,
+                            |key| Message::AddSecondarySort(key.value)
+                        )
+                        .placeholder(self.i18n.tr("then-by")),
+                        row(self
+                            .secondary_sort
+                            .iter()
+                            .map(|key| {
+                                button(text(format!("{} x", self.i18n.localize(*key))))
+                                    .on_press(Message::RemoveSecondarySort(*key))
+                                    .style(chip_style(true))
+                                    .into()
+                            })
+                            .collect::<Vec<_>>()),
+                        text_input(&self.i18n.tr("search-champions"), &champ_filter.query)
+                            .on_input(Message::ChampionQueryChanged),
+                        cost_chips,
+                        pick_list(trait_names, None, Message::AddTraitFilter)
+                            .placeholder(self.i18n.tr("filter-by-trait")),
+                        trait_chips,
+                        checkbox(
+                            self.i18n.tr("has-bis-saved"),
+                            champ_filter.has_bis,
+                            Message::ToggleBisFilter
+                        )
+                    ),
                     |col, row| col.push(row),
                 );
 
-                let item_chunks = self.items.clone().into_iter().chunks(3);
+                let item_chunks = self
+                    .db
+                    .items
+                    .iter()
+                    .filter(|item| {
+                        self.filters
+                            .items
+                            .matches(item, self.db.english_name(&item.api_name))
+                    })
+                    .cloned()
+                    .chunks(3);
                 let mut item_rows = vec![];
                 for item_chunk in &item_chunks {
                     item_rows.push(row(item_chunk
@@ -306,8 +1057,11 @@ impl Sandbox for Model {
                                     button(text(a.name.clone()))
                                         .on_press(Message::ClickedItem(a.clone())),
                                     button(text("-"))
-                                        .on_press(Message::ClickedItemRemove(a))
-                                        .style(iced::theme::Button::Destructive)
+                                        .on_press(Message::ClickedItemRemove(a.clone()))
+                                        .style(iced::theme::Button::Destructive),
+                                    button(text("?"))
+                                        .on_press(Message::ClickedItemDetail(a.api_name))
+                                        .style(iced::theme::Button::Secondary)
                                 )
                             )
                             .into()
@@ -315,33 +1069,84 @@ impl Sandbox for Model {
                         .collect::<Vec<_>>()))
                 }
 
-                let item_col = item_rows
-                    .into_iter()
-                    .fold(column!(), |col, row| col.push(row));
+                let item_filter = &self.filters.items;
+                let item_col = item_rows.into_iter().fold(
+                    column!(
+                        text_input(&self.i18n.tr("search-items"), &item_filter.query)
+                            .on_input(Message::ItemQueryChanged),
+                        row([ItemKind::Completed, ItemKind::Emblem]
+                            .into_iter()
+                            .map(|kind| {
+                                button(text(self.i18n.localize(kind)))
+                                    .on_press(Message::ToggleItemKindFilter(kind))
+                                    .style(chip_style(item_filter.kinds.contains(&kind)))
+                                    .into()
+                            })
+                            .collect::<Vec<_>>())
+                    ),
+                    |col, row| col.push(row),
+                );
+
+                let mut undo_button = button(text(self.i18n.tr("undo")));
+                if self.history.can_undo() {
+                    undo_button = undo_button.on_press(Message::Undo);
+                }
+                let mut redo_button = button(text(self.i18n.tr("redo")));
+                if self.history.can_redo() {
+                    redo_button = redo_button.on_press(Message::Redo);
+                }
 
                 container(row!(
                     scrollable(champion_col),
                     scrollable(item_col),
-                    column!(
-                        text(match self.focused_champion.clone() {
-                            Some(champ) => {
-                                let champ = self
-                                    .champs
-                                    .iter()
-                                    .find(|champ_state| champ_state.champ.name == champ)
-                                    .unwrap();
-                                format!("{}: {}", champ.champ, ItemsDisplay(champ.items.clone()))
-                            }
-                            None => String::from("No champion selected"),
-                        }),
-                        button(text("Save")).on_press(Message::ClickedSave),
-                        button(text("Go to Item Determiner")).on_press(Message::ChangeScreen),
-                    )
+                    scrollable(column!(
+                        self.notebook_panel(),
+                        self.locale_panel(),
+                        self.match_import_panel(),
+                        match self
+                            .focused_champion
+                            .as_ref()
+                            .and_then(|api_name| self.find_champ(api_name))
+                        {
+                            Some(champ) => self.champion_detail(champ),
+                            None => text(self.i18n.tr("no-champion-selected")).into(),
+                        },
+                        match self.focused_item.as_ref().and_then(|api_name| {
+                            self.db.items.iter().find(|item| &item.api_name == api_name)
+                        }) {
+                            Some(item) => self.item_detail(item),
+                            None => column!().into(),
+                        },
+                        text(self.save_warnings.clone().unwrap_or_default()),
+                        row!(undo_button, redo_button),
+                        button(text(self.i18n.tr("save"))).on_press(Message::ClickedSave),
+                        button(text(self.i18n.tr("go-to-item-determiner")))
+                            .on_press(Message::ChangeScreen(Screen::ItemDeterminer)),
+                        button(text(self.i18n.tr("go-to-comps")))
+                            .on_press(Message::ChangeScreen(Screen::Comps)),
+                        button(text(self.i18n.tr("go-to-game-log")))
+                            .on_press(Message::ChangeScreen(Screen::GameLog)),
+                        button(text(self.i18n.tr("go-to-augments")))
+                            .on_press(Message::ChangeScreen(Screen::Augments)),
+                        button(text(self.i18n.tr("go-to-tier-lists")))
+                            .on_press(Message::ChangeScreen(Screen::TierLists)),
+                        button(text(self.i18n.tr("go-to-patch-changes")))
+                            .on_press(Message::ChangeScreen(Screen::Changelog)),
+                        button(text(self.i18n.tr("go-to-settings")))
+                            .on_press(Message::ChangeScreen(Screen::Settings)),
+                    ))
                 ))
                 .width(Length::Fill)
                 .height(Length::Fill)
                 .into()
             }
+            Screen::Comps => self.comps_view(),
+            Screen::GameLog => self.game_log_view(),
+            Screen::Analytics => self.analytics_view(),
+            Screen::Augments => self.augments_view(),
+            Screen::TierLists => self.tier_lists_view(),
+            Screen::Changelog => self.changelog_view(),
+            Screen::Settings => self.settings_view(),
             Screen::ItemDeterminer => {
                 let item_chunks = self.components.clone().into_iter().chunks(3);
                 let mut item_rows = vec![];
@@ -399,59 +1204,7 @@ impl Sandbox for Model {
                     comp_rows.into_iter().map(|x| x.into()).collect::<Vec<_>>(),
                 )));
                 // now show the champions that like these items
-                let mut sorted_champs = self.champs.clone();
-                sorted_champs.sort_by(|a: &ChampionState, b| {
-                    let a_items: Vec<String> = a
-                        .items
-                        .iter()
-                        .flat_map(|item| item.composition.clone())
-                        .collect();
-                    let b_items: Vec<String> = b
-                        .items
-                        .iter()
-                        .flat_map(|item| item.composition.clone())
-                        .collect();
-                    let a_map = a_items.iter().fold(HashMap::new(), |mut acc, c| {
-                        *acc.entry(c).or_insert(0usize) += 1;
-                        acc
-                    });
-                    let b_map = b_items.iter().fold(HashMap::new(), |mut acc, c| {
-                        *acc.entry(c).or_insert(0usize) += 1;
-                        acc
-                    });
-
-                    let comp_map = self.components.iter().fold(HashMap::new(), |mut acc, c| {
-                        acc.insert(&c.component.api_name, c.count);
-                        acc
-                    });
-
-                    let mut a_total = 0;
-                    let mut b_total = 0;
-
-                    for (comp_name, comp_count) in comp_map.iter() {
-                        for (a_name, a_count) in a_map.clone() {
-                            if comp_name == &a_name {
-                                a_total += if *comp_count > a_count {
-                                    a_count
-                                } else {
-                                    *comp_count
-                                };
-                            }
-                        }
-                    }
-                    for (comp_name, comp_count) in comp_map {
-                        for (b_name, b_count) in b_map.clone() {
-                            if comp_name == b_name {
-                                b_total += if comp_count > b_count {
-                                    b_count
-                                } else {
-                                    comp_count
-                                };
-                            }
-                        }
-                    }
-                    b_total.cmp(&a_total)
-                });
+                let sorted_champs = rank_champions(&self.champs, &self.components);
 
                 let chunks = sorted_champs.into_iter().chunks(3);
                 let mut rows = vec![];
@@ -459,10 +1212,10 @@ impl Sandbox for Model {
                 for chunk in &chunks {
                     rows.push(row(chunk
                         .into_iter()
-                        .map(|a| {
+                        .map(|(a, _)| {
                             column!(
                                 Image::new(a.champ.square_icon.handle.clone()),
-                                text(a.champ.name)
+                                text(&a.champ.name)
                             )
                             .into()
                         })
@@ -473,7 +1226,11 @@ impl Sandbox for Model {
                 container(row!(
                     item_col,
                     scrollable(champion_col),
-                    button(text("Go to Character Builder")).on_press(Message::ChangeScreen)
+                    column!(
+                        self.live_panel(),
+                        button(text(self.i18n.tr("go-to-character-builder")))
+                            .on_press(Message::ChangeScreen(Screen::CharacterBuilder))
+                    )
                 ))
                 .width(Length::Fill)
                 .height(Length::Fill)
@@ -483,7 +1240,10 @@ impl Sandbox for Model {
     }
 
     fn theme(&self) -> Theme {
-        Theme::default()
+        match self.shown_config().theme {
+            ThemeName::Light => Theme::Light,
+            ThemeName::Dark => Theme::Dark,
+        }
     }
 
     fn style(&self) -> theme::Application {
@@ -491,14 +1251,36 @@ impl Sandbox for Model {
     }
 
     fn scale_factor(&self) -> f64 {
-        1.0
+        // a scale being typed may be out of range for a moment
+        self.shown_config().scale.clamp(MIN_SCALE, MAX_SCALE)
     }
 
-    fn run(settings: Settings<()>) -> Result<(), iced::Error>
-    where
-        Self: 'static + Sized,
-    {
-        <Self as iced::Application>::run(settings)
+    fn subscription(&self) -> Subscription<Message> {
+        let events = subscription::events_with(|event, status| match event {
+            Event::Keyboard(keyboard::Event::KeyPressed {
+                key_code,
+                modifiers,
+            }) if matches!(status, event::Status::Ignored) && modifiers.command() => match key_code
+            {
+                keyboard::KeyCode::Z if modifiers.shift() => Some(Message::Redo),
+                keyboard::KeyCode::Z => Some(Message::Undo),
+                keyboard::KeyCode::Y => Some(Message::Redo),
+                _ => None,
+            },
+            Event::Window(window::Event::CloseRequested) => Some(Message::CloseRequested),
+            _ => None,
+        });
+        let autosave = if self.last_edit.is_some() || self.filters_changed.is_some() {
+            time::every(Duration::from_millis(500)).map(|_| Message::AutosaveTick)
+        } else {
+            Subscription::none()
+        };
+        let live = if self.live_enabled {
+            time::every(LIVE_POLL_INTERVAL).map(|_| Message::Live(LiveMessage::Poll))
+        } else {
+            Subscription::none()
+        };
+        Subscription::batch([events, autosave, live])
     }
 }
 
@@ -507,7 +1289,7 @@ fn main() {
     env_logger::builder().format_timestamp(None).init();
 
     // set up directories
-    let dir = DIR.get_or_init(|| ProjectDirs::from("", "Sharif Haason", "TFT_Notebook").unwrap());
+    let dir = project_dirs();
     CACHE_DIR.get_or_init(|| {
         fs::create_dir_all(dir.cache_dir()).unwrap();
         dir.cache_dir().to_path_buf()
@@ -517,23 +1299,21 @@ fn main() {
         dir.data_dir().to_path_buf()
     });
 
-    ureq::get("https://raw.communitydragon.org/latest/cdragon/tft/en_us.json")
-        .call()
-        .unwrap();
+    let config = Config::load(&config_path()).unwrap_or_else(|e| {
+        log::warn!("{e}, using the default settings");
+        Config::default()
+    });
 
     Model::run(Settings {
+        flags: config,
         antialiasing: true,
         window: iced::window::Settings {
             position: iced::window::Position::Centered,
             ..iced::window::Settings::default()
         },
+        // the notebook is saved before closing
+        exit_on_close_request: false,
         ..Settings::default()
     })
     .unwrap()
-
-    // ok
-    // now we have to read a file that gives bis
-    // maybe use toml
-
-    // println!("{items:#?}");
 }
@@ -0,0 +1,394 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Display,
+    fs, io,
+    path::Path,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::save::write_atomic;
+
+/// Riot's regional routing host for TFT matches.
+pub const RIOT_API_URL: &str = "https://americas.api.riotgames.com";
+
+/// Placements counted as a top 4 finish.
+const TOP_FOUR: u8 = 4;
+
+/// The parts of a Riot TFT match detail response the stats are built from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Match {
+    pub metadata: MatchMetadata,
+    pub info: MatchInfo,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MatchMetadata {
+    pub match_id: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MatchInfo {
+    pub participants: Vec<Participant>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Participant {
+    pub placement: u8,
+    #[serde(default)]
+    pub units: Vec<MatchUnit>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MatchUnit {
+    /// Champion api name.
+    pub character_id: String,
+    /// Item api names.
+    #[serde(default, rename = "itemNames")]
+    pub item_names: Vec<String>,
+    #[serde(default)]
+    pub tier: u8,
+}
+
+#[derive(Debug)]
+pub enum MatchError {
+    Io(io::Error),
+    Json(serde_json::Error),
+    Http(String),
+}
+
+impl Display for MatchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MatchError::Io(e) => write!(f, "{e}"),
+            MatchError::Json(e) => write!(f, "not a match file: {e}"),
+            MatchError::Http(e) => write!(f, "request failed: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for MatchError {}
+
+impl From<io::Error> for MatchError {
+    fn from(e: io::Error) -> Self {
+        MatchError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for MatchError {
+    fn from(e: serde_json::Error) -> Self {
+        MatchError::Json(e)
+    }
+}
+
+/// Reads every `.json` match detail file in a directory.
+pub fn read_match_dir(dir: &Path) -> Result<Vec<Match>, MatchError> {
+    let mut matches = vec![];
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path
+            .extension()
+            .is_some_and(|extension| extension == "json")
+        {
+            matches.push(serde_json::from_str(&fs::read_to_string(path)?)?);
+        }
+    }
+    Ok(matches)
+}
+
+/// Fetches matches from the Riot API, or from anything serving the same routes.
+#[derive(Debug, Clone)]
+pub struct MatchClient {
+    pub base_url: String,
+    pub api_key: String,
+}
+
+impl MatchClient {
+    pub fn new(base_url: &str, api_key: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            api_key: api_key.to_string(),
+        }
+    }
+
+    fn get(&self, path: &str) -> Result<String, MatchError> {
+        ureq::get(&format!("{}{path}", self.base_url))
+            .set("X-Riot-Token", &self.api_key)
+            .call()
+            .map_err(|e| MatchError::Http(e.to_string()))?
+            .into_string()
+            .map_err(MatchError::Io)
+    }
+
+    /// Ids of a player's most recent matches.
+    pub fn match_ids(&self, puuid: &str, count: usize) -> Result<Vec<String>, MatchError> {
+        let body = self.get(&format!(
+            "/tft/match/v1/matches/by-puuid/{puuid}/ids?count={count}"
+        ))?;
+        Ok(serde_json::from_str(&body)?)
+    }
+
+    /// The raw match detail JSON, for saving next to other match files.
+    pub fn match_json(&self, match_id: &str) -> Result<String, MatchError> {
+        self.get(&format!("/tft/match/v1/matches/{match_id}"))
+    }
+
+    pub fn fetch_match(&self, match_id: &str) -> Result<Match, MatchError> {
+        Ok(serde_json::from_str(&self.match_json(match_id)?)?)
+    }
+}
+
+/// How one set of items did on a champion.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ComboStats {
+    /// Sorted item api names.
+    pub items: Vec<String>,
+    pub games: u32,
+    pub placement_total: u32,
+    pub top_four: u32,
+}
+
+impl ComboStats {
+    pub fn average_placement(&self) -> f64 {
+        self.placement_total as f64 / self.games.max(1) as f64
+    }
+
+    pub fn top_four_rate(&self) -> f64 {
+        self.top_four as f64 / self.games.max(1) as f64
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChampionStats {
+    pub games: u32,
+    /// Games each item was held in.
+    pub item_games: BTreeMap<String, u32>,
+    pub combos: Vec<ComboStats>,
+}
+
+impl ChampionStats {
+    /// Share of the champion's games in which it held `item`.
+    pub fn item_frequency(&self, item: &str) -> f64 {
+        self.item_games.get(item).copied().unwrap_or_default() as f64 / self.games.max(1) as f64
+    }
+
+    /// Item sets played at least `min_games` times, best average placement first.
+    pub fn best_combos(&self, min_games: u32) -> Vec<&ComboStats> {
+        let mut combos: Vec<&ComboStats> = self
+            .combos
+            .iter()
+            .filter(|combo| combo.games >= min_games && !combo.items.is_empty())
+            .collect();
+        combos.sort_by(|a, b| a.average_placement().total_cmp(&b.average_placement()));
+        combos
+    }
+}
+
+/// Per-champion item statistics over every imported match.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MatchStats {
+    /// Ids of the counted matches, so importing a match twice doesn't count it twice.
+    pub matches: BTreeSet<String>,
+    pub champions: BTreeMap<String, ChampionStats>,
+}
+
+impl MatchStats {
+    pub fn load(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let s = serde_json::to_string(self)?;
+        write_atomic(path, s.as_bytes())
+    }
+
+    /// Counts a match. Returns `false` if it was counted before.
+    pub fn add_match(&mut self, game: &Match) -> bool {
+        if !self.matches.insert(game.metadata.match_id.clone()) {
+            return false;
+        }
+        for participant in game.info.participants.iter() {
+            for unit in participant.units.iter() {
+                let stats = self.champions.entry(unit.character_id.clone()).or_default();
+                stats.games += 1;
+                // a unit holding two copies of an item counts as one game for it
+                let held: BTreeSet<&String> = unit.item_names.iter().collect();
+                for item in held {
+                    *stats.item_games.entry(item.clone()).or_default() += 1;
+                }
+
+                let mut items = unit.item_names.clone();
+                items.sort();
+                let index = match stats.combos.iter().position(|combo| combo.items == items) {
+                    Some(index) => index,
+                    None => {
+                        stats.combos.push(ComboStats {
+                            items,
+                            ..ComboStats::default()
+                        });
+                        stats.combos.len() - 1
+                    }
+                };
+                let combo = &mut stats.combos[index];
+                combo.games += 1;
+                combo.placement_total += participant.placement as u32;
+                combo.top_four += u32::from(participant.placement <= TOP_FOUR);
+            }
+        }
+        true
+    }
+
+    /// Counts every match, returning how many were new.
+    pub fn add_matches<'a>(&mut self, matches: impl IntoIterator<Item = &'a Match>) -> usize {
+        matches
+            .into_iter()
+            .filter(|game| self.add_match(game))
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use super::*;
+
+    const BLUE_BUFF: &str = "TFT_Item_BlueBuff";
+    const SHOJIN: &str = "TFT_Item_SpearOfShojin";
+
+    /// A match detail response in Riot's shape, with one unit per placement.
+    fn match_json(id: &str, units: &[(u8, &str, &[&str])]) -> Value {
+        json!({
+            "metadata": {"match_id": id, "participants": []},
+            "info": {
+                "tft_set_number": 9,
+                "participants": units.iter().map(|(placement, champion, items)| json!({
+                    "placement": placement,
+                    "level": 8,
+                    "units": [{"character_id": champion, "itemNames": items, "tier": 2}],
+                })).collect::<Vec<_>>(),
+            },
+        })
+    }
+
+    fn matches() -> Vec<Match> {
+        [
+            match_json(
+                "NA1_1",
+                &[
+                    (1, "TFT9_Ahri", &[BLUE_BUFF, BLUE_BUFF, SHOJIN]),
+                    (6, "TFT9_Ahri", &[BLUE_BUFF]),
+                    (8, "TFT9_Garen", &[]),
+                ],
+            ),
+            match_json(
+                "NA1_2",
+                &[
+                    (3, "TFT9_Ahri", &[SHOJIN, BLUE_BUFF, BLUE_BUFF]),
+                    (5, "TFT9_Garen", &[]),
+                ],
+            ),
+        ]
+        .into_iter()
+        .map(|json| serde_json::from_value(json).unwrap())
+        .collect()
+    }
+
+    #[test]
+    fn items_are_counted_once_per_game() {
+        let mut stats = MatchStats::default();
+        assert_eq!(stats.add_matches(&matches()), 2);
+        let ahri = &stats.champions["TFT9_Ahri"];
+        assert_eq!(ahri.games, 3);
+        assert_eq!(ahri.item_games[BLUE_BUFF], 3);
+        assert_eq!(ahri.item_frequency(BLUE_BUFF), 1.0);
+        assert_eq!(ahri.item_frequency(SHOJIN), 2.0 / 3.0);
+        assert_eq!(ahri.item_frequency("TFT_Item_ChainVest"), 0.0);
+        assert_eq!(stats.champions["TFT9_Garen"].games, 2);
+    }
+
+    #[test]
+    fn combos_track_placement_and_top_four() {
+        let mut stats = MatchStats::default();
+        stats.add_matches(&matches());
+        let ahri = &stats.champions["TFT9_Ahri"];
+
+        // the same items in any order are one combo
+        let [best, worst] = ahri.best_combos(1)[..] else {
+            panic!("{:?}", ahri.combos);
+        };
+        assert_eq!(best.items, [BLUE_BUFF, BLUE_BUFF, SHOJIN]);
+        assert_eq!(best.games, 2);
+        assert_eq!(best.average_placement(), 2.0);
+        assert_eq!(best.top_four_rate(), 1.0);
+        assert_eq!(worst.items, [BLUE_BUFF]);
+        assert_eq!(worst.average_placement(), 6.0);
+        assert_eq!(worst.top_four_rate(), 0.0);
+
+        assert_eq!(ahri.best_combos(2), [best]);
+        // units without items have no combo to suggest
+        assert!(stats.champions["TFT9_Garen"].best_combos(1).is_empty());
+    }
+
+    #[test]
+    fn matches_are_only_counted_once() {
+        let mut stats = MatchStats::default();
+        let matches = matches();
+        assert_eq!(stats.add_matches(&matches[..1]), 1);
+        assert_eq!(stats.add_matches(&matches), 1);
+        assert_eq!(stats.champions["TFT9_Ahri"].games, 3);
+    }
+
+    #[test]
+    fn match_files_are_read_from_a_folder() {
+        let dir = std::env::temp_dir().join(format!("tft_matches_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        for game in matches() {
+            let path = dir.join(format!("{}.json", game.metadata.match_id));
+            fs::write(path, serde_json::to_string(&game).unwrap()).unwrap();
+        }
+        fs::write(dir.join("notes.txt"), "not a match").unwrap();
+        assert_eq!(read_match_dir(&dir).unwrap().len(), 2);
+
+        fs::write(dir.join("broken.json"), "{").unwrap();
+        assert!(matches!(read_match_dir(&dir), Err(MatchError::Json(_))));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn matches_are_fetched_from_a_local_mock() {
+        let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+        let port = server.server_addr().to_ip().unwrap().port();
+        let detail = match_json("NA1_1", &[(2, "TFT9_Ahri", &[SHOJIN])]).to_string();
+        let mock = std::thread::spawn(move || {
+            for request in server.incoming_requests().take(2) {
+                let key = request
+                    .headers()
+                    .iter()
+                    .find(|header| header.field.equiv("X-Riot-Token"))
+                    .map(|header| header.value.to_string());
+                let body = match request.url() {
+                    "/tft/match/v1/matches/by-puuid/me/ids?count=1" => String::from(r#"["NA1_1"]"#),
+                    "/tft/match/v1/matches/NA1_1" => detail.clone(),
+                    url => panic!("unexpected request for {url}"),
+                };
+                assert_eq!(key.as_deref(), Some("key"));
+                request
+                    .respond(tiny_http::Response::from_string(body))
+                    .unwrap();
+            }
+        });
+
+        let client = MatchClient::new(&format!("http://127.0.0.1:{port}/"), "key");
+        let ids = client.match_ids("me", 1).unwrap();
+        assert_eq!(ids, ["NA1_1"]);
+        let game = client.fetch_match(&ids[0]).unwrap();
+        assert_eq!(game.info.participants[0].placement, 2);
+        mock.join().unwrap();
+    }
+}
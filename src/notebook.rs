@@ -0,0 +1,128 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::save::{SaveError, SaveFile};
+
+pub const DEFAULT_NOTEBOOK: &str = "Default";
+
+/// Named notebooks, each its own save file in the `notebooks` directory.
+#[derive(Debug, Clone)]
+pub struct NotebookStore {
+    dir: PathBuf,
+}
+
+/// Names become file names, so they are limited to letters, digits, spaces, `-` and `_`.
+pub fn validate_name(name: &str) -> Result<(), SaveError> {
+    let valid = !name.trim().is_empty()
+        && name.len() <= 64
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == ' ' || c == '-' || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(SaveError::InvalidName(name.to_string()))
+    }
+}
+
+impl NotebookStore {
+    /// Opens the notebooks in `data_dir`. The first time, the old single `champ_info.json`
+    /// save becomes the default notebook.
+    pub fn open(data_dir: &Path) -> Result<Self, SaveError> {
+        let store = Self {
+            dir: data_dir.join("notebooks"),
+        };
+        fs::create_dir_all(&store.dir)?;
+        if store.list()?.is_empty() {
+            let save = SaveFile::read(&data_dir.join("champ_info.json"))?;
+            save.write(&store.path(DEFAULT_NOTEBOOK))?;
+        }
+        Ok(store)
+    }
+
+    /// The notebooks in `data_dir` without creating or migrating anything, for when `open`
+    /// failed. Notebooks that can't be read are never saved over, so nothing is lost.
+    pub fn unopened(data_dir: &Path) -> Self {
+        Self {
+            dir: data_dir.join("notebooks"),
+        }
+    }
+
+    fn path(&self, name: &str) -> PathBuf {
+        self.dir.join(format!("{name}.json"))
+    }
+
+    pub fn exists(&self, name: &str) -> bool {
+        self.path(name).exists()
+    }
+
+    /// Notebook names in alphabetical order.
+    pub fn list(&self) -> Result<Vec<String>, SaveError> {
+        let mut names = vec![];
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                if let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) {
+                    names.push(name.to_string());
+                }
+            }
+        }
+        names.sort();
+        Ok(names)
+    }
+
+    pub fn load(&self, name: &str) -> Result<SaveFile, SaveError> {
+        SaveFile::read(&self.path(name))
+    }
+
+    pub fn save(&self, name: &str, save: &SaveFile) -> Result<(), SaveError> {
+        save.write(&self.path(name))
+    }
+
+    fn check_free(&self, name: &str) -> Result<(), SaveError> {
+        validate_name(name)?;
+        if self.exists(name) {
+            return Err(SaveError::NameTaken(name.to_string()));
+        }
+        Ok(())
+    }
+
+    pub fn create(&self, name: &str) -> Result<(), SaveError> {
+        self.check_free(name)?;
+        self.save(name, &SaveFile::default())
+    }
+
+    pub fn rename(&self, name: &str, new_name: &str) -> Result<(), SaveError> {
+        self.check_free(new_name)?;
+        fs::rename(self.path(name), self.path(new_name))?;
+        if self.last_used().as_deref() == Some(name) {
+            self.set_last_used(new_name)?;
+        }
+        Ok(())
+    }
+
+    pub fn duplicate(&self, name: &str, new_name: &str) -> Result<(), SaveError> {
+        self.check_free(new_name)?;
+        fs::copy(self.path(name), self.path(new_name))?;
+        Ok(())
+    }
+
+    pub fn delete(&self, name: &str) -> Result<(), SaveError> {
+        fs::remove_file(self.path(name))?;
+        Ok(())
+    }
+
+    /// The notebook that was open when the app was last used, if it still exists.
+    pub fn last_used(&self) -> Option<String> {
+        fs::read_to_string(self.dir.join("last_used.txt"))
+            .ok()
+            .map(|name| name.trim().to_string())
+            .filter(|name| self.exists(name))
+    }
+
+    pub fn set_last_used(&self, name: &str) -> io::Result<()> {
+        fs::write(self.dir.join("last_used.txt"), name)
+    }
+}
@@ -0,0 +1,500 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    fs::{self, File},
+    io::{self, Write},
+    path::Path,
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+use crate::{
+    comp::Comp,
+    database::TftDatabase,
+    state::ChampionState,
+    tier_list::{Rank, TierList},
+};
+
+pub const CURRENT_VERSION: u32 = 4;
+
+/// `MIGRATIONS[n]` upgrades a version `n` save to version `n + 1`.
+const MIGRATIONS: &[fn(Value) -> Value] = &[
+    migrate_v0_to_v1,
+    migrate_v1_to_v2,
+    migrate_v2_to_v3,
+    migrate_v3_to_v4,
+];
+
+/// A notebook's save file. Only api names are stored so a save outlives patches and sets.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SaveFile {
+    pub version: u32,
+    /// Champion api name to the api names of its BIS items.
+    pub champions: BTreeMap<String, Vec<String>>,
+    pub comps: Vec<Comp>,
+    pub notes: String,
+    /// Augment api name to its rank. Unranked augments are left out.
+    pub augment_tiers: BTreeMap<String, Rank>,
+    /// Champion tier lists.
+    pub tier_lists: Vec<TierList>,
+}
+
+impl Default for SaveFile {
+    fn default() -> Self {
+        Self {
+            version: CURRENT_VERSION,
+            champions: BTreeMap::new(),
+            comps: vec![],
+            notes: String::new(),
+            augment_tiers: BTreeMap::new(),
+            tier_lists: vec![],
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum SaveError {
+    Io(io::Error),
+    Json(serde_json::Error),
+    /// The save was written by a newer version of the app.
+    UnsupportedVersion(u32),
+    InvalidName(String),
+    NameTaken(String),
+}
+
+impl Display for SaveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SaveError::Io(e) => write!(f, "could not access save file: {e}"),
+            SaveError::Json(e) => write!(f, "save file is malformed: {e}"),
+            SaveError::UnsupportedVersion(version) => write!(
+                f,
+                "save file version {version} is newer than the supported version {CURRENT_VERSION}"
+            ),
+            SaveError::InvalidName(name) => write!(
+                f,
+                "\"{name}\" is not a valid name, use letters, digits, spaces, - and _"
+            ),
+            SaveError::NameTaken(name) => write!(f, "\"{name}\" already exists"),
+        }
+    }
+}
+
+impl std::error::Error for SaveError {}
+
+impl From<io::Error> for SaveError {
+    fn from(e: io::Error) -> Self {
+        SaveError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for SaveError {
+    fn from(e: serde_json::Error) -> Self {
+        SaveError::Json(e)
+    }
+}
+
+/// Saved references that don't exist in the current data.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SaveReport {
+    pub unknown_champions: Vec<String>,
+    /// Champion api name and the unknown item api name.
+    pub unknown_items: Vec<(String, String)>,
+}
+
+impl SaveReport {
+    pub fn is_empty(&self) -> bool {
+        self.unknown_champions.is_empty() && self.unknown_items.is_empty()
+    }
+}
+
+impl Display for SaveReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for champ in &self.unknown_champions {
+            writeln!(f, "unknown champion {champ}")?;
+        }
+        for (champ, item) in &self.unknown_items {
+            writeln!(f, "unknown item {item} on {champ}")?;
+        }
+        Ok(())
+    }
+}
+
+/// BIS entries kept aside by `SaveFile::unmatched`, by champion api name: each item api name
+/// with its index in the saved list.
+pub type UnmatchedBis = BTreeMap<String, Vec<(usize, String)>>;
+
+/// Writes to a temporary file next to `path` and renames it over `path`, so a crash mid-write
+/// leaves either the old or the new contents behind, never a truncated file.
+pub fn write_atomic(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let mut tmp_path = path.as_os_str().to_owned();
+    tmp_path.push(".tmp");
+    let mut file = File::create(&tmp_path)?;
+    file.write_all(contents)?;
+    file.sync_all()?;
+    fs::rename(&tmp_path, path)
+}
+
+fn version_of(value: &Value) -> u32 {
+    if value.is_array() {
+        // the unversioned format was a bare list
+        0
+    } else {
+        value
+            .get("version")
+            .and_then(Value::as_u64)
+            .unwrap_or_default() as u32
+    }
+}
+
+/// Version 0 is the old `Vec<ChampionState>` dump with full champion and item data.
+fn migrate_v0_to_v1(value: Value) -> Value {
+    let mut champions = Map::new();
+    for state in value.as_array().into_iter().flatten() {
+        let Some(api_name) = state["champ"]["apiName"].as_str() else {
+            continue;
+        };
+        let items: Vec<Value> = state["items"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|item| item["apiName"].as_str())
+            .map(Value::from)
+            .collect();
+        champions.insert(api_name.to_string(), Value::Array(items));
+    }
+    json!({ "version": 1, "champions": champions })
+}
+
+/// Version 2 turned the BIS save into a notebook with comps and notes.
+fn migrate_v1_to_v2(mut value: Value) -> Value {
+    value["version"] = json!(2);
+    value["comps"] = json!([]);
+    value["notes"] = json!("");
+    value
+}
+
+/// Version 3 added the augment tier list.
+fn migrate_v2_to_v3(mut value: Value) -> Value {
+    value["version"] = json!(3);
+    value["augment_tiers"] = json!({});
+    value
+}
+
+/// Version 4 added champion tier lists.
+fn migrate_v3_to_v4(mut value: Value) -> Value {
+    value["version"] = json!(4);
+    value["tier_lists"] = json!([]);
+    value
+}
+
+/// Upgrades a save of any known version to the current version.
+pub fn migrate(mut value: Value) -> Result<Value, SaveError> {
+    let version = version_of(&value);
+    if version > CURRENT_VERSION {
+        return Err(SaveError::UnsupportedVersion(version));
+    }
+    for migration in &MIGRATIONS[version as usize..] {
+        value = migration(value);
+    }
+    Ok(value)
+}
+
+impl SaveFile {
+    /// Reads and migrates a save file. A missing file is an empty save.
+    pub fn read(path: &Path) -> Result<Self, SaveError> {
+        let s = match fs::read_to_string(path) {
+            Ok(s) => s,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(e.into()),
+        };
+        Self::parse(&s)
+    }
+
+    pub fn parse(s: &str) -> Result<Self, SaveError> {
+        let value: Value = serde_json::from_str(s)?;
+        Ok(serde_json::from_value(migrate(value)?)?)
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), SaveError> {
+        let s = serde_json::to_string_pretty(self)?;
+        write_atomic(path, s.as_bytes())?;
+        Ok(())
+    }
+
+    /// Champions without any items are left out.
+    pub fn from_champions(champs: &[ChampionState]) -> Self {
+        Self {
+            champions: champs
+                .iter()
+                .filter(|champ_state| !champ_state.items.is_empty())
+                .map(|champ_state| {
+                    (
+                        champ_state.champ.api_name.clone(),
+                        champ_state
+                            .items
+                            .iter()
+                            .map(|item| item.api_name.clone())
+                            .collect(),
+                    )
+                })
+                .collect(),
+            ..Self::default()
+        }
+    }
+
+    /// The saved BIS entries `db` doesn't know about: whole lists of unknown champions, and the
+    /// unknown items of known ones. `reconcile` leaves them out, so they're kept aside while the
+    /// notebook is open and put back with `with_unmatched` when it's written.
+    pub fn unmatched(&self, db: &TftDatabase) -> UnmatchedBis {
+        self.champions
+            .iter()
+            .filter_map(|(champion, items)| {
+                let champion_known = db.champion(champion).is_some();
+                let items: Vec<(usize, String)> = items
+                    .iter()
+                    .cloned()
+                    .enumerate()
+                    .filter(|(_, item)| !champion_known || db.item(item).is_none())
+                    .collect();
+                (!items.is_empty()).then(|| (champion.clone(), items))
+            })
+            .collect()
+    }
+
+    /// Puts entries set aside by `unmatched` back into the BIS lists, where they were saved.
+    pub fn with_unmatched(mut self, unmatched: &UnmatchedBis) -> Self {
+        for (champion, items) in unmatched {
+            let list = self.champions.entry(champion.clone()).or_default();
+            // in saved order, so the items that were before each one are already back
+            for (index, item) in items {
+                list.insert((*index).min(list.len()), item.clone());
+            }
+        }
+        self
+    }
+
+    /// Resolves the saved references against `db`, giving every champion in the data its
+    /// saved items. Anything the data doesn't know about is left out and reported.
+    pub fn reconcile(&self, db: &TftDatabase) -> (Vec<ChampionState>, SaveReport) {
+        let mut report = SaveReport::default();
+        for api_name in self.champions.keys() {
+            if db.champion(api_name).is_none() {
+                report.unknown_champions.push(api_name.clone());
+            }
+        }
+
+        let champs = db
+            .champions
+            .iter()
+            .map(|champ| {
+                let items = self
+                    .champions
+                    .get(&champ.api_name)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|item_api_name| {
+                        let item = db.item(item_api_name);
+                        if item.is_none() {
+                            report
+                                .unknown_items
+                                .push((champ.api_name.clone(), item_api_name.clone()));
+                        }
+                        item.cloned()
+                    })
+                    .collect();
+                ChampionState {
+                    champ: champ.clone(),
+                    items,
+                }
+            })
+            .collect();
+        (champs, report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::comp::CompUnit;
+    use crate::tier_list::TierRow;
+
+    fn bis(entries: &[(&str, &[&str])]) -> BTreeMap<String, Vec<String>> {
+        entries
+            .iter()
+            .map(|(champion, items)| {
+                let items = items.iter().map(|item| item.to_string()).collect();
+                (champion.to_string(), items)
+            })
+            .collect()
+    }
+
+    /// A small save of every version, `FIXTURES[n]` being version `n`.
+    const FIXTURES: [&str; 5] = [
+        include_str!("../tests/fixtures/save_v0.json"),
+        include_str!("../tests/fixtures/save_v1.json"),
+        include_str!("../tests/fixtures/save_v2.json"),
+        include_str!("../tests/fixtures/save_v3.json"),
+        include_str!("../tests/fixtures/save_v4.json"),
+    ];
+
+    fn fixture(version: usize) -> SaveFile {
+        SaveFile::parse(FIXTURES[version]).unwrap()
+    }
+
+    #[test]
+    fn every_version_has_a_fixture() {
+        assert_eq!(FIXTURES.len(), CURRENT_VERSION as usize + 1);
+    }
+
+    #[test]
+    fn v0_saves_keep_the_api_names() {
+        let expected = SaveFile {
+            champions: bis(&[
+                (
+                    "TFT8_Ahri",
+                    &["TFT_Item_BlueBuff", "TFT_Item_JeweledGauntlet"],
+                ),
+                ("TFT8_Annie", &[]),
+            ]),
+            ..SaveFile::default()
+        };
+        assert_eq!(fixture(0), expected);
+    }
+
+    #[test]
+    fn v1_saves_get_empty_comps_and_notes() {
+        let expected = SaveFile {
+            champions: bis(&[(
+                "TFT8_Ahri",
+                &["TFT_Item_BlueBuff", "TFT_Item_JeweledGauntlet"],
+            )]),
+            ..SaveFile::default()
+        };
+        assert_eq!(fixture(1), expected);
+    }
+
+    #[test]
+    fn v2_saves_keep_comps_and_notes() {
+        let expected = SaveFile {
+            champions: bis(&[("TFT8_Ahri", &["TFT_Item_BlueBuff"])]),
+            comps: vec![Comp {
+                name: String::from("Ahri reroll"),
+                units: vec![CompUnit {
+                    star: 3,
+                    ..CompUnit::new(String::from("TFT8_Ahri"))
+                }],
+                notes: String::new(),
+            }],
+            notes: String::from("Slam Blue Buff early"),
+            ..SaveFile::default()
+        };
+        assert_eq!(fixture(2), expected);
+    }
+
+    #[test]
+    fn v3_saves_keep_augment_tiers() {
+        let expected = SaveFile {
+            champions: bis(&[("TFT8_Ahri", &["TFT_Item_BlueBuff"])]),
+            augment_tiers: BTreeMap::from([(
+                String::from("TFT6_Augment_CyberneticImplants1"),
+                Rank::A,
+            )]),
+            ..SaveFile::default()
+        };
+        assert_eq!(fixture(3), expected);
+    }
+
+    #[test]
+    fn v4_saves_load_as_they_are() {
+        let expected = SaveFile {
+            champions: bis(&[("TFT8_Ahri", &["TFT_Item_BlueBuff"])]),
+            tier_lists: vec![TierList {
+                name: String::from("Patch 13.12"),
+                rows: vec![TierRow {
+                    label: String::from("S"),
+                    champions: vec![String::from("TFT8_Ahri")],
+                }],
+            }],
+            ..SaveFile::default()
+        };
+        assert_eq!(fixture(4), expected);
+    }
+
+    #[test]
+    fn newer_saves_are_refused() {
+        let result = SaveFile::parse(r#"{ "version": 99 }"#);
+        assert!(matches!(result, Err(SaveError::UnsupportedVersion(99))));
+    }
+
+    #[test]
+    fn unknown_entries_survive_a_save() {
+        let path = std::env::temp_dir().join(format!("tft_save_{}.json", std::process::id()));
+        let mut save = SaveFile::default();
+        save.champions.insert(
+            String::from("TFT8_Ahri"),
+            vec![
+                String::from("TFT_Item_Gone"),
+                String::from("TFT_Item_Known"),
+                String::from("TFT_Item_Gone"),
+                String::from("TFT_Item_Removed"),
+                String::from("TFT_Item_Other"),
+            ],
+        );
+        save.champions.insert(
+            String::from("TFT7_Nunu"),
+            vec![String::from("TFT_Item_Known")],
+        );
+        save.write(&path).unwrap();
+
+        // data of another set, knowing only Ahri and one of the items
+        let db = TftDatabase::with_names(&["TFT8_Ahri"], &["TFT_Item_Known", "TFT_Item_Other"]);
+        let loaded = SaveFile::read(&path).unwrap();
+        let (champs, report) = loaded.reconcile(&db);
+        assert_eq!(report.unknown_champions, vec![String::from("TFT7_Nunu")]);
+        let unmatched = loaded.unmatched(&db);
+        assert_eq!(
+            unmatched[&String::from("TFT8_Ahri")],
+            [
+                (0, String::from("TFT_Item_Gone")),
+                (2, String::from("TFT_Item_Gone")),
+                (3, String::from("TFT_Item_Removed")),
+            ]
+        );
+        SaveFile::from_champions(&champs)
+            .with_unmatched(&unmatched)
+            .write(&path)
+            .unwrap();
+
+        let saved = SaveFile::read(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(saved.champions, save.champions);
+    }
+
+    #[test]
+    fn unknown_items_go_back_after_the_items_before_them() {
+        let db = TftDatabase::with_names(&["TFT8_Ahri"], &["TFT_Item_A", "TFT_Item_B"]);
+        let mut save = SaveFile::default();
+        save.champions.insert(
+            String::from("TFT8_Ahri"),
+            [
+                "TFT_Item_A",
+                "TFT_Item_Gone",
+                "TFT_Item_B",
+                "TFT_Item_Removed",
+            ]
+            .map(String::from)
+            .to_vec(),
+        );
+        let unmatched = save.unmatched(&db);
+        let (mut champs, _) = save.reconcile(&db);
+        // B was taken out while the notebook was open
+        champs[0].items.pop();
+        let saved = SaveFile::from_champions(&champs).with_unmatched(&unmatched);
+        assert_eq!(
+            saved.champions[&String::from("TFT8_Ahri")],
+            ["TFT_Item_A", "TFT_Item_Gone", "TFT_Item_Removed"]
+        );
+    }
+}
@@ -0,0 +1,13 @@
+pub mod analytics;
+pub mod audit;
+pub mod augments;
+pub mod calc;
+pub mod changelog;
+pub mod comps;
+pub mod game_log;
+pub mod live;
+pub mod locale;
+pub mod match_stats;
+pub mod notebooks;
+pub mod settings;
+pub mod tier_lists;
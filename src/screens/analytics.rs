@@ -0,0 +1,265 @@
+use iced::mouse;
+use iced::widget::canvas::{self, Canvas, Frame, Geometry, Path, Stroke, Text};
+use iced::widget::{button, column, container, row, scrollable, text, text_input};
+use iced::{Color, Element, Length, Point, Rectangle, Renderer, Size, Theme};
+
+use tft::analytics::*;
+use tft::game_log::is_valid_date;
+use tft::tft_data::format_number;
+
+use crate::{chip_style, Message, Model, Screen};
+
+/// Bars shown per chart.
+const MAX_BARS: usize = 10;
+const BAR_HEIGHT: f32 = 22.0;
+const LABEL_WIDTH: f32 = 160.0;
+/// Games the top 4 rate over time is averaged over.
+const TOP_FOUR_WINDOW: usize = 10;
+
+const BAR_COLOR: Color = Color::from_rgb(0.33, 0.5, 0.8);
+
+#[derive(Debug, Clone)]
+pub enum AnalyticsMessage {
+    FromChanged(String),
+    ToChanged(String),
+    TogglePatch(String),
+}
+
+/// Date inputs of the analytics screen. Only valid dates make it into the filter.
+#[derive(Debug, Default)]
+pub(crate) struct AnalyticsInputs {
+    pub from: String,
+    pub to: String,
+}
+
+/// One labelled horizontal bar per value, scaled to `max`.
+struct BarChart {
+    bars: Vec<(String, f32)>,
+    max: f32,
+}
+
+impl<Message> canvas::Program<Message> for BarChart {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &(),
+        renderer: &Renderer,
+        _theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<Geometry> {
+        let mut frame = Frame::new(renderer, bounds.size());
+        // room for the value printed after the bar
+        let bar_space = (bounds.width - LABEL_WIDTH - 48.0).max(0.0);
+        for (index, (label, value)) in self.bars.iter().enumerate() {
+            let y = index as f32 * BAR_HEIGHT;
+            frame.fill_text(Text {
+                content: label.clone(),
+                position: Point::new(0.0, y + 3.0),
+                ..Text::default()
+            });
+            let width = bar_space * (value / self.max.max(f32::EPSILON)).clamp(0.0, 1.0);
+            frame.fill_rectangle(
+                Point::new(LABEL_WIDTH, y + 2.0),
+                Size::new(width, BAR_HEIGHT - 4.0),
+                BAR_COLOR,
+            );
+            frame.fill_text(Text {
+                content: format_number(*value as f64),
+                position: Point::new(LABEL_WIDTH + width + 4.0, y + 3.0),
+                ..Text::default()
+            });
+        }
+        vec![frame.into_geometry()]
+    }
+}
+
+/// Rates between 0 and 1 joined into a line, with the 50% mark drawn for reference.
+struct LineChart {
+    points: Vec<f32>,
+}
+
+impl<Message> canvas::Program<Message> for LineChart {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &(),
+        renderer: &Renderer,
+        _theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<Geometry> {
+        let mut frame = Frame::new(renderer, bounds.size());
+        let point = |index: usize, rate: f32| {
+            let step = bounds.width / (self.points.len().max(2) - 1) as f32;
+            Point::new(index as f32 * step, bounds.height * (1.0 - rate))
+        };
+        frame.stroke(
+            &Path::line(
+                Point::new(0.0, bounds.height / 2.0),
+                Point::new(bounds.width, bounds.height / 2.0),
+            ),
+            Stroke::default().with_color(Color::from_rgb(0.7, 0.7, 0.7)),
+        );
+        let line = Path::new(|builder| {
+            for (index, rate) in self.points.iter().enumerate() {
+                if index == 0 {
+                    builder.move_to(point(index, *rate));
+                } else {
+                    builder.line_to(point(index, *rate));
+                }
+            }
+        });
+        frame.stroke(
+            &line,
+            Stroke::default().with_width(2.0).with_color(BAR_COLOR),
+        );
+        vec![frame.into_geometry()]
+    }
+}
+
+fn bar_chart<'a>(bars: Vec<(String, f32)>, max: f32) -> Element<'a, Message> {
+    let height = (bars.len().max(1) as f32) * BAR_HEIGHT;
+    Canvas::new(BarChart { bars, max })
+        .width(Length::Fill)
+        .height(Length::Fixed(height))
+        .into()
+}
+
+impl Model {
+    pub(crate) fn update_analytics(&mut self, message: AnalyticsMessage) {
+        match message {
+            AnalyticsMessage::FromChanged(from) => {
+                self.analytics_filter.from = is_valid_date(&from).then(|| from.clone());
+                self.analytics_inputs.from = from;
+            }
+            AnalyticsMessage::ToChanged(to) => {
+                self.analytics_filter.to = is_valid_date(&to).then(|| to.clone());
+                self.analytics_inputs.to = to;
+            }
+            AnalyticsMessage::TogglePatch(patch) => {
+                if !self.analytics_filter.patches.remove(&patch) {
+                    self.analytics_filter.patches.insert(patch);
+                }
+            }
+        }
+    }
+
+    fn champion_name(&self, api_name: &str) -> String {
+        self.db
+            .champion(api_name)
+            .map_or(api_name.to_string(), |champ| champ.name.clone())
+    }
+
+    fn item_name(&self, api_name: &str) -> String {
+        self.db
+            .item(api_name)
+            .map_or(api_name.to_string(), |item| item.name.clone())
+    }
+
+    pub(crate) fn analytics_view(&self) -> Element<Message> {
+        let games = self.analytics_filter.apply(&self.games);
+        let summary = overall(&games);
+
+        let mut patches: Vec<&String> = self.games.iter().map(|game| &game.patch).collect();
+        patches.sort();
+        patches.dedup();
+        let patch_chips = row(patches
+            .into_iter()
+            .map(|patch| {
+                button(text(patch))
+                    .on_press(Message::Analytics(AnalyticsMessage::TogglePatch(
+                        patch.clone(),
+                    )))
+                    .style(chip_style(self.analytics_filter.patches.contains(patch)))
+                    .into()
+            })
+            .collect::<Vec<_>>());
+
+        let placements = |groups: Vec<Group>, name: &dyn Fn(&str) -> String| {
+            bar_chart(
+                groups
+                    .into_iter()
+                    .take(MAX_BARS)
+                    .map(|group| {
+                        (
+                            format!("{} ({})", name(&group.key), group.stats.games),
+                            group.stats.average_placement() as f32,
+                        )
+                    })
+                    .collect(),
+                8.0,
+            )
+        };
+        let mut items = by_item(&games);
+        items.sort_by(|a, b| b.stats.games.cmp(&a.stats.games));
+        let item_rates = bar_chart(
+            items
+                .into_iter()
+                .take(MAX_BARS)
+                .map(|group| {
+                    (
+                        self.item_name(&group.key),
+                        (group.stats.top_four_rate() * 100.0) as f32,
+                    )
+                })
+                .collect(),
+            100.0,
+        );
+        let over_time = Canvas::new(LineChart {
+            points: top_four_over_time(&games, TOP_FOUR_WINDOW)
+                .into_iter()
+                .map(|(_, rate)| rate as f32)
+                .collect(),
+        })
+        .width(Length::Fill)
+        .height(Length::Fixed(160.0));
+
+        let filters = column!(
+            row!(
+                text_input(&self.i18n.tr("analytics-from"), &self.analytics_inputs.from)
+                    .on_input(|from| Message::Analytics(AnalyticsMessage::FromChanged(from))),
+                text_input(&self.i18n.tr("analytics-to"), &self.analytics_inputs.to)
+                    .on_input(|to| Message::Analytics(AnalyticsMessage::ToChanged(to)))
+            ),
+            patch_chips,
+            button(text(self.i18n.tr("go-to-game-log")))
+                .on_press(Message::ChangeScreen(Screen::GameLog))
+        );
+
+        let charts = column!(
+            text(self.i18n.tr_args(
+                "analytics-summary",
+                &[
+                    ("games", summary.games.into()),
+                    ("average", format_number(summary.average_placement()).into()),
+                    (
+                        "top-four",
+                        format_number(summary.top_four_rate() * 100.0).into()
+                    ),
+                    ("wins", format_number(summary.win_rate() * 100.0).into()),
+                ]
+            )),
+            text(self.i18n.tr("analytics-by-comp")),
+            placements(by_comp(&games), &|key| key.to_string()),
+            text(self.i18n.tr("analytics-by-carry")),
+            placements(by_carry(&games), &|key| self.champion_name(key)),
+            text(self.i18n.tr_args(
+                "analytics-top-four-over-time",
+                &[("games", TOP_FOUR_WINDOW.into())]
+            )),
+            over_time,
+            text(self.i18n.tr("analytics-top-four-by-item")),
+            item_rates,
+            text(self.i18n.tr("analytics-by-patch")),
+            placements(by_patch(&games), &|key| key.to_string())
+        );
+
+        container(row!(filters, scrollable(charts)))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into()
+    }
+}
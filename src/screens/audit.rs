@@ -0,0 +1,118 @@
+use iced::widget::{button, column, row, text};
+use iced::Element;
+
+use tft::audit::{Finding, Issue};
+use tft::history::Edit;
+use tft::state::ChampionState;
+use tft::tft_data::format_number;
+
+use crate::{Message, Model};
+
+#[derive(Debug, Clone)]
+pub enum AuditMessage {
+    /// Swaps a flagged item on a champion for a suggested one.
+    Replace {
+        champion: String,
+        item: String,
+        replacement: String,
+    },
+    /// Keeps a flagged item as it is.
+    Keep { champion: String, item: String },
+}
+
+impl Model {
+    pub(crate) fn update_audit(&mut self, message: AuditMessage) {
+        match message {
+            AuditMessage::Replace {
+                champion,
+                item,
+                replacement,
+            } => {
+                let Some(champ_state) = self.find_champ_mut(&champion) else {
+                    return;
+                };
+                let from: Vec<String> = champ_state
+                    .items
+                    .iter()
+                    .map(|item| item.api_name.clone())
+                    .collect();
+                let mut to = from.clone();
+                match to.iter().position(|api_name| *api_name == item) {
+                    Some(index) => to[index] = replacement,
+                    // removed items are only kept aside for saving
+                    None => {
+                        to.push(replacement);
+                        if let Some(unmatched) = self.unmatched_bis.get_mut(&champion) {
+                            unmatched.retain(|(_, api_name)| *api_name != item);
+                        }
+                    }
+                }
+                self.perform(Edit::ReplaceItems {
+                    champion: champion.clone(),
+                    from,
+                    to,
+                });
+                self.audit.resolve(&champion, &item);
+            }
+            AuditMessage::Keep { champion, item } => self.audit.resolve(&champion, &item),
+        }
+    }
+
+    fn finding_text(&self, finding: &Finding) -> String {
+        let item = ("item", finding.item_name.as_str().into());
+        match &finding.issue {
+            Issue::Removed => self.i18n.tr_args("audit-removed", &[item]),
+            Issue::RecipeChanged { old, new } => self.i18n.tr_args(
+                "audit-recipe-changed",
+                &[
+                    item,
+                    ("old", old.join(" + ").into()),
+                    ("new", new.join(" + ").into()),
+                ],
+            ),
+            Issue::EffectChanged { effect, old, new } => self.i18n.tr_args(
+                "audit-effect-changed",
+                &[
+                    item,
+                    ("effect", effect.as_str().into()),
+                    ("old", format_number(*old).into()),
+                    ("new", format_number(*new).into()),
+                ],
+            ),
+        }
+    }
+
+    /// Saved items of the champion that changed since the previous data, with replacements.
+    pub(crate) fn audit_panel(&self, champ_state: &ChampionState) -> Element<Message> {
+        let champion = &champ_state.champ.api_name;
+        let mut panel = column!();
+        for finding in self.audit.for_champion(champion) {
+            let replace_buttons = finding.replacements.iter().filter_map(|api_name| {
+                let replacement = self.db.item(api_name)?;
+                Some(
+                    button(text(self.i18n.tr_args(
+                        "audit-use",
+                        &[("item", replacement.name.as_str().into())],
+                    )))
+                    .on_press(Message::Audit(AuditMessage::Replace {
+                        champion: champion.clone(),
+                        item: finding.item.clone(),
+                        replacement: api_name.clone(),
+                    }))
+                    .into(),
+                )
+            });
+            let keep_button = button(text(self.i18n.tr("audit-keep"))).on_press(Message::Audit(
+                AuditMessage::Keep {
+                    champion: champion.clone(),
+                    item: finding.item.clone(),
+                },
+            ));
+            panel = panel.push(text(self.finding_text(finding)));
+            panel = panel.push(row(replace_buttons
+                .chain(std::iter::once(keep_button.into()))
+                .collect::<Vec<_>>()));
+        }
+        panel.into()
+    }
+}
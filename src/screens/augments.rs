@@ -0,0 +1,146 @@
+use iced::widget::{button, column, container, row, scrollable, text, text_input, Image};
+use iced::{Element, Length};
+
+use tft::search::matches_query;
+use tft::tft_data::{Augment, AugmentTier};
+use tft::tier_list::Rank;
+
+use crate::{chip_style, Message, Model, Screen};
+
+/// Search state of the augment browser.
+#[derive(Debug, Default)]
+pub(crate) struct AugmentBrowser {
+    pub query: String,
+    /// Only show augments of this tier.
+    pub tier: Option<AugmentTier>,
+}
+
+#[derive(Debug, Clone)]
+pub enum AugmentMessage {
+    QueryChanged(String),
+    ToggleTier(AugmentTier),
+    /// Augment api name and its new rank, `None` to unrank it.
+    SetRank(String, Option<Rank>),
+}
+
+impl Model {
+    pub(crate) fn update_augments(&mut self, message: AugmentMessage) {
+        match message {
+            AugmentMessage::QueryChanged(query) => self.augment_browser.query = query,
+            AugmentMessage::ToggleTier(tier) => {
+                let browser = &mut self.augment_browser;
+                browser.tier = (browser.tier != Some(tier)).then_some(tier);
+            }
+            AugmentMessage::SetRank(api_name, rank) => {
+                match rank {
+                    Some(rank) => self.augment_tiers.insert(api_name, rank),
+                    None => self.augment_tiers.remove(&api_name),
+                };
+                self.mark_dirty();
+            }
+        }
+    }
+
+    fn augment_row(&self, augment: &Augment) -> Element<Message> {
+        let current = self.augment_tiers.get(&augment.api_name).copied();
+        let rank_buttons = row(Rank::ALL
+            .into_iter()
+            .map(|rank| {
+                let new_rank = (current != Some(rank)).then_some(rank);
+                button(text(rank))
+                    .on_press(Message::Augments(AugmentMessage::SetRank(
+                        augment.api_name.clone(),
+                        new_rank,
+                    )))
+                    .style(chip_style(current == Some(rank)))
+                    .into()
+            })
+            .collect::<Vec<_>>());
+        row!(
+            Image::new(augment.icon.handle.clone()).width(Length::Fixed(48.0)),
+            column!(
+                text(format!("{} ({})", augment.name, augment.tier)),
+                text(augment.rendered_description()),
+                rank_buttons
+            )
+        )
+        .into()
+    }
+
+    /// Every rank with the names of the augments in it.
+    fn augment_tier_list(&self) -> Element<Message> {
+        column(
+            Rank::ALL
+                .into_iter()
+                .map(|rank| {
+                    let names: Vec<&str> = self
+                        .augment_tiers
+                        .iter()
+                        .filter(|(_, augment_rank)| **augment_rank == rank)
+                        .map(|(api_name, _)| {
+                            self.db
+                                .augment(api_name)
+                                .map_or(api_name.as_str(), |augment| &augment.name)
+                        })
+                        .collect();
+                    text(format!("{rank}: {}", names.join(", "))).into()
+                })
+                .collect(),
+        )
+        .into()
+    }
+
+    pub(crate) fn augments_view(&self) -> Element<Message> {
+        let browser = &self.augment_browser;
+        let tier_chips = row(AugmentTier::ALL
+            .into_iter()
+            .map(|tier| {
+                button(text(tier))
+                    .on_press(Message::Augments(AugmentMessage::ToggleTier(tier)))
+                    .style(chip_style(browser.tier == Some(tier)))
+                    .into()
+            })
+            .collect::<Vec<_>>());
+        let augments = column(
+            self.db
+                .augments
+                .iter()
+                .filter(|augment| browser.tier.iter().all(|tier| augment.tier == *tier))
+                .filter(|augment| {
+                    browser.query.is_empty()
+                        || matches_query(
+                            &browser.query,
+                            &augment.name,
+                            self.db.english_name(&augment.api_name),
+                            &augment.api_name,
+                        )
+                })
+                .map(|augment| self.augment_row(augment))
+                .collect(),
+        );
+
+        container(row!(
+            column!(
+                text_input(&self.i18n.tr("search-augments"), &browser.query)
+                    .on_input(|query| Message::Augments(AugmentMessage::QueryChanged(query))),
+                tier_chips,
+                scrollable(augments)
+            )
+            .width(Length::FillPortion(2)),
+            column!(
+                text(self.i18n.tr_args(
+                    "augment-tier-list",
+                    &[("notebook", self.notebook.as_str().into())]
+                )),
+                self.augment_tier_list(),
+                button(text(self.i18n.tr("save"))).on_press(Message::ClickedSave),
+                button(text(self.i18n.tr("go-to-character-builder")))
+                    .on_press(Message::ChangeScreen(Screen::CharacterBuilder))
+            )
+            .width(Length::FillPortion(1))
+        ))
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .into()
+    }
+}
@@ -0,0 +1,106 @@
+use std::collections::BTreeMap;
+
+use iced::widget::{column, row, text, text_input};
+use iced::Element;
+
+use tft::calc::*;
+use tft::i18n::Localize;
+use tft::state::ChampionState;
+use tft::tft_data::format_number;
+
+use crate::{Message, Model};
+
+/// Trait bonuses typed into the calculator. Only numbers make it into the bonus.
+#[derive(Debug, Default)]
+pub(crate) struct CalcInputs {
+    pub inputs: BTreeMap<Stat, String>,
+    pub bonus: Bonus,
+}
+
+#[derive(Debug, Clone)]
+pub enum CalcMessage {
+    BonusChanged(Stat, String),
+}
+
+impl Model {
+    pub(crate) fn update_calc(&mut self, message: CalcMessage) {
+        match message {
+            CalcMessage::BonusChanged(stat, input) => {
+                *self.calc.bonus.get_mut(stat) = input.trim().parse().unwrap_or_default();
+                self.calc.inputs.insert(stat, input);
+            }
+        }
+    }
+
+    /// Damage and durability of a champion with its BIS items, shown in the champion detail.
+    pub(crate) fn calc_panel(&self, champ_state: &ChampionState) -> Element<Message> {
+        let calculation = calculate(
+            &champ_state.champ,
+            self.star_level,
+            &champ_state.items,
+            &self.calc.bonus,
+        );
+        let bonus_inputs = column(
+            Stat::ALL
+                .into_iter()
+                .map(|stat| {
+                    let input = self.calc.inputs.get(&stat).map_or("", String::as_str);
+                    row!(
+                        text(self.i18n.tr_args(
+                            "calc-trait-bonus",
+                            &[("stat", self.i18n.tr(stat.message_id()).into())]
+                        )),
+                        text_input("0", input).on_input(move |input| Message::Calc(
+                            CalcMessage::BonusChanged(stat, input)
+                        ))
+                    )
+                    .into()
+                })
+                .collect(),
+        );
+        let ability_damage = column(
+            calculation
+                .ability_damage
+                .iter()
+                .map(|(name, damage)| {
+                    text(self.i18n.tr_args(
+                        "calc-ability-damage",
+                        &[
+                            ("ability", name.as_str().into()),
+                            ("damage", format_number(*damage).into()),
+                        ],
+                    ))
+                    .into()
+                })
+                .collect(),
+        );
+        let stats = calculation.stats;
+
+        column!(
+            text(self.i18n.tr("calc-title")),
+            bonus_inputs,
+            text(self.i18n.tr_args(
+                "calc-stats",
+                &[
+                    ("ad", format_number(stats.attack_damage).into()),
+                    ("ap", format_number(stats.ability_power).into()),
+                    ("as", format_number(stats.attack_speed).into()),
+                    ("crit", format_number(stats.crit_chance * 100.0).into()),
+                ]
+            )),
+            text(self.i18n.tr_args(
+                "calc-attack-dps",
+                &[("dps", format_number(calculation.attack_dps).into())]
+            )),
+            ability_damage,
+            text(self.i18n.tr_args(
+                "calc-effective-hp",
+                &[
+                    ("physical", format_number(calculation.physical_ehp).into()),
+                    ("magic", format_number(calculation.magic_ehp).into()),
+                ]
+            ))
+        )
+        .into()
+    }
+}
@@ -0,0 +1,161 @@
+use std::path::Path;
+
+use iced::theme::Palette;
+use iced::widget::{button, column, container, row, scrollable, text, text_input};
+use iced::{Application, Color, Element, Length};
+
+use tft::diff::{diff, load_snapshot, Category, Change, Direction, PatchDiff};
+use tft::i18n::Localize;
+use tft::serde_help::set_image_downloads;
+
+use crate::{chip_style, Message, Model, Screen};
+
+/// State of the patch changelog screen.
+#[derive(Debug, Default)]
+pub(crate) struct Changelog {
+    /// Snapshot the current data is compared against.
+    pub old_path: String,
+    /// Only show changes in this direction.
+    pub direction: Option<Direction>,
+    pub diff: Option<Result<PatchDiff, String>>,
+}
+
+#[derive(Debug, Clone)]
+pub enum ChangelogMessage {
+    OldPathChanged(String),
+    Compare,
+    ToggleDirection(Direction),
+}
+
+/// Colors of the current theme, so changes stay readable on the dark one.
+fn direction_color(palette: Palette, direction: Direction) -> Color {
+    match direction {
+        Direction::Buff => palette.success,
+        Direction::Nerf => palette.danger,
+        Direction::Adjusted => palette.text,
+    }
+}
+
+impl Model {
+    pub(crate) fn update_changelog(&mut self, message: ChangelogMessage) {
+        let set = self.config.set.as_deref();
+        let changelog = &mut self.changelog;
+        match message {
+            ChangelogMessage::OldPathChanged(path) => changelog.old_path = path,
+            ChangelogMessage::Compare => {
+                // icons of removed entries aren't shown, don't fetch them
+                set_image_downloads(false);
+                let old = load_snapshot(Path::new(&changelog.old_path), set);
+                set_image_downloads(true);
+                changelog.diff = Some(
+                    old.map(|old| diff(&old, &self.db))
+                        .map_err(|e| e.to_string()),
+                );
+            }
+            ChangelogMessage::ToggleDirection(direction) => {
+                changelog.direction = (changelog.direction != Some(direction)).then_some(direction);
+            }
+        }
+    }
+
+    fn change_text(&self, change: &Change) -> String {
+        self.i18n.tr_args(
+            "changelog-change",
+            &[
+                ("name", change.name.as_str().into()),
+                ("field", change.field.as_str().into()),
+                ("old", change.old.as_str().into()),
+                ("new", change.new.as_str().into()),
+                (
+                    "direction",
+                    self.i18n.tr(change.direction.message_id()).into(),
+                ),
+            ],
+        )
+    }
+
+    fn changelog_section(&self, patch: &PatchDiff, category: Category) -> Element<Message> {
+        let palette = self.theme().palette();
+        let mut section = column!(text(self.i18n.tr(category.message_id())).size(24));
+        for (_, name) in patch.added.iter().filter(|(c, _)| *c == category) {
+            section = section.push(
+                text(
+                    self.i18n
+                        .tr_args("changelog-added", &[("name", name.as_str().into())]),
+                )
+                .style(direction_color(palette, Direction::Buff)),
+            );
+        }
+        for (_, name) in patch.removed.iter().filter(|(c, _)| *c == category) {
+            section = section.push(
+                text(
+                    self.i18n
+                        .tr_args("changelog-removed", &[("name", name.as_str().into())]),
+                )
+                .style(direction_color(palette, Direction::Nerf)),
+            );
+        }
+        let changes = patch.changes.iter().filter(|change| {
+            change.category == category
+                && self
+                    .changelog
+                    .direction
+                    .iter()
+                    .all(|direction| change.direction == *direction)
+        });
+        for change in changes {
+            section = section.push(
+                text(self.change_text(change)).style(direction_color(palette, change.direction)),
+            );
+        }
+        section.into()
+    }
+
+    pub(crate) fn changelog_view(&self) -> Element<Message> {
+        let changelog = &self.changelog;
+        let direction_buttons = row([Direction::Buff, Direction::Nerf, Direction::Adjusted]
+            .into_iter()
+            .map(|direction| {
+                button(text(self.i18n.tr(direction.message_id())))
+                    .on_press(Message::Changelog(ChangelogMessage::ToggleDirection(
+                        direction,
+                    )))
+                    .style(chip_style(changelog.direction == Some(direction)))
+                    .into()
+            })
+            .collect::<Vec<_>>());
+        let changes: Element<Message> = match &changelog.diff {
+            None => text(self.i18n.tr("changelog-prompt")).into(),
+            Some(Err(e)) => text(e).into(),
+            Some(Ok(patch)) if patch.is_empty() => {
+                text(self.i18n.tr("changelog-no-changes")).into()
+            }
+            Some(Ok(patch)) => scrollable(column!(
+                self.changelog_section(patch, Category::Champion),
+                self.changelog_section(patch, Category::Trait),
+                self.changelog_section(patch, Category::Item)
+            ))
+            .into(),
+        };
+
+        container(row!(
+            container(changes).width(Length::Fill),
+            column!(
+                text_input(
+                    &self.i18n.tr("changelog-previous-snapshot"),
+                    &changelog.old_path
+                )
+                .on_input(|path| Message::Changelog(ChangelogMessage::OldPathChanged(path))),
+                button(text(self.i18n.tr("changelog-compare")))
+                    .on_press(Message::Changelog(ChangelogMessage::Compare)),
+                direction_buttons,
+                button(text(self.i18n.tr("go-to-character-builder")))
+                    .on_press(Message::ChangeScreen(Screen::CharacterBuilder))
+            )
+            .width(Length::Fixed(260.0))
+        ))
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .into()
+    }
+}
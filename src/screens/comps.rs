@@ -0,0 +1,377 @@
+use iced::widget::{
+    button, column, container, horizontal_space, pick_list, row, scrollable, text, text_input,
+    Image,
+};
+use iced::{clipboard, Command, Element, Length};
+
+use itertools::Itertools;
+
+use tft::comp::*;
+use tft::history::Edit;
+use tft::share_code;
+
+use crate::{chip_style, Message, Model, Screen};
+
+/// Editing state of the comps screen.
+#[derive(Debug, Default)]
+pub(crate) struct CompEditor {
+    pub selected_comp: Option<usize>,
+    pub name_input: String,
+    /// Api name of the champion placed when clicking an empty hex.
+    pub placing: Option<String>,
+    /// Hex of the unit being edited. Clicking an empty hex moves it there.
+    pub selected_unit: Option<BoardPosition>,
+    /// Result of the last copy or paste of a comp code.
+    pub code_status: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub enum CompMessage {
+    Select(usize),
+    NameInputChanged(String),
+    Create,
+    Delete,
+    /// Champion display name to place on the board.
+    PickChampion(String),
+    ClickedHex(BoardPosition),
+    RemoveUnit,
+    ChangeUnitStar(u8),
+    UseBisItems,
+    NotesChanged(String),
+    CopyPlannerCode,
+    CopyShareCode,
+    PasteCode,
+    /// Clipboard contents read for `PasteCode`.
+    Pasted(Option<String>),
+}
+
+impl Model {
+    fn selected_comp_mut(&mut self) -> Option<&mut Comp> {
+        self.comp_editor
+            .selected_comp
+            .and_then(|index| self.comps.get_mut(index))
+    }
+
+    /// Index of the selected comp, and the hex and a copy of its selected unit.
+    fn selected_unit(&self) -> Option<(usize, BoardPosition, CompUnit)> {
+        let index = self.comp_editor.selected_comp?;
+        let position = self.comp_editor.selected_unit?;
+        let unit = self.comps.get(index)?.unit_at(position)?;
+        Some((index, position, unit.clone()))
+    }
+
+    pub(crate) fn update_comp(&mut self, message: CompMessage) -> Command<Message> {
+        match message {
+            CompMessage::Select(index) => {
+                self.comp_editor.selected_comp = Some(index);
+                self.comp_editor.selected_unit = None;
+            }
+            CompMessage::NameInputChanged(input) => {
+                self.comp_editor.name_input = input;
+            }
+            CompMessage::Create => {
+                let name = self.comp_editor.name_input.trim().to_string();
+                if !name.is_empty() {
+                    self.comps.push(Comp::new(name));
+                    self.comp_editor.selected_comp = Some(self.comps.len() - 1);
+                    self.comp_editor.selected_unit = None;
+                    self.comp_editor.name_input.clear();
+                    self.mark_dirty();
+                }
+            }
+            CompMessage::Delete => {
+                if let Some(index) = self.comp_editor.selected_comp.take() {
+                    self.comps.remove(index);
+                    self.comp_editor.selected_unit = None;
+                    // board edits refer to comps by index
+                    self.history.clear();
+                    self.mark_dirty();
+                }
+            }
+            CompMessage::PickChampion(name) => {
+                self.comp_editor.placing = self
+                    .db
+                    .champions
+                    .iter()
+                    .find(|champ| champ.name == name)
+                    .map(|champ| champ.api_name.clone());
+                self.comp_editor.selected_unit = None;
+            }
+            CompMessage::ClickedHex(position) => {
+                let Some(index) = self.comp_editor.selected_comp else {
+                    return Command::none();
+                };
+                let selected_unit = self.comp_editor.selected_unit;
+                if self.comps[index].unit_at(position).is_some() {
+                    // clicking the selected unit again deselects it
+                    self.comp_editor.selected_unit =
+                        (selected_unit != Some(position)).then_some(position);
+                } else if let Some(from) = selected_unit {
+                    self.perform(Edit::BoardMove {
+                        comp: index,
+                        from,
+                        to: position,
+                    });
+                    self.comp_editor.selected_unit = Some(position);
+                } else if let Some(champion) = self.comp_editor.placing.clone() {
+                    self.perform(Edit::PlaceUnit {
+                        comp: index,
+                        unit: CompUnit {
+                            position: Some(position),
+                            ..CompUnit::new(champion)
+                        },
+                    });
+                }
+            }
+            CompMessage::RemoveUnit => {
+                let Some((index, _, unit)) = self.selected_unit() else {
+                    return Command::none();
+                };
+                self.comp_editor.selected_unit = None;
+                self.perform(Edit::RemoveUnit { comp: index, unit });
+            }
+            CompMessage::ChangeUnitStar(star) => {
+                let Some((index, position, unit)) = self.selected_unit() else {
+                    return Command::none();
+                };
+                if unit.star != star {
+                    self.perform(Edit::UnitStar {
+                        comp: index,
+                        position,
+                        from: unit.star,
+                        to: star,
+                    });
+                }
+            }
+            CompMessage::UseBisItems => {
+                let Some((index, position, unit)) = self.selected_unit() else {
+                    return Command::none();
+                };
+                let items: Vec<String> = self
+                    .champs
+                    .iter()
+                    .find(|champ_state| champ_state.champ.api_name == unit.champion)
+                    .map(|champ_state| {
+                        champ_state
+                            .items
+                            .iter()
+                            .map(|item| item.api_name.clone())
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                if unit.items != items {
+                    self.perform(Edit::UnitItems {
+                        comp: index,
+                        position,
+                        from: unit.items,
+                        to: items,
+                    });
+                }
+            }
+            CompMessage::NotesChanged(notes) => {
+                if let Some(comp) = self.selected_comp_mut() {
+                    comp.notes = notes;
+                    self.mark_dirty();
+                }
+            }
+            CompMessage::CopyPlannerCode => {
+                let Some(index) = self.comp_editor.selected_comp else {
+                    return Command::none();
+                };
+                match self.db.planner.encode_comp(&self.comps[index]) {
+                    Ok(code) => {
+                        self.comp_editor.code_status =
+                            Some(self.i18n.tr("comp-planner-code-copied"));
+                        return clipboard::write(code);
+                    }
+                    Err(e) => self.comp_editor.code_status = Some(e.to_string()),
+                }
+            }
+            CompMessage::CopyShareCode => {
+                let Some(index) = self.comp_editor.selected_comp else {
+                    return Command::none();
+                };
+                self.comp_editor.code_status = Some(self.i18n.tr("comp-share-code-copied"));
+                return clipboard::write(share_code::encode_share_code(&self.comps[index]));
+            }
+            CompMessage::PasteCode => {
+                return clipboard::read(|contents| Message::Comp(CompMessage::Pasted(contents)));
+            }
+            CompMessage::Pasted(contents) => {
+                let code = contents.unwrap_or_default();
+                match share_code::decode_any(&code, &self.db.planner) {
+                    Ok(comp) => {
+                        self.comp_editor.code_status = Some(
+                            self.i18n
+                                .tr_args("comp-imported", &[("name", comp.name.as_str().into())]),
+                        );
+                        self.comps.push(comp);
+                        self.comp_editor.selected_comp = Some(self.comps.len() - 1);
+                        self.comp_editor.selected_unit = None;
+                        self.mark_dirty();
+                    }
+                    Err(e) => self.comp_editor.code_status = Some(e.to_string()),
+                }
+            }
+        }
+        Command::none()
+    }
+
+    fn board(&self, comp: &Comp) -> Element<Message> {
+        column(
+            (0..BOARD_ROWS)
+                .map(|board_row| {
+                    let mut hexes: Vec<Element<Message>> = vec![];
+                    if board_row % 2 == 1 {
+                        hexes.push(horizontal_space(Length::Fixed(28.0)).into());
+                    }
+                    for col in 0..BOARD_COLS {
+                        let position = BoardPosition {
+                            row: board_row,
+                            col,
+                        };
+                        let content: Element<Message> = match comp.unit_at(position) {
+                            Some(unit) => {
+                                let icon = self
+                                    .db
+                                    .champion(&unit.champion)
+                                    .map(|champ| champ.square_icon.clone())
+                                    .unwrap_or_default();
+                                column!(
+                                    Image::new(icon.handle).width(Length::Fixed(40.0)),
+                                    text(format!("{}★", unit.star))
+                                )
+                                .into()
+                            }
+                            None => text("").into(),
+                        };
+                        hexes.push(
+                            button(content)
+                                .width(Length::Fixed(56.0))
+                                .height(Length::Fixed(64.0))
+                                .on_press(Message::Comp(CompMessage::ClickedHex(position)))
+                                .style(chip_style(self.comp_editor.selected_unit == Some(position)))
+                                .into(),
+                        );
+                    }
+                    row(hexes).into()
+                })
+                .collect::<Vec<_>>(),
+        )
+        .into()
+    }
+
+    fn unit_panel(&self, unit: &CompUnit) -> Element<Message> {
+        let champ = self.db.champion(&unit.champion);
+        column!(
+            text(champ.map_or(unit.champion.clone(), |champ| champ.name.clone())),
+            row((1..=3)
+                .map(|star| {
+                    button(text(format!("{star}★")))
+                        .on_press(Message::Comp(CompMessage::ChangeUnitStar(star)))
+                        .style(chip_style(star == unit.star))
+                        .into()
+                })
+                .collect::<Vec<_>>()),
+            row(unit
+                .items
+                .iter()
+                .filter_map(|api_name| self.db.item(api_name))
+                .map(|item| Image::new(item.icon.handle.clone()).into())
+                .collect::<Vec<_>>()),
+            row!(
+                button(text(self.i18n.tr("comp-use-bis-items")))
+                    .on_press(Message::Comp(CompMessage::UseBisItems)),
+                button(text(self.i18n.tr("comp-remove-unit")))
+                    .on_press(Message::Comp(CompMessage::RemoveUnit))
+                    .style(iced::theme::Button::Destructive)
+            )
+        )
+        .into()
+    }
+
+    pub(crate) fn comps_view(&self) -> Element<Message> {
+        let comp_list = self.comps.iter().enumerate().fold(
+            column!(
+                text_input(&self.i18n.tr("comp-name"), &self.comp_editor.name_input)
+                    .on_input(|input| Message::Comp(CompMessage::NameInputChanged(input))),
+                button(text(self.i18n.tr("comp-new"))).on_press(Message::Comp(CompMessage::Create)),
+                button(text(self.i18n.tr("comp-paste-code")))
+                    .on_press(Message::Comp(CompMessage::PasteCode))
+            ),
+            |col, (index, comp)| {
+                col.push(
+                    button(text(&comp.name))
+                        .on_press(Message::Comp(CompMessage::Select(index)))
+                        .style(chip_style(self.comp_editor.selected_comp == Some(index))),
+                )
+            },
+        );
+
+        let placing = self
+            .comp_editor
+            .placing
+            .as_ref()
+            .and_then(|api_name| self.db.champion(api_name).map(|champ| champ.name.clone()));
+        let champion_names = self
+            .db
+            .champions
+            .iter()
+            .map(|champ| champ.name.clone())
+            .sorted()
+            .collect::<Vec<_>>();
+
+        let editor: Element<Message> = match self
+            .comp_editor
+            .selected_comp
+            .and_then(|index| self.comps.get(index))
+        {
+            Some(comp) => column!(
+                text(&comp.name),
+                self.board(comp),
+                pick_list(champion_names, placing, |name| {
+                    Message::Comp(CompMessage::PickChampion(name))
+                })
+                .placeholder(self.i18n.tr("comp-champion-to-place")),
+                match self
+                    .comp_editor
+                    .selected_unit
+                    .and_then(|position| comp.unit_at(position))
+                {
+                    Some(unit) => self.unit_panel(unit),
+                    None => text(self.i18n.tr("comp-select-unit")).into(),
+                },
+                text_input(&self.i18n.tr("comp-notes"), &comp.notes)
+                    .on_input(|notes| Message::Comp(CompMessage::NotesChanged(notes))),
+                row!(
+                    button(text(self.i18n.tr("comp-copy-planner-code")))
+                        .on_press(Message::Comp(CompMessage::CopyPlannerCode)),
+                    button(text(self.i18n.tr("comp-copy-share-code")))
+                        .on_press(Message::Comp(CompMessage::CopyShareCode))
+                ),
+                button(text(self.i18n.tr("comp-delete")))
+                    .on_press(Message::Comp(CompMessage::Delete))
+                    .style(iced::theme::Button::Destructive)
+            )
+            .into(),
+            None => text(self.i18n.tr("comp-none-selected")).into(),
+        };
+        let editor = column!(
+            editor,
+            text(self.comp_editor.code_status.as_deref().unwrap_or_default())
+        );
+
+        container(row!(
+            scrollable(comp_list),
+            editor,
+            column!(
+                button(text(self.i18n.tr("save"))).on_press(Message::ClickedSave),
+                button(text(self.i18n.tr("go-to-character-builder")))
+                    .on_press(Message::ChangeScreen(Screen::CharacterBuilder))
+            )
+        ))
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .into()
+    }
+}
@@ -0,0 +1,205 @@
+use std::fs;
+
+use iced::widget::{button, column, container, pick_list, row, scrollable, text, text_input};
+use iced::{Element, Length};
+
+use tft::game_log::*;
+
+use crate::{chip_style, Message, Model, Screen};
+
+/// The game being entered on the game log screen.
+#[derive(Debug, Default)]
+pub(crate) struct GameEntry {
+    pub date: String,
+    pub patch: String,
+    pub placement: Option<u8>,
+    /// Name of the notebook comp used as the final board.
+    pub comp: Option<String>,
+    /// Comma separated augment names.
+    pub augments: String,
+    pub notes: String,
+    pub csv_path: String,
+    pub status: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub enum GameLogMessage {
+    DateChanged(String),
+    PatchChanged(String),
+    Placement(u8),
+    PickComp(String),
+    AugmentsChanged(String),
+    NotesChanged(String),
+    Log,
+    /// Index into the log, oldest first.
+    Delete(usize),
+    CsvPathChanged(String),
+    ExportCsv,
+}
+
+impl Model {
+    pub(crate) fn update_game_log(&mut self, message: GameLogMessage) {
+        let entry = &mut self.game_entry;
+        match message {
+            GameLogMessage::DateChanged(date) => entry.date = date,
+            GameLogMessage::PatchChanged(patch) => entry.patch = patch,
+            GameLogMessage::Placement(placement) => entry.placement = Some(placement),
+            GameLogMessage::PickComp(name) => entry.comp = Some(name),
+            GameLogMessage::AugmentsChanged(augments) => entry.augments = augments,
+            GameLogMessage::NotesChanged(notes) => entry.notes = notes,
+            GameLogMessage::Log => {
+                let status = self.log_game();
+                self.game_entry.status = Some(status);
+            }
+            GameLogMessage::Delete(index) => {
+                // only the games read so far are known, rewriting would lose the rest
+                if self.game_log_unreadable {
+                    self.game_entry.status = Some(self.i18n.tr("game-log-not-rewritten"));
+                } else if index < self.games.len() {
+                    self.games.remove(index);
+                    self.game_entry.status = self
+                        .game_log
+                        .write_all(&self.games)
+                        .err()
+                        .map(|e| e.to_string());
+                }
+            }
+            GameLogMessage::CsvPathChanged(path) => entry.csv_path = path,
+            GameLogMessage::ExportCsv => {
+                let csv = to_csv(&self.games, &self.db);
+                let path = &self.game_entry.csv_path;
+                self.game_entry.status = Some(match fs::write(path, csv) {
+                    Ok(()) => self
+                        .i18n
+                        .tr_args("exported-to", &[("path", path.as_str().into())]),
+                    Err(e) => e.to_string(),
+                });
+            }
+        }
+    }
+
+    /// Records the entered game. The date and patch are kept for the next game.
+    fn log_game(&mut self) -> String {
+        let entry = &self.game_entry;
+        if !is_valid_date(&entry.date) {
+            return self.i18n.tr("game-log-bad-date");
+        }
+        let Some(placement) = entry.placement else {
+            return self.i18n.tr("game-log-pick-placement");
+        };
+        let comp = entry
+            .comp
+            .as_ref()
+            .and_then(|name| self.comps.iter().find(|comp| &comp.name == name));
+        let record = GameRecord {
+            date: entry.date.clone(),
+            patch: entry.patch.trim().to_string(),
+            placement,
+            comp_name: comp.map(|comp| comp.name.clone()).unwrap_or_default(),
+            comp: comp.map(|comp| comp.units.clone()).unwrap_or_default(),
+            augments: entry
+                .augments
+                .split(',')
+                .map(str::trim)
+                .filter(|augment| !augment.is_empty())
+                .map(String::from)
+                .collect(),
+            notes: entry.notes.clone(),
+        };
+        if let Err(e) = self.game_log.append(&record) {
+            return e.to_string();
+        }
+        self.games.push(record);
+        self.game_entry = GameEntry {
+            date: self.game_entry.date.clone(),
+            patch: self.game_entry.patch.clone(),
+            csv_path: self.game_entry.csv_path.clone(),
+            ..GameEntry::default()
+        };
+        self.i18n.tr("game-log-logged")
+    }
+
+    pub(crate) fn game_log_view(&self) -> Element<Message> {
+        let entry = &self.game_entry;
+        let msg = Message::GameLog;
+
+        let placements = row((1..=8)
+            .map(|placement| {
+                button(text(format!("#{placement}")))
+                    .on_press(msg(GameLogMessage::Placement(placement)))
+                    .style(chip_style(entry.placement == Some(placement)))
+                    .into()
+            })
+            .collect::<Vec<_>>());
+        let comp_names: Vec<String> = self.comps.iter().map(|comp| comp.name.clone()).collect();
+
+        let form = column!(
+            row!(
+                text_input(&self.i18n.tr("game-log-date"), &entry.date)
+                    .on_input(|date| Message::GameLog(GameLogMessage::DateChanged(date))),
+                text_input(&self.i18n.tr("game-log-patch"), &entry.patch)
+                    .on_input(|patch| Message::GameLog(GameLogMessage::PatchChanged(patch)))
+            ),
+            placements,
+            pick_list(comp_names, entry.comp.clone(), |name| {
+                Message::GameLog(GameLogMessage::PickComp(name))
+            })
+            .placeholder(self.i18n.tr("game-log-final-comp")),
+            text_input(&self.i18n.tr("game-log-augments"), &entry.augments).on_input(|augments| {
+                Message::GameLog(GameLogMessage::AugmentsChanged(augments))
+            }),
+            text_input(&self.i18n.tr("notes"), &entry.notes)
+                .on_input(|notes| Message::GameLog(GameLogMessage::NotesChanged(notes))),
+            button(text(self.i18n.tr("game-log-log")))
+                .on_press(msg(GameLogMessage::Log))
+                .style(iced::theme::Button::Primary),
+            text(entry.status.as_deref().unwrap_or_default()),
+            row!(
+                text_input(&self.i18n.tr("game-log-csv-file"), &entry.csv_path)
+                    .on_input(|path| Message::GameLog(GameLogMessage::CsvPathChanged(path))),
+                button(text(self.i18n.tr("game-log-export-csv")))
+                    .on_press(msg(GameLogMessage::ExportCsv))
+            ),
+            button(text(self.i18n.tr("go-to-analytics")))
+                .on_press(Message::ChangeScreen(Screen::Analytics)),
+            button(text(self.i18n.tr("go-to-character-builder")))
+                .on_press(Message::ChangeScreen(Screen::CharacterBuilder))
+        );
+
+        // most recent first
+        let games = self
+            .games
+            .iter()
+            .enumerate()
+            .rev()
+            .fold(column!(), |col, (index, game)| {
+                let champions: Vec<String> = game
+                    .comp
+                    .iter()
+                    .map(|unit| {
+                        self.db
+                            .champion(&unit.champion)
+                            .map_or(unit.champion.clone(), |champ| champ.name.clone())
+                    })
+                    .collect();
+                col.push(row!(
+                    text(format!(
+                        "{} {} #{} {} | {}",
+                        game.date,
+                        game.patch,
+                        game.placement,
+                        champions.join(", "),
+                        game.augments.join(", ")
+                    )),
+                    button(text(self.i18n.tr("delete")))
+                        .on_press(msg(GameLogMessage::Delete(index)))
+                        .style(iced::theme::Button::Destructive)
+                ))
+            });
+
+        container(row!(form, scrollable(games)))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into()
+    }
+}
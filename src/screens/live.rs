@@ -0,0 +1,111 @@
+use iced::widget::{checkbox, column, text, text_input};
+use iced::{Command, Element};
+
+use tft::live_client::*;
+
+use crate::{Message, Model};
+
+#[derive(Debug, Clone)]
+pub enum LiveMessage {
+    Toggle(bool),
+    UrlChanged(String),
+    Poll,
+    Polled(LiveState),
+}
+
+impl Model {
+    pub(crate) fn update_live(&mut self, message: LiveMessage) -> Command<Message> {
+        match message {
+            LiveMessage::Toggle(enabled) => {
+                self.live_enabled = enabled;
+                if !enabled {
+                    self.live_state = LiveState::default();
+                }
+            }
+            LiveMessage::UrlChanged(url) => {
+                self.live_client = LiveClient::new(&url);
+            }
+            LiveMessage::Poll => {
+                let client = self.live_client.clone();
+                return Command::perform(async move { client.poll() }, |state| {
+                    Message::Live(LiveMessage::Polled(state))
+                });
+            }
+            LiveMessage::Polled(state) => {
+                // a poll can finish after the poller was turned off
+                if !self.live_enabled {
+                    return Command::none();
+                }
+                if let LiveState::Connected(game) = &state {
+                    self.apply_live_items(&game.items);
+                }
+                self.live_state = state;
+            }
+        }
+        Command::none()
+    }
+
+    /// Sets the ItemDeterminer counts to the components the game lists, if it lists any items.
+    /// These aren't user edits, so they skip the undo history.
+    fn apply_live_items(&mut self, items: &[String]) {
+        if items.is_empty() {
+            return;
+        }
+        for component_state in self.components.iter_mut() {
+            // the game lists items in English, whatever the data's locale
+            let component = &component_state.component;
+            let english_name = self
+                .db
+                .english_name(&component.api_name)
+                .unwrap_or(&component.name);
+            component_state.count = items.iter().filter(|name| *name == english_name).count();
+        }
+    }
+
+    pub(crate) fn live_panel(&self) -> Element<Message> {
+        let status: Element<Message> = match &self.live_state {
+            LiveState::Disconnected(reason) => text(
+                self.i18n
+                    .tr_args("live-disconnected", &[("reason", reason.as_str().into())]),
+            )
+            .into(),
+            LiveState::Connected(game) => {
+                let gold = game.gold.max(0.0) as u32;
+                let next_interest = match gold_to_next_interest(gold) {
+                    Some(missing) => self
+                        .i18n
+                        .tr_args("live-next-interest", &[("gold", missing.into())]),
+                    None => self.i18n.tr("live-max-interest"),
+                };
+                column!(
+                    text(
+                        self.i18n
+                            .tr_args("live-level", &[("level", game.level.into())])
+                    ),
+                    text(self.i18n.tr_args("live-gold", &[("gold", gold.into())])),
+                    text(
+                        self.i18n
+                            .tr_args("live-interest", &[("interest", interest(gold).into())])
+                    ),
+                    text(next_interest)
+                )
+                .into()
+            }
+        };
+        column!(
+            checkbox(
+                self.i18n.tr("live-read-game"),
+                self.live_enabled,
+                |enabled| { Message::Live(LiveMessage::Toggle(enabled)) }
+            ),
+            text_input(&self.i18n.tr("live-client-url"), &self.live_client.url)
+                .on_input(|url| Message::Live(LiveMessage::UrlChanged(url))),
+            if self.live_enabled {
+                status
+            } else {
+                text("").into()
+            }
+        )
+        .into()
+    }
+}
@@ -0,0 +1,164 @@
+use iced::widget::{column, pick_list, row, text};
+use iced::{Command, Element};
+
+use tft::database::{previous_data_path, LoadOptions, TftDatabase, LOCALES};
+use tft::diff::load_snapshot;
+use tft::i18n::{language_for_locale, Translator, LANGUAGES};
+use tft::serde_help::set_image_downloads;
+
+use crate::{Message, Model};
+
+#[derive(Debug, Clone)]
+pub enum LocaleMessage {
+    Select(String),
+    /// The data of a locale finished loading.
+    Loaded(String, Result<TftDatabase, String>),
+    /// Language tag of the UI strings, `None` to follow the data.
+    SelectUiLanguage(Option<String>),
+}
+
+impl Model {
+    pub(crate) fn update_locale(&mut self, message: LocaleMessage) -> Command<Message> {
+        match message {
+            LocaleMessage::Select(locale) => {
+                if locale == self.locale {
+                    return Command::none();
+                }
+                return self.load_data(locale);
+            }
+            LocaleMessage::Loaded(locale, Ok(db)) => {
+                self.switch_database(db);
+                self.locale = locale;
+                self.locale_status = None;
+                if self.config.locale != self.locale {
+                    self.config.locale = self.locale.clone();
+                    self.settings.draft.locale = self.locale.clone();
+                    self.save_config();
+                }
+                self.update_translator();
+                set_image_downloads(false);
+                self.previous_db = load_snapshot(
+                    &previous_data_path(&self.locale),
+                    self.config.set.as_deref(),
+                )
+                .ok();
+                set_image_downloads(true);
+                self.changelog.old_path = previous_data_path(&self.locale)
+                    .to_string_lossy()
+                    .into_owned();
+            }
+            LocaleMessage::Loaded(_, Err(e)) => self.locale_status = Some(e),
+            LocaleMessage::SelectUiLanguage(language) => {
+                self.config.ui_language = language.clone();
+                self.settings.draft.ui_language = language;
+                self.save_config();
+                self.update_translator();
+            }
+        }
+        Command::none()
+    }
+
+    /// Loads the data of a locale in the background, from the configured source and set.
+    pub(crate) fn load_data(&mut self, locale: String) -> Command<Message> {
+        self.locale_status = Some(
+            self.i18n
+                .tr_args("loading-locale", &[("locale", locale.clone().into())]),
+        );
+        let options = LoadOptions {
+            locale: locale.clone(),
+            ..self.config.load_options(false)
+        };
+        Command::perform(
+            async move { TftDatabase::load_with(&options).map_err(|e| e.to_string()) },
+            move |db| Message::Locale(LocaleMessage::Loaded(locale, db)),
+        )
+    }
+
+    /// Picks the UI strings for the chosen language, or the one matching the data.
+    pub(crate) fn update_translator(&mut self) {
+        let language = match &self.config.ui_language {
+            Some(language) => language.as_str(),
+            None => language_for_locale(&self.locale),
+        };
+        if self.i18n.language() != language {
+            self.i18n = Translator::new(language);
+        }
+    }
+
+    /// Swaps in data of another locale. Everything the user edited refers to api names and is
+    /// kept, including BIS entries neither data knows about; the few places holding display
+    /// names are translated.
+    fn switch_database(&mut self, db: TftDatabase) {
+        let bis = self.bis_save();
+        let (champs, _) = bis.reconcile(&db);
+        self.unmatched_bis = bis.unmatched(&db);
+        self.focused_champion = self
+            .focused_champion
+            .take()
+            .filter(|api_name| db.champion(api_name).is_some());
+        self.filters.champions.traits = self
+            .filters
+            .champions
+            .traits
+            .iter()
+            .filter_map(|name| {
+                let api_name = &self.db.trait_named(name)?.api_name;
+                db.traits.iter().find(|t| &t.api_name == api_name)
+            })
+            .map(|t| t.name.clone())
+            .collect();
+        for component_state in self.components.iter_mut() {
+            if let Some(component) = db.item(&component_state.component.api_name) {
+                component_state.component = component.clone();
+            }
+        }
+        self.champs = champs;
+        self.db = db;
+        self.mark_filters_dirty();
+    }
+
+    /// Picks a UI language by native name, after the choice to follow the data.
+    pub(crate) fn ui_language_picker(
+        &self,
+        selected: Option<&str>,
+        on_select: impl Fn(Option<String>) -> Message + 'static,
+    ) -> Element<Message> {
+        let same_as_data = self.i18n.tr("same-as-data");
+        let mut languages = vec![same_as_data.clone()];
+        languages.extend(LANGUAGES.iter().map(|(_, name)| name.to_string()));
+        let selected = match selected {
+            Some(language) => LANGUAGES
+                .iter()
+                .find(|(tag, _)| *tag == language)
+                .map(|(_, name)| name.to_string()),
+            None => Some(same_as_data),
+        };
+        pick_list(languages, selected, move |name| {
+            let language = LANGUAGES
+                .iter()
+                .find(|(_, language_name)| *language_name == name)
+                .map(|(tag, _)| tag.to_string());
+            on_select(language)
+        })
+        .into()
+    }
+
+    pub(crate) fn locale_panel(&self) -> Element<Message> {
+        column!(
+            row!(
+                text(self.i18n.tr("data-language")),
+                pick_list(LOCALES, Some(self.locale.as_str()), |locale| {
+                    Message::Locale(LocaleMessage::Select(locale.to_string()))
+                }),
+                text(self.locale_status.clone().unwrap_or_default())
+            ),
+            row!(
+                text(self.i18n.tr("ui-language")),
+                self.ui_language_picker(self.config.ui_language.as_deref(), |language| {
+                    Message::Locale(LocaleMessage::SelectUiLanguage(language))
+                })
+            )
+        )
+        .into()
+    }
+}
@@ -0,0 +1,170 @@
+use std::path::Path;
+
+use iced::widget::{button, column, row, text, text_input, Image};
+use iced::{Element, Length};
+
+use tft::history::Edit;
+use tft::match_stats::read_match_dir;
+use tft::state::ChampionState;
+use tft::tft_data::format_number;
+
+use crate::{Message, Model, DATA_DIR};
+
+/// Item sets played fewer times than this are too noisy to suggest.
+const MIN_COMBO_GAMES: u32 = 3;
+
+#[derive(Debug, Clone)]
+pub enum MatchMessage {
+    DirChanged(String),
+    Import,
+    /// Replaces a champion's BIS list with an item set from the stats.
+    UseAsBis {
+        champion: String,
+        items: Vec<String>,
+    },
+}
+
+impl Model {
+    pub(crate) fn update_matches(&mut self, message: MatchMessage) {
+        match message {
+            MatchMessage::DirChanged(dir) => {
+                self.match_dir = dir;
+            }
+            MatchMessage::Import => {
+                self.match_status = Some(match read_match_dir(Path::new(&self.match_dir)) {
+                    Ok(matches) => {
+                        let added = self.match_stats.add_matches(&matches);
+                        match self
+                            .match_stats
+                            .save(&DATA_DIR.get().unwrap().join("match_stats.json"))
+                        {
+                            Ok(()) => self.i18n.tr_args(
+                                "match-imported",
+                                &[("added", added.into()), ("total", matches.len().into())],
+                            ),
+                            Err(e) => self.i18n.tr_args(
+                                "match-stats-save-failed",
+                                &[("error", e.to_string().into())],
+                            ),
+                        }
+                    }
+                    Err(e) => e.to_string(),
+                });
+            }
+            MatchMessage::UseAsBis { champion, items } => {
+                let Some(champ_state) = self
+                    .champs
+                    .iter()
+                    .find(|champ_state| champ_state.champ.api_name == champion)
+                else {
+                    return;
+                };
+                let from = champ_state
+                    .items
+                    .iter()
+                    .map(|item| item.api_name.clone())
+                    .collect();
+                // the new list replaces the items the data doesn't know about too
+                self.unmatched_bis.remove(&champion);
+                self.perform(Edit::ReplaceItems {
+                    champion,
+                    from,
+                    to: items,
+                });
+            }
+        }
+    }
+
+    pub(crate) fn match_import_panel(&self) -> Element<Message> {
+        column!(
+            row!(
+                text_input(&self.i18n.tr("match-folder"), &self.match_dir)
+                    .on_input(|dir| Message::Matches(MatchMessage::DirChanged(dir))),
+                button(text(self.i18n.tr("match-import")))
+                    .on_press(Message::Matches(MatchMessage::Import))
+            ),
+            text(self.match_status.as_deref().unwrap_or_default())
+        )
+        .into()
+    }
+
+    /// Match stats of a champion, shown under its manual BIS list.
+    pub(crate) fn match_stats_panel(&self, champ_state: &ChampionState) -> Element<Message> {
+        let champion = &champ_state.champ.api_name;
+        let Some(stats) = self.match_stats.champions.get(champion) else {
+            return text(self.i18n.tr("match-no-stats")).into();
+        };
+        let item_name = |api_name: &String| {
+            self.db
+                .item(api_name)
+                .map_or(api_name.clone(), |item| item.name.clone())
+        };
+
+        let mut frequent_items: Vec<(&String, &u32)> = stats.item_games.iter().collect();
+        frequent_items.sort_by(|a, b| b.1.cmp(a.1));
+        let frequent_items =
+            frequent_items
+                .into_iter()
+                .take(5)
+                .fold(column!(), |col, (api_name, _)| {
+                    col.push(text(self.i18n.tr_args(
+                        "match-item-frequency",
+                        &[
+                            ("item", item_name(api_name).into()),
+                            (
+                                "percent",
+                                format_number(stats.item_frequency(api_name) * 100.0).into(),
+                            ),
+                        ],
+                    )))
+                });
+
+        let combos =
+            stats
+                .best_combos(MIN_COMBO_GAMES)
+                .into_iter()
+                .take(3)
+                .fold(column!(), |col, combo| {
+                    let icons = row(combo
+                        .items
+                        .iter()
+                        .filter_map(|api_name| self.db.item(api_name))
+                        .map(|item| {
+                            Image::new(item.icon.handle.clone())
+                                .width(Length::Fixed(32.0))
+                                .into()
+                        })
+                        .collect::<Vec<_>>());
+                    col.push(row!(
+                        icons,
+                        text(self.i18n.tr_args(
+                            "match-combo",
+                            &[
+                                ("average", format_number(combo.average_placement()).into()),
+                                (
+                                    "top-four",
+                                    format_number(combo.top_four_rate() * 100.0).into()
+                                ),
+                                ("games", combo.games.into()),
+                            ]
+                        )),
+                        button(text(self.i18n.tr("match-use-as-bis"))).on_press(Message::Matches(
+                            MatchMessage::UseAsBis {
+                                champion: champion.clone(),
+                                items: combo.items.clone(),
+                            }
+                        ))
+                    ))
+                });
+
+        column!(
+            text(
+                self.i18n
+                    .tr_args("match-stats-title", &[("games", stats.games.into())])
+            ),
+            frequent_items,
+            combos
+        )
+        .into()
+    }
+}
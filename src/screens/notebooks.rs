@@ -0,0 +1,276 @@
+use std::fs;
+
+use iced::widget::{button, column, pick_list, row, text, text_input};
+use iced::Element;
+
+use itertools::Itertools;
+
+use tft::audit::{audit, DEFAULT_THRESHOLD};
+use tft::save::{SaveError, SaveFile};
+use tft::toml_format;
+
+use crate::screens::comps::CompEditor;
+use crate::screens::tier_lists::TierBoard;
+use crate::{Message, Model, DATA_DIR};
+
+#[derive(Debug, Clone)]
+pub enum NotebookMessage {
+    Select(String),
+    NameInputChanged(String),
+    Create,
+    Rename,
+    Duplicate,
+    Delete,
+    NotesChanged(String),
+    TomlPathChanged(String),
+    ExportToml,
+    ImportToml,
+}
+
+impl Model {
+    /// Loads a notebook, replacing the BIS lists, comps and notes being edited. An unreadable
+    /// notebook goes to the save warnings, errors of the notebook directory are returned.
+    pub(crate) fn open_notebook(&mut self, name: String) -> Result<(), SaveError> {
+        let save = match self.notebooks.load(&name) {
+            Ok(save) => {
+                self.notebook_unreadable = false;
+                save
+            }
+            Err(e) => {
+                self.save_warnings = Some(e.to_string());
+                // the empty notebook shown instead must not replace the file
+                self.notebook_unreadable = true;
+                SaveFile::default()
+            }
+        };
+        self.audit = audit(
+            &save.champions,
+            self.previous_db.as_ref(),
+            &self.db,
+            DEFAULT_THRESHOLD,
+        );
+        let (champs, report) = save.reconcile(&self.db);
+        if !report.is_empty() {
+            log::warn!("{report}");
+            self.save_warnings = Some(report.to_string());
+        }
+        self.champs = champs;
+        self.unmatched_bis = save.unmatched(&self.db);
+        self.comps = save.comps;
+        self.notes = save.notes;
+        self.augment_tiers = save.augment_tiers;
+        self.tier_lists = save.tier_lists;
+        self.comp_editor = CompEditor::default();
+        self.history.clear();
+        self.last_edit = None;
+        self.tier_board = TierBoard {
+            export_path: DATA_DIR
+                .get()
+                .unwrap()
+                .join(format!("{name}_tiers"))
+                .to_string_lossy()
+                .into_owned(),
+            ..TierBoard::default()
+        };
+        self.toml_path = DATA_DIR
+            .get()
+            .unwrap()
+            .join(format!("{name}.toml"))
+            .to_string_lossy()
+            .into_owned();
+        let last_used = self.notebooks.set_last_used(&name);
+        self.notebook = name;
+        let listed = self.notebooks.list();
+        // the open notebook stays listed when the others can't be
+        self.notebook_names = match &listed {
+            Ok(names) => names.clone(),
+            Err(_) => vec![self.notebook.clone()],
+        };
+        last_used?;
+        listed.map(|_| ())
+    }
+
+    /// The BIS lists being edited, with the entries the loaded data doesn't know about.
+    pub(crate) fn bis_save(&self) -> SaveFile {
+        SaveFile::from_champions(&self.champs).with_unmatched(&self.unmatched_bis)
+    }
+
+    pub(crate) fn save_notebook(&mut self) {
+        if self.notebook_unreadable {
+            self.save_warnings = Some(self.i18n.tr_args(
+                "notebook-unreadable",
+                &[("name", self.notebook.as_str().into())],
+            ));
+            self.last_edit = None;
+            return;
+        }
+        let save = SaveFile {
+            comps: self.comps.clone(),
+            notes: self.notes.clone(),
+            augment_tiers: self.augment_tiers.clone(),
+            tier_lists: self.tier_lists.clone(),
+            ..self.bis_save()
+        };
+        self.save_warnings = self
+            .notebooks
+            .save(&self.notebook, &save)
+            .err()
+            .map(|e| e.to_string());
+        self.last_edit = None;
+    }
+
+    pub(crate) fn update_notebook(&mut self, message: NotebookMessage) {
+        let name = self.notebook_name_input.trim().to_string();
+        let confirm_delete = std::mem::take(&mut self.confirm_notebook_delete);
+        let result: Result<(), SaveError> = match message {
+            NotebookMessage::Select(name) => {
+                self.save_notebook();
+                self.open_notebook(name)
+            }
+            NotebookMessage::NameInputChanged(input) => {
+                self.notebook_name_input = input;
+                Ok(())
+            }
+            NotebookMessage::Create => self.notebooks.create(&name).and_then(|()| {
+                self.save_notebook();
+                self.open_notebook(name)
+            }),
+            NotebookMessage::Rename => {
+                self.save_notebook();
+                self.notebooks
+                    .rename(&self.notebook, &name)
+                    .and_then(|()| self.open_notebook(name))
+            }
+            NotebookMessage::Duplicate => {
+                self.save_notebook();
+                self.notebooks
+                    .duplicate(&self.notebook, &name)
+                    .and_then(|()| self.open_notebook(name))
+            }
+            NotebookMessage::Delete => {
+                let next = self
+                    .notebook_names
+                    .iter()
+                    .find(|name| **name != self.notebook)
+                    .cloned();
+                match next {
+                    // the last notebook can't be deleted
+                    None => Ok(()),
+                    Some(next) if confirm_delete => self
+                        .notebooks
+                        .delete(&self.notebook)
+                        .and_then(|()| self.open_notebook(next)),
+                    Some(_) => {
+                        self.confirm_notebook_delete = true;
+                        Ok(())
+                    }
+                }
+            }
+            NotebookMessage::NotesChanged(notes) => {
+                self.notes = notes;
+                self.mark_dirty();
+                Ok(())
+            }
+            NotebookMessage::TomlPathChanged(path) => {
+                self.toml_path = path;
+                Ok(())
+            }
+            NotebookMessage::ExportToml => {
+                let bis = SaveFile::from_champions(&self.champs).champions;
+                let s = toml_format::export(&bis, &self.comps, &self.db);
+                self.toml_status = Some(match fs::write(&self.toml_path, s) {
+                    Ok(()) => self
+                        .i18n
+                        .tr_args("exported-to", &[("path", self.toml_path.as_str().into())]),
+                    Err(e) => e.to_string(),
+                });
+                Ok(())
+            }
+            NotebookMessage::ImportToml => {
+                self.toml_status = Some(self.import_toml());
+                Ok(())
+            }
+        };
+        match result {
+            Ok(()) => self.notebook_error = None,
+            Err(e) => self.notebook_error = Some(e.to_string()),
+        }
+    }
+
+    /// Merges the TOML file into the open notebook, returning a status for the UI.
+    fn import_toml(&mut self) -> String {
+        let s = match fs::read_to_string(&self.toml_path) {
+            Ok(s) => s,
+            Err(e) => return e.to_string(),
+        };
+        let imported = match toml_format::import(&s, &self.db) {
+            Ok(imported) => imported,
+            Err(errors) => return errors.iter().join("\n"),
+        };
+        let status = self.i18n.tr_args(
+            "notebook-imported",
+            &[
+                ("bis", imported.bis.len().into()),
+                ("comps", imported.comps.len().into()),
+            ],
+        );
+        for (champion, items) in imported.bis {
+            if let Some(champ_state) = self
+                .champs
+                .iter_mut()
+                .find(|champ_state| champ_state.champ.api_name == champion)
+            {
+                champ_state.items = items
+                    .iter()
+                    .filter_map(|item| self.db.item(item).cloned())
+                    .collect();
+                self.unmatched_bis.remove(&champion);
+            }
+        }
+        self.comps.extend(imported.comps);
+        // the history doesn't know about the replaced lists
+        self.history.clear();
+        self.mark_dirty();
+        status
+    }
+
+    pub(crate) fn notebook_panel(&self) -> Element<Message> {
+        column!(
+            pick_list(
+                self.notebook_names.clone(),
+                Some(self.notebook.clone()),
+                |name| Message::Notebook(NotebookMessage::Select(name))
+            ),
+            text_input(&self.i18n.tr("notebook-name"), &self.notebook_name_input)
+                .on_input(|input| Message::Notebook(NotebookMessage::NameInputChanged(input))),
+            row!(
+                button(text(self.i18n.tr("notebook-new")))
+                    .on_press(Message::Notebook(NotebookMessage::Create)),
+                button(text(self.i18n.tr("notebook-rename")))
+                    .on_press(Message::Notebook(NotebookMessage::Rename)),
+                button(text(self.i18n.tr("notebook-duplicate")))
+                    .on_press(Message::Notebook(NotebookMessage::Duplicate)),
+                button(text(self.i18n.tr(if self.confirm_notebook_delete {
+                    "confirm-delete"
+                } else {
+                    "delete"
+                })))
+                .on_press(Message::Notebook(NotebookMessage::Delete))
+                .style(iced::theme::Button::Destructive)
+            ),
+            text(self.notebook_error.clone().unwrap_or_default()),
+            text_input(&self.i18n.tr("notes"), &self.notes)
+                .on_input(|notes| Message::Notebook(NotebookMessage::NotesChanged(notes))),
+            text_input(&self.i18n.tr("toml-file"), &self.toml_path)
+                .on_input(|path| Message::Notebook(NotebookMessage::TomlPathChanged(path))),
+            row!(
+                button(text(self.i18n.tr("export-toml")))
+                    .on_press(Message::Notebook(NotebookMessage::ExportToml)),
+                button(text(self.i18n.tr("import-toml")))
+                    .on_press(Message::Notebook(NotebookMessage::ImportToml))
+            ),
+            text(self.toml_status.clone().unwrap_or_default())
+        )
+        .into()
+    }
+}
@@ -0,0 +1,232 @@
+use iced::widget::{button, column, container, pick_list, row, text, text_input};
+use iced::{Application, Command, Element, Length};
+
+use tft::config::{config_path, Config, ThemeName, MAX_SCALE, MIN_SCALE};
+use tft::database::{CDRAGON_DATA_URL, LOCALES};
+use tft::i18n::Translator;
+use tft::serde_help::prune_image_cache;
+use tft::sort::SortChampMethod;
+
+use crate::{Message, Model, Screen};
+
+/// Settings being edited. The theme and scale are previewed while the settings screen is open,
+/// the rest applies on save.
+#[derive(Debug, Default)]
+pub(crate) struct SettingsForm {
+    pub draft: Config,
+    pub scale_input: String,
+    pub cache_size_input: String,
+    pub set_input: String,
+    pub status: Option<String>,
+}
+
+impl SettingsForm {
+    pub fn new(config: &Config) -> Self {
+        Self {
+            draft: config.clone(),
+            scale_input: config.scale.to_string(),
+            cache_size_input: config.cache_size_mb.to_string(),
+            set_input: config.set.clone().unwrap_or_default(),
+            status: None,
+        }
+    }
+
+    /// What keeps the settings from being saved, empty if nothing.
+    fn problems(&self, i18n: &Translator) -> Vec<String> {
+        let mut problems = vec![];
+        if self.scale_input.trim().parse::<f64>().is_err() {
+            problems.push(i18n.tr("problem-scale-not-number"));
+        }
+        if self.cache_size_input.trim().parse::<u64>().is_err() {
+            problems.push(i18n.tr("problem-cache-size-not-number"));
+        }
+        problems.extend(
+            self.draft
+                .problems()
+                .iter()
+                .map(|problem| i18n.problem(problem)),
+        );
+        problems
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum SettingsMessage {
+    ThemeSelected(ThemeName),
+    ScaleChanged(String),
+    LocaleSelected(String),
+    /// Language tag of the UI strings, `None` to follow the data.
+    UiLanguageSelected(Option<String>),
+    DataUrlChanged(String),
+    CacheSizeChanged(String),
+    DefaultSortSelected(SortChampMethod),
+    SetChanged(String),
+    Save,
+    Revert,
+}
+
+impl Model {
+    pub(crate) fn update_settings(&mut self, message: SettingsMessage) -> Command<Message> {
+        let form = &mut self.settings;
+        match message {
+            SettingsMessage::ThemeSelected(theme) => form.draft.theme = theme,
+            SettingsMessage::ScaleChanged(input) => {
+                if let Ok(scale) = input.trim().parse() {
+                    form.draft.scale = scale;
+                }
+                form.scale_input = input;
+            }
+            SettingsMessage::LocaleSelected(locale) => form.draft.locale = locale,
+            SettingsMessage::UiLanguageSelected(language) => form.draft.ui_language = language,
+            SettingsMessage::DataUrlChanged(url) => form.draft.data_url = url,
+            SettingsMessage::CacheSizeChanged(input) => {
+                if let Ok(size) = input.trim().parse() {
+                    form.draft.cache_size_mb = size;
+                }
+                form.cache_size_input = input;
+            }
+            SettingsMessage::DefaultSortSelected(method) => form.draft.default_sort = method,
+            SettingsMessage::SetChanged(input) => {
+                let set = input.trim();
+                form.draft.set = (!set.is_empty()).then(|| set.to_string());
+                form.set_input = input;
+            }
+            SettingsMessage::Save => return self.save_settings(),
+            SettingsMessage::Revert => self.settings = SettingsForm::new(&self.config),
+        }
+        Command::none()
+    }
+
+    fn save_settings(&mut self) -> Command<Message> {
+        let problems = self.settings.problems(&self.i18n);
+        if !problems.is_empty() {
+            self.settings.status = Some(problems.join("\n"));
+            return Command::none();
+        }
+        let config = self.settings.draft.clone();
+        let reload = config.locale != self.config.locale
+            || config.set != self.config.set
+            || config.data_url != self.config.data_url;
+        if config.default_sort != self.config.default_sort {
+            self.curr_sort_method = config.default_sort;
+        }
+        self.config = config;
+        self.settings.status = Some(self.i18n.tr("settings-saved"));
+        self.save_config();
+        self.update_translator();
+        if let Some(max_bytes) = self.config.cache_size_bytes() {
+            if let Err(e) = prune_image_cache(max_bytes, &self.db.icon_paths()) {
+                self.settings.status = Some(self.i18n.tr_args(
+                    "settings-cache-cleanup-failed",
+                    &[("error", e.to_string().into())],
+                ));
+            }
+        }
+        if reload {
+            return self.load_data(self.config.locale.clone());
+        }
+        Command::none()
+    }
+
+    /// Writes the config, reporting a failure on the settings screen.
+    pub(crate) fn save_config(&mut self) {
+        if let Err(e) = self.config.save(&config_path()) {
+            self.settings.status = Some(e.to_string());
+        }
+    }
+
+    /// The config whose theme and scale are shown, the draft while it's being edited.
+    pub(crate) fn shown_config(&self) -> &Config {
+        if self.screen == Screen::Settings {
+            &self.settings.draft
+        } else {
+            &self.config
+        }
+    }
+
+    pub(crate) fn settings_view(&self) -> Element<Message> {
+        let form = &self.settings;
+        let draft = &form.draft;
+        let problems = form.problems(&self.i18n);
+        let mut save_button = button(text(self.i18n.tr("save")));
+        if problems.is_empty() {
+            save_button = save_button.on_press(Message::Settings(SettingsMessage::Save));
+        }
+
+        let settings = column!(
+            text(self.i18n.tr("settings")).size(24),
+            row!(
+                text(self.i18n.tr("settings-theme")),
+                pick_list(
+                    ThemeName::ALL
+                        .map(|theme| self.i18n.localize(theme))
+                        .to_vec(),
+                    Some(self.i18n.localize(draft.theme)),
+                    |theme| Message::Settings(SettingsMessage::ThemeSelected(theme.value))
+                )
+            ),
+            row!(
+                text(self.i18n.tr_args(
+                    "settings-ui-scale",
+                    &[("min", MIN_SCALE.into()), ("max", MAX_SCALE.into())]
+                )),
+                text_input("1", &form.scale_input)
+                    .on_input(|input| Message::Settings(SettingsMessage::ScaleChanged(input)))
+            ),
+            row!(
+                text(self.i18n.tr("data-language")),
+                pick_list(LOCALES, Some(draft.locale.as_str()), |locale| {
+                    Message::Settings(SettingsMessage::LocaleSelected(locale.to_string()))
+                })
+            ),
+            row!(
+                text(self.i18n.tr("ui-language")),
+                self.ui_language_picker(draft.ui_language.as_deref(), |language| {
+                    Message::Settings(SettingsMessage::UiLanguageSelected(language))
+                })
+            ),
+            row!(
+                text(self.i18n.tr("settings-set")),
+                text_input(&self.i18n.tr("settings-default-set"), &form.set_input)
+                    .on_input(|input| Message::Settings(SettingsMessage::SetChanged(input)))
+            ),
+            row!(
+                text(self.i18n.tr("settings-data-source")),
+                text_input(CDRAGON_DATA_URL, &draft.data_url)
+                    .on_input(|url| Message::Settings(SettingsMessage::DataUrlChanged(url)))
+            ),
+            row!(
+                text(self.i18n.tr("settings-cache-size")),
+                text_input("0", &form.cache_size_input)
+                    .on_input(|input| Message::Settings(SettingsMessage::CacheSizeChanged(input)))
+            ),
+            row!(
+                text(self.i18n.tr("settings-default-sort")),
+                pick_list(
+                    SortChampMethod::ALL
+                        .map(|method| self.i18n.localize(method))
+                        .to_vec(),
+                    Some(self.i18n.localize(draft.default_sort)),
+                    |method| Message::Settings(SettingsMessage::DefaultSortSelected(method.value))
+                )
+            ),
+            text(problems.join("\n")).style(self.theme().palette().danger),
+            text(form.status.clone().unwrap_or_default()),
+            text(self.locale_status.clone().unwrap_or_default()),
+            row!(
+                save_button,
+                button(text(self.i18n.tr("settings-revert")))
+                    .on_press(Message::Settings(SettingsMessage::Revert))
+            ),
+            button(text(self.i18n.tr("go-to-character-builder")))
+                .on_press(Message::ChangeScreen(Screen::CharacterBuilder))
+        )
+        .width(Length::Fixed(520.0));
+
+        container(settings)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .center_x()
+            .into()
+    }
+}
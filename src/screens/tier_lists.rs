@@ -0,0 +1,319 @@
+use std::path::Path;
+
+use iced::widget::{
+    button, column, container, mouse_area, row, scrollable, text, text_input, Image,
+};
+use iced::{Element, Length};
+
+use itertools::Itertools;
+
+use tft::tier_list::{TierList, TierRow};
+
+use crate::{chip_style, Message, Model, Screen};
+
+/// Champion icons per line of a tier row.
+const ICONS_PER_LINE: usize = 12;
+const ICON_SIZE: f32 = 48.0;
+
+/// Editing state of the tier list screen.
+#[derive(Debug, Default)]
+pub(crate) struct TierBoard {
+    /// Index into the notebook's tier lists.
+    pub selected: usize,
+    /// Api name of the champion being dragged.
+    pub dragging: Option<String>,
+    pub list_name_input: String,
+    pub row_label_input: String,
+    /// File the list is exported to, without extension.
+    pub export_path: String,
+    pub status: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub enum TierMessage {
+    Select(usize),
+    ListNameChanged(String),
+    NewList,
+    RenameList,
+    DeleteList,
+    RowLabelChanged(String),
+    AddRow,
+    RenameRow(usize),
+    RemoveRow(usize),
+    /// A champion icon was pressed.
+    Drag(String),
+    /// The mouse was released over a row, before the champion at `index`. Over no row the
+    /// champion becomes unranked.
+    Drop {
+        row: Option<usize>,
+        index: Option<usize>,
+    },
+    /// The mouse was released outside the board.
+    CancelDrag,
+    ExportPathChanged(String),
+    ExportText,
+    ExportPng,
+}
+
+impl Model {
+    pub(crate) fn update_tier_lists(&mut self, message: TierMessage) {
+        let board = &mut self.tier_board;
+        let list_name = board.list_name_input.trim().to_string();
+        let row_label = board.row_label_input.trim().to_string();
+        match message {
+            TierMessage::Select(index) => board.selected = index,
+            TierMessage::ListNameChanged(name) => board.list_name_input = name,
+            TierMessage::NewList => {
+                let name = if list_name.is_empty() {
+                    self.i18n.tr_args(
+                        "tier-list-default-name",
+                        &[("number", (self.tier_lists.len() + 1).into())],
+                    )
+                } else {
+                    list_name
+                };
+                self.tier_lists.push(TierList::new(&name));
+                self.tier_board.selected = self.tier_lists.len() - 1;
+                self.mark_dirty();
+            }
+            TierMessage::RenameList => {
+                if let Some(list) = self.tier_lists.get_mut(board.selected) {
+                    if !list_name.is_empty() {
+                        list.name = list_name;
+                        self.mark_dirty();
+                    }
+                }
+            }
+            TierMessage::DeleteList => {
+                if board.selected < self.tier_lists.len() {
+                    self.tier_lists.remove(board.selected);
+                    board.selected = board.selected.saturating_sub(1);
+                    self.mark_dirty();
+                }
+            }
+            TierMessage::RowLabelChanged(label) => board.row_label_input = label,
+            TierMessage::AddRow => {
+                if let Some(list) = self.tier_lists.get_mut(board.selected) {
+                    if !row_label.is_empty() {
+                        list.rows.push(TierRow {
+                            label: row_label,
+                            champions: vec![],
+                        });
+                        self.mark_dirty();
+                    }
+                }
+            }
+            TierMessage::RenameRow(row) => {
+                let tier_row = self
+                    .tier_lists
+                    .get_mut(board.selected)
+                    .and_then(|list| list.rows.get_mut(row));
+                if let Some(tier_row) = tier_row {
+                    if !row_label.is_empty() {
+                        tier_row.label = row_label;
+                        self.mark_dirty();
+                    }
+                }
+            }
+            TierMessage::RemoveRow(row) => {
+                if let Some(list) = self.tier_lists.get_mut(board.selected) {
+                    list.remove_row(row);
+                    self.mark_dirty();
+                }
+            }
+            TierMessage::Drag(champion) => board.dragging = Some(champion),
+            TierMessage::Drop { row, index } => {
+                let dragged = board.dragging.take();
+                let list = self.tier_lists.get_mut(board.selected);
+                if let (Some(champion), Some(list)) = (dragged, list) {
+                    match row {
+                        Some(row) => list.place(&champion, row, index),
+                        None => {
+                            list.remove(&champion);
+                        }
+                    }
+                    self.mark_dirty();
+                }
+            }
+            TierMessage::CancelDrag => board.dragging = None,
+            TierMessage::ExportPathChanged(path) => board.export_path = path,
+            TierMessage::ExportText => {
+                let path = Path::new(&board.export_path).with_extension("txt");
+                board.status =
+                    self.tier_lists.get(board.selected).map(|list| {
+                        match std::fs::write(&path, list.to_text(&self.db)) {
+                            Ok(()) => self.i18n.tr_args(
+                                "exported-to",
+                                &[("path", path.display().to_string().into())],
+                            ),
+                            Err(e) => e.to_string(),
+                        }
+                    });
+            }
+            TierMessage::ExportPng => {
+                let path = Path::new(&board.export_path).with_extension("png");
+                board.status = self.tier_lists.get(board.selected).map(|list| {
+                    match list.to_image(&self.db).save(&path) {
+                        Ok(()) => self.i18n.tr_args(
+                            "exported-to",
+                            &[("path", path.display().to_string().into())],
+                        ),
+                        Err(e) => e.to_string(),
+                    }
+                });
+            }
+        }
+    }
+
+    /// Champion icons in lines of `ICONS_PER_LINE`. `drop_row` is where dropping on an icon
+    /// puts the dragged champion, before that icon.
+    fn tier_icons<'a>(
+        &self,
+        champions: &[&'a str],
+        drop_row: Option<usize>,
+    ) -> Element<'a, Message> {
+        let lines = champions.iter().enumerate().chunks(ICONS_PER_LINE);
+        column(
+            (&lines)
+                .into_iter()
+                .map(|line| {
+                    row(line
+                        .map(|(index, api_name)| {
+                            let icon = self
+                                .db
+                                .champion(api_name)
+                                .map(|champ| champ.square_icon.clone())
+                                .unwrap_or_default()
+                                .handle;
+                            mouse_area(Image::new(icon).width(Length::Fixed(ICON_SIZE)))
+                                .on_press(Message::TierLists(TierMessage::Drag(
+                                    api_name.to_string(),
+                                )))
+                                .on_release(Message::TierLists(TierMessage::Drop {
+                                    row: drop_row,
+                                    index: drop_row.map(|_| index),
+                                }))
+                                .into()
+                        })
+                        .collect::<Vec<_>>())
+                    .into()
+                })
+                .collect(),
+        )
+        .into()
+    }
+
+    fn tier_board_view(&self, list: &TierList) -> Element<Message> {
+        let mut board = column!();
+        for (row_index, tier_row) in list.rows.iter().enumerate() {
+            let champions: Vec<&str> = tier_row.champions.iter().map(String::as_str).collect();
+            let drop_zone = mouse_area(
+                container(self.tier_icons(&champions, Some(row_index)))
+                    .width(Length::Fill)
+                    // an empty row still needs room to drop on
+                    .height(if champions.is_empty() {
+                        Length::Fixed(ICON_SIZE)
+                    } else {
+                        Length::Shrink
+                    }),
+            )
+            .on_release(Message::TierLists(TierMessage::Drop {
+                row: Some(row_index),
+                index: None,
+            }));
+            board = board.push(row!(
+                container(text(&tier_row.label)).width(Length::Fixed(60.0)),
+                drop_zone,
+                button(text(self.i18n.tr("tier-rename-row")))
+                    .on_press(Message::TierLists(TierMessage::RenameRow(row_index))),
+                button(text("x")).on_press(Message::TierLists(TierMessage::RemoveRow(row_index)))
+            ));
+        }
+
+        let unranked: Vec<&str> = list
+            .unranked(&self.db.champions)
+            .into_iter()
+            .map(|champ| champ.api_name.as_str())
+            .collect();
+        board = board.push(text(self.i18n.tr("tier-unranked")));
+        board = board.push(
+            mouse_area(container(self.tier_icons(&unranked, None)).width(Length::Fill)).on_release(
+                Message::TierLists(TierMessage::Drop {
+                    row: None,
+                    index: None,
+                }),
+            ),
+        );
+
+        mouse_area(scrollable(board))
+            .on_release(Message::TierLists(TierMessage::CancelDrag))
+            .into()
+    }
+
+    pub(crate) fn tier_lists_view(&self) -> Element<Message> {
+        let board = &self.tier_board;
+        let list_buttons = column(
+            self.tier_lists
+                .iter()
+                .enumerate()
+                .map(|(index, list)| {
+                    button(text(&list.name))
+                        .on_press(Message::TierLists(TierMessage::Select(index)))
+                        .style(chip_style(index == board.selected))
+                        .into()
+                })
+                .collect(),
+        );
+        let dragging = board
+            .dragging
+            .as_ref()
+            .and_then(|api_name| self.db.champion(api_name))
+            .map(|champ| {
+                self.i18n
+                    .tr_args("tier-moving", &[("champion", champ.name.as_str().into())])
+            })
+            .unwrap_or_default();
+
+        container(row!(
+            match self.tier_lists.get(board.selected) {
+                Some(list) => self.tier_board_view(list),
+                None => text(self.i18n.tr("tier-no-lists")).into(),
+            },
+            column!(
+                list_buttons,
+                text_input(&self.i18n.tr("tier-list-name"), &board.list_name_input)
+                    .on_input(|name| Message::TierLists(TierMessage::ListNameChanged(name))),
+                row!(
+                    button(text(self.i18n.tr("tier-new-list")))
+                        .on_press(Message::TierLists(TierMessage::NewList)),
+                    button(text(self.i18n.tr("tier-rename-list")))
+                        .on_press(Message::TierLists(TierMessage::RenameList)),
+                    button(text(self.i18n.tr("delete")))
+                        .on_press(Message::TierLists(TierMessage::DeleteList))
+                        .style(iced::theme::Button::Destructive)
+                ),
+                text_input(&self.i18n.tr("tier-row-label"), &board.row_label_input)
+                    .on_input(|label| Message::TierLists(TierMessage::RowLabelChanged(label))),
+                button(text(self.i18n.tr("tier-add-row")))
+                    .on_press(Message::TierLists(TierMessage::AddRow)),
+                text(dragging),
+                text_input(&self.i18n.tr("tier-export-file"), &board.export_path)
+                    .on_input(|path| Message::TierLists(TierMessage::ExportPathChanged(path))),
+                row!(
+                    button(text(self.i18n.tr("tier-export-text")))
+                        .on_press(Message::TierLists(TierMessage::ExportText)),
+                    button(text(self.i18n.tr("tier-export-png")))
+                        .on_press(Message::TierLists(TierMessage::ExportPng))
+                ),
+                text(board.status.clone().unwrap_or_default()),
+                button(text(self.i18n.tr("save"))).on_press(Message::ClickedSave),
+                button(text(self.i18n.tr("go-to-character-builder")))
+                    .on_press(Message::ChangeScreen(Screen::CharacterBuilder))
+            )
+            .width(Length::Fixed(260.0))
+        ))
+        .width(Length::Fill)
+        .height(Length::Fill)
+        .into()
+    }
+}
@@ -0,0 +1,225 @@
+use std::{collections::BTreeSet, fs, io, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{save::write_atomic, tft_data::*};
+
+/// Scores how well `query` fuzzily matches `candidate`, or `None` if it doesn't match at all.
+///
+/// Every query character has to appear in the candidate in order, ignoring case and
+/// whitespace in the query. Consecutive matches and matches at the start of a word score higher.
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<u32> {
+    let query: Vec<char> = query
+        .to_lowercase()
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect();
+    if query.is_empty() {
+        return Some(0);
+    }
+    let candidate: Vec<char> = candidate.to_lowercase().chars().collect();
+
+    let mut score = 0;
+    let mut matched = 0;
+    let mut prev_match = None;
+    for (i, c) in candidate.iter().enumerate() {
+        if matched == query.len() {
+            break;
+        }
+        if *c != query[matched] {
+            continue;
+        }
+        score += 1;
+        if i > 0 && prev_match == Some(i - 1) {
+            score += 2;
+        }
+        if i == 0 || !candidate[i - 1].is_alphanumeric() {
+            score += 3;
+        }
+        prev_match = Some(i);
+        matched += 1;
+    }
+    (matched == query.len()).then_some(score)
+}
+
+/// Whether `query` matches the display name, the English name of localized data or the api name.
+pub fn matches_query(query: &str, name: &str, english_name: Option<&str>, api_name: &str) -> bool {
+    fuzzy_score(query, name).is_some()
+        || english_name.is_some_and(|english_name| fuzzy_score(query, english_name).is_some())
+        || fuzzy_score(query, api_name).is_some()
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChampionFilter {
+    pub query: String,
+    /// Champions of any of these costs are shown. Empty means every cost.
+    pub costs: BTreeSet<u8>,
+    /// Champions must have all of these traits.
+    pub traits: BTreeSet<String>,
+    /// Only show champions that have a BIS list saved.
+    pub has_bis: bool,
+}
+
+impl ChampionFilter {
+    pub fn matches(&self, champ: &Champion, english_name: Option<&str>, has_bis: bool) -> bool {
+        matches_query(&self.query, &champ.name, english_name, &champ.api_name)
+            && (self.costs.is_empty() || self.costs.contains(&champ.cost))
+            && self.traits.iter().all(|t| champ.traits.contains(t))
+            && (!self.has_bis || has_bis)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ItemFilter {
+    pub query: String,
+    /// Items of any of these kinds are shown. Empty means every kind.
+    pub kinds: BTreeSet<ItemKind>,
+}
+
+impl ItemFilter {
+    pub fn matches(&self, item: &Item, english_name: Option<&str>) -> bool {
+        matches_query(&self.query, &item.name, english_name, &item.api_name)
+            && (self.kinds.is_empty() || self.kinds.contains(&item.kind()))
+    }
+}
+
+/// Search and filter state of the champion and item grids, kept between sessions.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Filters {
+    pub champions: ChampionFilter,
+    pub items: ItemFilter,
+}
+
+impl Filters {
+    /// Loads saved filters, falling back to no filters if there are none or they can't be read.
+    pub fn load(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let s = serde_json::to_string(self)?;
+        write_atomic(path, s.as_bytes())
+    }
+}
+
+/// Levenshtein distance between two strings, ignoring case.
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.to_lowercase().chars().collect();
+    let b: Vec<char> = b.to_lowercase().chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut curr = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = prev[j] + usize::from(ca != cb);
+            curr[j + 1] = substitution.min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        prev = curr;
+    }
+    prev[b.len()]
+}
+
+/// The candidate most likely meant by a misspelled `name`, for "did you mean" hints.
+pub fn closest_match<'a>(
+    name: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+    let max_distance = (name.chars().count() / 3).max(2);
+    candidates
+        .into_iter()
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ITEMS: [&str; 4] = [
+        "Infinity Edge",
+        "Guinsoo's Rageblade",
+        "Blue Buff",
+        "Bloodthirster",
+    ];
+
+    /// Candidates that match, best first.
+    fn ranked<'a>(query: &str, candidates: &[&'a str]) -> Vec<&'a str> {
+        let mut scored: Vec<(u32, &str)> = candidates
+            .iter()
+            .filter_map(|candidate| Some((fuzzy_score(query, candidate)?, *candidate)))
+            .collect();
+        scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        scored.into_iter().map(|(_, candidate)| candidate).collect()
+    }
+
+    #[test]
+    fn word_starts_and_runs_rank_first() {
+        // "ie" starts both words of Infinity Edge but is scattered in the others
+        assert_eq!(
+            ranked("ie", &ITEMS),
+            ["Infinity Edge", "Guinsoo's Rageblade", "Bloodthirster"]
+        );
+        // an unbroken run beats the same letters spread out
+        assert_eq!(ranked("blu", &ITEMS), ["Blue Buff"]);
+        assert_eq!(ranked("bl", &ITEMS)[0], "Blue Buff");
+        assert!(fuzzy_score("edge", "Infinity Edge") > fuzzy_score("edge", "Guinsoo's Rageblade"));
+    }
+
+    #[test]
+    fn queries_ignore_case_and_spaces() {
+        assert!(fuzzy_score("INFINITY EDGE", "Infinity Edge").is_some());
+        assert!(fuzzy_score("blue buff", "BlueBuff").is_some());
+        assert_eq!(fuzzy_score("  ", "Blue Buff"), Some(0));
+    }
+
+    #[test]
+    fn letters_must_appear_in_order() {
+        assert_eq!(fuzzy_score("zed", "Ahri"), None);
+        assert_eq!(fuzzy_score("ffub", "Blue Buff"), None);
+        assert_eq!(fuzzy_score("blue buffs", "Blue Buff"), None);
+    }
+
+    #[test]
+    fn skipped_letters_still_match() {
+        assert!(fuzzy_score("infnty", "Infinity Edge").is_some());
+        assert!(fuzzy_score("bthirst", "Bloodthirster").is_some());
+    }
+
+    #[test]
+    fn typos_suggest_the_closest_name() {
+        assert_eq!(edit_distance("Infinty Edge", "infinity edge"), 1);
+        assert_eq!(edit_distance("Gunisoo", "Guinsoo"), 2);
+        assert_eq!(closest_match("Infinty Edg", ITEMS), Some("Infinity Edge"));
+        assert_eq!(closest_match("blu buf", ITEMS), Some("Blue Buff"));
+        assert_eq!(closest_match("Warmog's Armor", ITEMS), None);
+    }
+
+    #[test]
+    fn queries_match_english_and_api_names() {
+        assert!(matches_query(
+            "ie",
+            "Filo del infinito",
+            None,
+            "TFT_Item_InfinityEdge"
+        ));
+        assert!(matches_query(
+            "infinity",
+            "Filo del infinito",
+            Some("Infinity Edge"),
+            "TFT_Item_InfinityEdge"
+        ));
+        assert!(!matches_query(
+            "rageblade",
+            "Filo del infinito",
+            Some("Infinity Edge"),
+            "x"
+        ));
+    }
+}
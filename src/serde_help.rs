@@ -1,6 +1,11 @@
 use std::{
+    collections::HashSet,
+    fs, io,
     path::{Path, PathBuf},
-    sync::OnceLock,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        OnceLock,
+    },
 };
 
 use directories::ProjectDirs;
@@ -19,65 +24,162 @@ static DIR: OnceLock<ProjectDirs> = OnceLock::new();
 static CACHE_DIR: OnceLock<PathBuf> = OnceLock::new();
 static DATA_DIR: OnceLock<PathBuf> = OnceLock::new();
 
+static DOWNLOAD_IMAGES: AtomicBool = AtomicBool::new(true);
+
+/// Turns fetching of uncached icons on or off. Headless front-ends don't need the images, and
+/// offline runs can't fetch them.
+pub fn set_image_downloads(enabled: bool) {
+    DOWNLOAD_IMAGES.store(enabled, Ordering::Relaxed);
+}
+
+/// Name the app's directories are named after.
+pub const APPLICATION: &str = "TFT_Notebook";
+
+/// Organization older versions nested the directories in on Windows and macOS. Only used to
+/// find them, see `move_legacy_dirs`.
+const LEGACY_ORGANIZATION: &str = "Sharif Haason";
+
+/// The app's cache, config and data directories.
+pub fn project_dirs() -> &'static ProjectDirs {
+    DIR.get_or_init(|| {
+        let dirs = ProjectDirs::from("", "", APPLICATION).unwrap();
+        move_legacy_dirs(&dirs);
+        dirs
+    })
+}
+
+/// Moves the directories of older versions to where they are now, so saves aren't left behind.
+/// Directories that already exist are kept. On Linux the paths never had the organization in
+/// them, so nothing moves.
+fn move_legacy_dirs(dirs: &ProjectDirs) {
+    let Some(legacy) = ProjectDirs::from("", LEGACY_ORGANIZATION, APPLICATION) else {
+        return;
+    };
+    for (old, new) in [
+        (legacy.config_dir(), dirs.config_dir()),
+        (legacy.data_dir(), dirs.data_dir()),
+        (legacy.cache_dir(), dirs.cache_dir()),
+    ] {
+        if old == new || !old.exists() || new.exists() {
+            continue;
+        }
+        let moved = new
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|()| fs::rename(old, new));
+        if let Err(e) = moved {
+            log::warn!("could not move {} to {}: {e}", old.display(), new.display());
+        }
+    }
+}
+
+/// The app's cache directory, created on first use.
+pub fn cache_dir() -> &'static Path {
+    let dir = project_dirs();
+    CACHE_DIR.get_or_init(|| {
+        std::fs::create_dir_all(dir.cache_dir()).unwrap();
+        dir.cache_dir().to_path_buf()
+    })
+}
+
+/// Deletes the oldest cached icons until they take up at most `max_bytes`, skipping the ones
+/// `in_use`. Returns how many bytes were freed. The cached data files are kept.
+pub fn prune_image_cache(max_bytes: u64, in_use: &HashSet<PathBuf>) -> io::Result<u64> {
+    let mut icons = vec![];
+    for entry in fs::read_dir(cache_dir())? {
+        let entry = entry?;
+        let path = entry.path();
+        if path.extension().is_some_and(|extension| extension == "png") && !in_use.contains(&path) {
+            let metadata = entry.metadata()?;
+            icons.push((metadata.modified()?, metadata.len(), path));
+        }
+    }
+    let mut total: u64 = icons.iter().map(|(_, len, _)| len).sum();
+    icons.sort();
+    let mut freed = 0;
+    for (_, len, path) in icons {
+        if total <= max_bytes {
+            break;
+        }
+        fs::remove_file(path)?;
+        total -= len;
+        freed += len;
+    }
+    Ok(freed)
+}
+
+/// Where the icon at `url` is cached once downloaded.
+pub fn cached_image_path(url: &str) -> PathBuf {
+    cache_dir().join(url.rsplit('/').next().unwrap_or_default())
+}
+
 pub fn deserialize_image<'de, D>(deserializer: D) -> Result<Handle, D::Error>
 where
     D: Deserializer<'de>,
 {
-    let opt: Option<String> = Option::deserialize(deserializer)?;
-    if opt.is_none() {
+    let Some(path) = Option::<String>::deserialize(deserializer)? else {
         return Ok(Handle::default());
-    }
-    let s = opt
-        .unwrap()
+    };
+    let path = path
         .to_lowercase() // url needs to be lowercase
         .replace("dds", "png") // replace dds file with png
         .replace("tex", "png");
-    let mut url = String::from(CDRAGON_URL);
-    url.push_str(&s);
-
-    let path: Vec<&str> = url.split('/').collect();
-    let file_name = path.last().unwrap();
-
-    let dir = DIR.get_or_init(|| ProjectDirs::from("", "Sharif Haason", "TFT_Notebook").unwrap());
-    let cache_dir = CACHE_DIR.get_or_init(|| {
-        std::fs::create_dir_all(dir.cache_dir()).unwrap();
-        dir.cache_dir().to_path_buf()
-    });
-
-    let cache_path = cache_dir.join(file_name);
-
-    let image = if !Path::exists(&cache_dir.join(path.last().unwrap())) {
-        let mut buf: Vec<u8> = vec![];
-        ureq::get(&url)
-            .call()
-            .unwrap()
-            .into_reader()
-            .read_to_end(&mut buf)
-            .unwrap();
+    let url = format!("{CDRAGON_URL}{path}");
+    let cache_path = cached_image_path(&url);
+
+    if Path::exists(&cache_path) || !DOWNLOAD_IMAGES.load(Ordering::Relaxed) {
+        return Ok(Handle {
+            handle: image::Handle::from_path(cache_path),
+            url,
+        });
+    }
+    let image = match download_image(&url) {
+        Ok(buf) => cache_image(&buf, &cache_path),
+        Err(e) => {
+            // a missing icon isn't worth failing the whole data load for
+            log::warn!("could not download {url}: {e}");
+            return Ok(Handle {
+                url,
+                ..Handle::default()
+            });
+        }
+    };
 
-        let img_mem = img::load_from_memory(&buf).unwrap();
+    Ok(Handle { handle: image, url })
+}
 
-        // dbg!(img_mem.height(), img_mem.width());
-        // dbg!(&url);
+fn download_image(url: &str) -> Result<Vec<u8>, String> {
+    let mut buf: Vec<u8> = vec![];
+    ureq::get(url)
+        .call()
+        .map_err(|e| e.to_string())?
+        .into_reader()
+        .read_to_end(&mut buf)
+        .map_err(|e| e.to_string())?;
+    Ok(buf)
+}
 
+/// Saves a downloaded icon at most 128 pixels wide. Icons that can't be saved are shown from
+/// memory until the next start.
+fn cache_image(buf: &[u8], cache_path: &Path) -> image::Handle {
+    let saved = img::load_from_memory(buf).and_then(|img_mem| {
         let img_mem = if img_mem.width() > 128 {
             img_mem.resize(128, 128, img::imageops::FilterType::CatmullRom)
         } else {
             img_mem
         };
-
-        // dbg!(img_mem.height(), img_mem.width());
-
-        img_mem.save(cache_path).unwrap();
-        // fs::write(cache_path, &img_mem).unwrap();
-
-        println!("{} has been cached", file_name);
-        image::Handle::from_memory(img_mem.as_bytes().to_owned())
-    } else {
-        image::Handle::from_path(cache_path)
-    };
-
-    Ok(Handle { handle: image, url })
+        img_mem.save(cache_path)
+    });
+    match saved {
+        Ok(()) => {
+            log::info!("{} has been cached", cache_path.display());
+            image::Handle::from_path(cache_path)
+        }
+        Err(e) => {
+            log::warn!("could not cache {}: {e}", cache_path.display());
+            image::Handle::from_memory(buf.to_owned())
+        }
+    }
 }
 
 pub fn deserialize_null_default<'de, D, T>(deserializer: D) -> Result<T, D::Error>
@@ -0,0 +1,681 @@
+use std::{
+    io::Write,
+    net::IpAddr,
+    sync::{
+        mpsc::{self, Receiver, RecvTimeoutError, Sender},
+        Mutex,
+    },
+    thread,
+    time::Duration,
+};
+
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::{
+    database::TftDatabase,
+    determiner::rank_champions,
+    notebook::{validate_name, NotebookStore, DEFAULT_NOTEBOOK},
+    state::ComponentState,
+};
+
+/// How often an idle event stream is written to, to find out whether the client is still there.
+const KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Status and JSON body of an API response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiResponse {
+    pub status: u16,
+    pub body: String,
+}
+
+impl ApiResponse {
+    fn ok(value: &impl Serialize) -> Self {
+        Self {
+            status: 200,
+            body: serde_json::to_string(value).unwrap(),
+        }
+    }
+
+    fn error(status: u16, message: impl ToString) -> Self {
+        Self {
+            status,
+            body: json!({ "error": message.to_string() }).to_string(),
+        }
+    }
+
+    fn not_found() -> Self {
+        Self::error(404, "not found")
+    }
+}
+
+/// Data served by the API, shared between request threads.
+///
+/// The ItemDeterminer's component counts live here, so overlays see the counts set through the
+/// API, and every change is pushed to the `/determiner/events` stream.
+pub struct ApiState {
+    pub db: TftDatabase,
+    pub notebooks: NotebookStore,
+    components: Mutex<Vec<ComponentState>>,
+    subscribers: Mutex<Vec<Sender<String>>>,
+}
+
+impl ApiState {
+    pub fn new(db: TftDatabase, notebooks: NotebookStore) -> Self {
+        let components = db
+            .components
+            .iter()
+            .cloned()
+            .map(|component| ComponentState {
+                component,
+                count: 0,
+            })
+            .collect();
+        Self {
+            db,
+            notebooks,
+            components: Mutex::new(components),
+            subscribers: Mutex::new(vec![]),
+        }
+    }
+
+    /// Receives the determiner state as JSON after every change.
+    pub fn subscribe(&self) -> Receiver<String> {
+        let (sender, receiver) = mpsc::channel();
+        self.subscribers.lock().unwrap().push(sender);
+        receiver
+    }
+
+    fn broadcast(&self, event: String) {
+        // disconnected subscribers are dropped
+        self.subscribers
+            .lock()
+            .unwrap()
+            .retain(|sender| sender.send(event.clone()).is_ok());
+    }
+
+    /// Component counts and the champions of a notebook ranked by held BIS components.
+    fn determiner(&self, notebook: &str) -> Result<Value, ApiResponse> {
+        if !self.notebooks.exists(notebook) {
+            return Err(ApiResponse::not_found());
+        }
+        let save = self
+            .notebooks
+            .load(notebook)
+            .map_err(|e| ApiResponse::error(500, e))?;
+        let (champs, _) = save.reconcile(&self.db);
+        let components = self.components.lock().unwrap();
+        let ranking: Vec<Value> = rank_champions(&champs, &components)
+            .into_iter()
+            .filter(|(champ_state, _)| !champ_state.items.is_empty())
+            .map(|(champ_state, held)| {
+                json!({
+                    "champion": champ_state.champ.api_name,
+                    "held": held,
+                    "items": champ_state
+                        .items
+                        .iter()
+                        .map(|item| &item.api_name)
+                        .collect::<Vec<_>>(),
+                })
+            })
+            .collect();
+        Ok(json!({
+            "notebook": notebook,
+            "components": components
+                .iter()
+                .map(|component| json!({
+                    "api_name": component.component.api_name,
+                    "name": component.component.name,
+                    "count": component.count,
+                }))
+                .collect::<Vec<_>>(),
+            "ranking": ranking,
+        }))
+    }
+
+    fn set_component_count(&self, api_name: &str, body: &str) -> Result<(), ApiResponse> {
+        let count = serde_json::from_str::<Value>(body)
+            .ok()
+            .and_then(|body| body.get("count")?.as_u64())
+            .ok_or_else(|| ApiResponse::error(400, "expected {\"count\": <number>}"))?;
+        let mut components = self.components.lock().unwrap();
+        let component = components
+            .iter_mut()
+            .find(|component| component.component.api_name == api_name)
+            .ok_or_else(ApiResponse::not_found)?;
+        component.count = count as usize;
+        Ok(())
+    }
+}
+
+/// Decodes `%XX` escapes, so notebook names can contain spaces.
+fn percent_decode(s: &str) -> String {
+    let mut bytes = vec![];
+    let mut rest = s.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        let escaped = (byte == b'%')
+            .then(|| tail.get(..2))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok());
+        match escaped {
+            Some(decoded) => {
+                bytes.push(decoded);
+                rest = &tail[2..];
+            }
+            None => {
+                bytes.push(byte);
+                rest = tail;
+            }
+        }
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+fn query_param(query: &str, name: &str) -> Option<String> {
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| percent_decode(value))
+}
+
+/// Names come from the url and become file names, so they're checked before any lookup.
+fn notebook_name(name: &str) -> Result<&str, ApiResponse> {
+    validate_name(name)
+        .map(|()| name)
+        .map_err(|e| ApiResponse::error(400, e))
+}
+
+/// The notebook named in the query, or else the one last opened in the app.
+fn determiner_notebook(state: &ApiState, query: &str) -> Result<String, ApiResponse> {
+    match query_param(query, "notebook") {
+        Some(name) => notebook_name(&name).map(str::to_string),
+        None => Ok(state
+            .notebooks
+            .last_used()
+            .unwrap_or_else(|| String::from(DEFAULT_NOTEBOOK))),
+    }
+}
+
+/// Answers one API request. Everything except the event stream goes through here, so the API
+/// can be exercised without a socket.
+///
+/// | Route | |
+/// |---|---|
+/// | `GET /champions`, `/champions/<api name>` | parsed champions |
+/// | `GET /items`, `/items/<api name>` | completed items and components |
+/// | `GET /traits`, `/traits/<name or api name>` | traits |
+/// | `GET /recipes` | completed items with their components |
+/// | `GET /notebooks`, `/notebooks/<name>` | notebook names, saved notebooks |
+/// | `GET /determiner?notebook=<name>` | component counts and ranked champions |
+/// | `PUT /determiner/<component api name>?notebook=<name>` | sets a count from `{"count": n}` |
+///
+/// `content_type` is the media type of `body`, without parameters.
+pub fn route(
+    state: &ApiState,
+    method: &str,
+    url: &str,
+    content_type: Option<&str>,
+    body: &str,
+) -> ApiResponse {
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    let segments: Vec<String> = path
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .map(percent_decode)
+        .collect();
+    let segments: Vec<&str> = segments.iter().map(String::as_str).collect();
+    let db = &state.db;
+
+    match (method, segments.as_slice()) {
+        ("GET", ["champions"]) => ApiResponse::ok(&db.champions),
+        ("GET", ["champions", api_name]) => db
+            .champion(api_name)
+            .map_or_else(ApiResponse::not_found, ApiResponse::ok),
+        ("GET", ["items"]) => ApiResponse::ok(&json!({
+            "items": db.items,
+            "components": db.components,
+        })),
+        ("GET", ["items", api_name]) => db
+            .item(api_name)
+            .map_or_else(ApiResponse::not_found, ApiResponse::ok),
+        ("GET", ["traits"]) => ApiResponse::ok(&db.traits),
+        ("GET", ["traits", name]) => db
+            .traits
+            .iter()
+            .find(|t| t.name == *name || t.api_name == *name)
+            .map_or_else(ApiResponse::not_found, ApiResponse::ok),
+        ("GET", ["recipes"]) => ApiResponse::ok(
+            &db.items
+                .iter()
+                .map(|item| {
+                    json!({
+                        "item": item.api_name,
+                        "name": item.name,
+                        "components": item.composition,
+                    })
+                })
+                .collect::<Vec<_>>(),
+        ),
+        ("GET", ["notebooks"]) => match state.notebooks.list() {
+            Ok(names) => ApiResponse::ok(&names),
+            Err(e) => ApiResponse::error(500, e),
+        },
+        ("GET", ["notebooks", name]) => match notebook_name(name) {
+            Err(response) => response,
+            Ok(name) if !state.notebooks.exists(name) => ApiResponse::not_found(),
+            Ok(name) => match state.notebooks.load(name) {
+                Ok(save) => ApiResponse::ok(&save),
+                Err(e) => ApiResponse::error(500, e),
+            },
+        },
+        ("GET", ["determiner"]) => determiner_notebook(state, query)
+            .and_then(|notebook| state.determiner(&notebook))
+            .map_or_else(|response| response, |value| ApiResponse::ok(&value)),
+        // only JSON PUTs, which browsers preflight, so other sites can't change the counts
+        ("PUT", ["determiner", _]) if content_type != Some("application/json") => {
+            ApiResponse::error(415, "expected Content-Type: application/json")
+        }
+        ("PUT", ["determiner", api_name]) => {
+            let result = determiner_notebook(state, query).and_then(|notebook| {
+                if !state.notebooks.exists(&notebook) {
+                    return Err(ApiResponse::not_found());
+                }
+                state.set_component_count(api_name, body)?;
+                state.determiner(&notebook)
+            });
+            match result {
+                Ok(value) => {
+                    state.broadcast(value.to_string());
+                    ApiResponse::ok(&value)
+                }
+                Err(response) => response,
+            }
+        }
+        // CORS preflight, the allowed origins and methods are in the headers
+        ("OPTIONS", _) => ApiResponse {
+            status: 204,
+            body: String::new(),
+        },
+        _ => ApiResponse::not_found(),
+    }
+}
+
+/// Whether a page from `origin` may use the API: only pages served from this machine, like
+/// stream overlays, are.
+fn is_loopback_origin(origin: &str) -> bool {
+    let Some(authority) = origin
+        .strip_prefix("http://")
+        .or_else(|| origin.strip_prefix("https://"))
+    else {
+        return false;
+    };
+    let host = match authority.strip_prefix('[') {
+        Some(ipv6) => ipv6.split(']').next().unwrap_or_default(),
+        None => authority.split(':').next().unwrap_or_default(),
+    };
+    host == "localhost" || host.parse::<IpAddr>().is_ok_and(|ip| ip.is_loopback())
+}
+
+fn header(request: &tiny_http::Request, name: &'static str) -> Option<String> {
+    request
+        .headers()
+        .iter()
+        .find(|header| header.field.equiv(name))
+        .map(|header| header.value.as_str().to_string())
+}
+
+/// CORS headers for a loopback `Origin`, none for other origins so browsers block them.
+fn cors_headers(request: &tiny_http::Request) -> Vec<String> {
+    match header(request, "Origin").filter(|origin| is_loopback_origin(origin)) {
+        Some(origin) => vec![
+            format!("Access-Control-Allow-Origin: {origin}"),
+            String::from("Access-Control-Allow-Methods: GET, PUT"),
+            String::from("Access-Control-Allow-Headers: Content-Type"),
+            String::from("Vary: Origin"),
+        ],
+        None => vec![],
+    }
+}
+
+/// Holds the connection open and writes a server-sent event for every determiner change.
+fn stream_events(state: &ApiState, request: tiny_http::Request) {
+    let events = state.subscribe();
+    let mut head = String::from(
+        "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\n",
+    );
+    for header in cors_headers(&request) {
+        head.push_str(&header);
+        head.push_str("\r\n");
+    }
+    head.push_str("\r\n");
+    let mut writer = request.into_writer();
+    // flushed right away, so the client sees the stream open before the first event
+    let opened = writer
+        .write_all(head.as_bytes())
+        .and_then(|()| writer.flush());
+    if opened.is_err() {
+        return;
+    }
+    loop {
+        let message = match events.recv_timeout(KEEP_ALIVE_INTERVAL) {
+            Ok(event) => format!("event: determiner\ndata: {event}\n\n"),
+            // a comment, so a client that went away is noticed without waiting for a change
+            Err(RecvTimeoutError::Timeout) => String::from(": keep-alive\n\n"),
+            Err(RecvTimeoutError::Disconnected) => return,
+        };
+        let sent = writer
+            .write_all(message.as_bytes())
+            .and_then(|()| writer.flush());
+        if sent.is_err() {
+            // the client went away
+            return;
+        }
+    }
+}
+
+fn respond(state: &ApiState, mut request: tiny_http::Request) {
+    let mut body = String::new();
+    // a body that isn't valid utf-8 is treated as missing
+    let _ = request.as_reader().read_to_string(&mut body);
+    let content_type = header(&request, "Content-Type");
+    // parameters like the charset don't matter
+    let media_type = content_type
+        .as_deref()
+        .and_then(|content_type| content_type.split(';').next())
+        .map(str::trim);
+    let response = route(
+        state,
+        request.method().as_str(),
+        request.url(),
+        media_type,
+        &body,
+    );
+    let mut headers = vec![String::from("Content-Type: application/json")];
+    headers.extend(cors_headers(&request));
+    let response = headers.iter().fold(
+        tiny_http::Response::from_string(response.body).with_status_code(response.status),
+        |response, header| response.with_header(header.parse::<tiny_http::Header>().unwrap()),
+    );
+    if let Err(e) = request.respond(response) {
+        log::warn!("failed to respond: {e}");
+    }
+}
+
+/// The API's listening socket. Binding comes before serving, so the address is known even when
+/// the port was picked by the system.
+pub struct ApiServer {
+    server: tiny_http::Server,
+}
+
+impl ApiServer {
+    pub fn bind(addr: &str) -> Result<Self, String> {
+        let server = tiny_http::Server::http(addr).map_err(|e| e.to_string())?;
+        Ok(Self { server })
+    }
+
+    pub fn addr(&self) -> String {
+        self.server.server_addr().to_string()
+    }
+
+    /// Serves the API until the process exits. Every request gets its own thread, so event
+    /// streams don't block other requests.
+    pub fn serve(self, state: &'static ApiState) {
+        for request in self.server.incoming_requests() {
+            thread::spawn(move || {
+                if request.url().split('?').next() == Some("/determiner/events") {
+                    stream_events(state, request);
+                } else {
+                    respond(state, request);
+                }
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::BufRead;
+
+    use super::*;
+    use crate::{
+        save::SaveFile,
+        tft_data::{Item, Trait},
+    };
+
+    const TEAR: &str = "TFT_Item_TearOfTheGoddess";
+
+    /// Ahri with a Blue Buff saved in the default notebook, in a fresh data directory.
+    fn state(test: &str) -> ApiState {
+        let dir = std::env::temp_dir().join(format!("tft_server_{}_{test}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let notebooks = NotebookStore::open(&dir).unwrap();
+        let mut save = SaveFile::default();
+        save.champions.insert(
+            String::from("TFT8_Ahri"),
+            vec![String::from("TFT_Item_BlueBuff")],
+        );
+        notebooks.save(DEFAULT_NOTEBOOK, &save).unwrap();
+
+        let mut db = TftDatabase::with_names(&["TFT8_Ahri"], &["TFT_Item_BlueBuff"]);
+        db.items[0].composition = vec![String::from(TEAR); 2];
+        let mut tear = Item::default();
+        tear.api_name = String::from(TEAR);
+        tear.name = String::from("Tear of the Goddess");
+        db.components.push(tear);
+        db.traits.push(Trait {
+            api_name: String::from("Set8_Spellslinger"),
+            name: String::from("Spellslinger"),
+            ..Trait::default()
+        });
+        ApiState::new(db, notebooks)
+    }
+
+    fn get(state: &ApiState, url: &str) -> ApiResponse {
+        route(state, "GET", url, None, "")
+    }
+
+    fn put(state: &ApiState, url: &str, body: &str) -> ApiResponse {
+        route(state, "PUT", url, Some("application/json"), body)
+    }
+
+    fn json(response: &ApiResponse) -> Value {
+        serde_json::from_str(&response.body).unwrap()
+    }
+
+    #[test]
+    fn data_routes() {
+        let state = state("data");
+        assert_eq!(json(&get(&state, "/champions"))[0]["apiName"], "TFT8_Ahri");
+        assert_eq!(get(&state, "/champions/TFT8_Ahri").status, 200);
+        assert_eq!(get(&state, "/champions/TFT8_Annie").status, 404);
+
+        let items = json(&get(&state, "/items"));
+        assert_eq!(items["items"][0]["apiName"], "TFT_Item_BlueBuff");
+        assert_eq!(items["components"][0]["apiName"], TEAR);
+        assert_eq!(get(&state, &format!("/items/{TEAR}")).status, 200);
+        assert_eq!(get(&state, "/items/TFT_Item_Nothing").status, 404);
+
+        assert_eq!(json(&get(&state, "/traits"))[0]["name"], "Spellslinger");
+        assert_eq!(get(&state, "/traits/Spellslinger").status, 200);
+        assert_eq!(get(&state, "/traits/Set8_Spellslinger").status, 200);
+        assert_eq!(get(&state, "/traits/Duelist").status, 404);
+
+        let recipes = json(&get(&state, "/recipes"));
+        assert_eq!(recipes[0]["item"], "TFT_Item_BlueBuff");
+        assert_eq!(recipes[0]["components"], serde_json::json!([TEAR, TEAR]));
+    }
+
+    #[test]
+    fn notebook_routes() {
+        let state = state("notebooks");
+        assert_eq!(
+            json(&get(&state, "/notebooks")),
+            serde_json::json!([DEFAULT_NOTEBOOK])
+        );
+        let notebook = json(&get(&state, "/notebooks/Default"));
+        assert_eq!(
+            notebook["champions"]["TFT8_Ahri"],
+            serde_json::json!(["TFT_Item_BlueBuff"])
+        );
+        assert_eq!(get(&state, "/notebooks/Other%20one").status, 404);
+    }
+
+    #[test]
+    fn unknown_routes_are_not_found() {
+        let state = state("unknown");
+        assert_eq!(get(&state, "/").status, 404);
+        assert_eq!(get(&state, "/champions/TFT8_Ahri/items").status, 404);
+        assert_eq!(route(&state, "DELETE", "/champions", None, "").status, 404);
+        assert_eq!(route(&state, "POST", "/determiner/x", None, "").status, 404);
+    }
+
+    #[test]
+    fn path_names_are_rejected() {
+        let state = state("names");
+        assert_eq!(get(&state, "/notebooks/..%2F..%2Fsecret").status, 400);
+        assert_eq!(get(&state, "/determiner?notebook=..%2Fsecret").status, 400);
+        let response = put(
+            &state,
+            &format!("/determiner/{TEAR}?notebook=%2Fetc%2Fpasswd"),
+            r#"{"count": 1}"#,
+        );
+        assert_eq!(response.status, 400);
+    }
+
+    #[test]
+    fn determiner_counts_and_ranks() {
+        let state = state("determiner");
+        let events = state.subscribe();
+        let determiner = json(&get(&state, "/determiner?notebook=Default"));
+        assert_eq!(determiner["components"][0]["count"], 0);
+        assert_eq!(determiner["ranking"][0]["held"], 0);
+
+        let response = put(&state, &format!("/determiner/{TEAR}"), r#"{"count": 2}"#);
+        assert_eq!(response.status, 200);
+        let determiner = json(&response);
+        assert_eq!(determiner["components"][0]["count"], 2);
+        assert_eq!(determiner["ranking"][0]["champion"], "TFT8_Ahri");
+        assert_eq!(determiner["ranking"][0]["held"], 2);
+        assert_eq!(events.try_recv().unwrap(), response.body);
+    }
+
+    #[test]
+    fn bad_puts_are_rejected() {
+        let state = state("bad_puts");
+        let url = format!("/determiner/{TEAR}");
+        assert_eq!(put(&state, &url, "two").status, 400);
+        assert_eq!(put(&state, &url, r#"{"count": -1}"#).status, 400);
+        assert_eq!(
+            put(&state, "/determiner/TFT_Item_Nothing", r#"{"count": 1}"#).status,
+            404
+        );
+        assert_eq!(
+            put(
+                &state,
+                &format!("{url}?notebook=Missing"),
+                r#"{"count": 1}"#
+            )
+            .status,
+            404
+        );
+        // a form or text post needs no preflight, so it could come from any site
+        let response = route(&state, "PUT", &url, Some("text/plain"), r#"{"count": 1}"#);
+        assert_eq!(response.status, 415);
+        assert_eq!(
+            json(&get(&state, "/determiner"))["components"][0]["count"],
+            0
+        );
+    }
+
+    #[test]
+    fn preflight_is_answered() {
+        let state = state("preflight");
+        assert_eq!(
+            route(&state, "OPTIONS", "/determiner/x", None, "").status,
+            204
+        );
+    }
+
+    /// Serves `state` on a free local port and returns the base url.
+    fn serve(state: ApiState) -> String {
+        let server = ApiServer::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", server.addr());
+        let state: &'static ApiState = Box::leak(Box::new(state));
+        thread::spawn(move || server.serve(state));
+        url
+    }
+
+    #[test]
+    fn requests_are_answered_over_http() {
+        let url = serve(state("http"));
+        let response = ureq::get(&format!("{url}/champions")).call().unwrap();
+        assert_eq!(response.content_type(), "application/json");
+        let champions: Value = serde_json::from_str(&response.into_string().unwrap()).unwrap();
+        assert_eq!(champions[0]["apiName"], "TFT8_Ahri");
+
+        let response = ureq::put(&format!("{url}/determiner/{TEAR}"))
+            .set("Origin", "http://localhost:8080")
+            .set("Content-Type", "application/json")
+            .send_string(r#"{"count": 1}"#)
+            .unwrap();
+        assert_eq!(
+            response.header("Access-Control-Allow-Origin"),
+            Some("http://localhost:8080")
+        );
+        let determiner: Value = serde_json::from_str(&response.into_string().unwrap()).unwrap();
+        assert_eq!(determiner["components"][0]["count"], 1);
+
+        // the browser blocks other sites by the missing CORS headers
+        let response = ureq::get(&format!("{url}/determiner"))
+            .set("Origin", "https://example.com")
+            .call()
+            .unwrap();
+        assert_eq!(response.header("Access-Control-Allow-Origin"), None);
+
+        let text_put = ureq::put(&format!("{url}/determiner/{TEAR}"))
+            .set("Content-Type", "text/plain")
+            .send_string(r#"{"count": 2}"#);
+        assert!(matches!(text_put, Err(ureq::Error::Status(415, _))));
+        assert!(matches!(
+            ureq::get(&format!("{url}/nothing")).call(),
+            Err(ureq::Error::Status(404, _))
+        ));
+    }
+
+    #[test]
+    fn changes_are_streamed_as_events() {
+        let url = serve(state("events"));
+        let stream = ureq::get(&format!("{url}/determiner/events"))
+            .set("Origin", "http://127.0.0.1:3000")
+            .call()
+            .unwrap();
+        assert_eq!(stream.content_type(), "text/event-stream");
+        assert_eq!(
+            stream.header("Access-Control-Allow-Origin"),
+            Some("http://127.0.0.1:3000")
+        );
+        let mut lines = std::io::BufReader::new(stream.into_reader()).lines();
+
+        ureq::put(&format!("{url}/determiner/{TEAR}"))
+            .set("Content-Type", "application/json")
+            .send_string(r#"{"count": 2}"#)
+            .unwrap();
+        assert_eq!(lines.next().unwrap().unwrap(), "event: determiner");
+        let data = lines.next().unwrap().unwrap();
+        let event: Value = serde_json::from_str(data.strip_prefix("data: ").unwrap()).unwrap();
+        assert_eq!(event["components"][0]["count"], 2);
+        assert_eq!(event["ranking"][0]["held"], 2);
+    }
+
+    #[test]
+    fn only_loopback_origins_are_allowed() {
+        assert!(is_loopback_origin("http://localhost:8080"));
+        assert!(is_loopback_origin("http://127.0.0.1"));
+        assert!(is_loopback_origin("https://[::1]:3000"));
+        assert!(!is_loopback_origin("https://example.com"));
+        assert!(!is_loopback_origin("http://localhost.example.com"));
+        assert!(!is_loopback_origin("null"));
+    }
+}
@@ -0,0 +1,448 @@
+use std::{collections::BTreeMap, fmt::Display};
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use serde_json::Value;
+
+use crate::comp::{BoardPosition, Comp, CompUnit, BOARD_COLS, BOARD_ROWS};
+
+/// Units a team planner code can hold.
+pub const PLANNER_SLOTS: usize = 10;
+
+/// Prefix of the app's own share codes, which also carry items, stars and positions.
+const SHARE_CODE_PREFIX: &str = "TFTN1.";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CodeError {
+    /// The team planner data of the set isn't loaded, so its codes are unknown.
+    NoPlanner(String),
+    TooManyUnits(usize),
+    /// A champion the planner has no code for.
+    UnknownChampion(String),
+    /// A planner code no champion of the set uses.
+    UnknownCode(u16),
+    WrongSet {
+        expected: String,
+        found: String,
+    },
+    Malformed(String),
+}
+
+impl Display for CodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CodeError::NoPlanner(set) => write!(
+                f,
+                "the team planner codes of {set} aren't loaded, use a share code instead"
+            ),
+            CodeError::TooManyUnits(count) => write!(
+                f,
+                "team planner codes hold at most {PLANNER_SLOTS} units, this comp has {count}"
+            ),
+            CodeError::UnknownChampion(api_name) => {
+                write!(f, "{api_name} is not in the team planner")
+            }
+            CodeError::UnknownCode(code) => write!(f, "no champion has planner code {code}"),
+            CodeError::WrongSet { expected, found } => {
+                write!(f, "code is for {found}, but the loaded set is {expected}")
+            }
+            CodeError::Malformed(reason) => write!(f, "not a valid code: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for CodeError {}
+
+/// The in-game team planner code: a format version, one hex number per slot with `0` for empty
+/// slots, and the set identifier, e.g. `02` + `01a` `000` ... + `TFTSet9`.
+///
+/// Version `01` uses two hex digits per slot, version `02` three.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlannerCode {
+    pub set: String,
+    /// Planner codes of the units, without the empty slots.
+    pub champions: Vec<u16>,
+}
+
+impl PlannerCode {
+    pub fn encode(&self) -> Result<String, CodeError> {
+        if self.champions.len() > PLANNER_SLOTS {
+            return Err(CodeError::TooManyUnits(self.champions.len()));
+        }
+        let mut code = String::from("02");
+        for slot in 0..PLANNER_SLOTS {
+            let champion = self.champions.get(slot).copied().unwrap_or_default();
+            code.push_str(&format!("{champion:03x}"));
+        }
+        code.push_str(&self.set);
+        Ok(code)
+    }
+
+    pub fn decode(code: &str) -> Result<Self, CodeError> {
+        let code = code.trim();
+        let digits = match code.get(..2) {
+            Some("01") => 2,
+            Some("02") => 3,
+            _ => return Err(CodeError::Malformed(String::from("unknown version"))),
+        };
+        let slots_end = 2 + digits * PLANNER_SLOTS;
+        let (Some(slots), Some(set)) = (code.get(2..slots_end), code.get(slots_end..)) else {
+            return Err(CodeError::Malformed(String::from("too short")));
+        };
+        // checked before slicing, multi-byte characters would split a slot and `from_str_radix`
+        // takes a leading `+`
+        if !slots.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(CodeError::Malformed(String::from(
+                "the slots have to be hexadecimal",
+            )));
+        }
+        if !set.starts_with("TFTSet") {
+            return Err(CodeError::Malformed(String::from("missing set identifier")));
+        }
+        let mut champions = vec![];
+        for slot in 0..PLANNER_SLOTS {
+            let hex = &slots[slot * digits..(slot + 1) * digits];
+            let champion = u16::from_str_radix(hex, 16)
+                .map_err(|_| CodeError::Malformed(format!("\"{hex}\" is not hexadecimal")))?;
+            if champion != 0 {
+                champions.push(champion);
+            }
+        }
+        Ok(Self {
+            set: set.to_string(),
+            champions,
+        })
+    }
+}
+
+/// Maps champions of a set to their team planner codes.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PlannerIndex {
+    /// Set identifier used in codes, e.g. `TFTSet9`.
+    pub set: String,
+    /// Only ever the official codes. Made up ones would look like in-game codes but not match
+    /// them, so without the team planner data there are none.
+    codes: BTreeMap<String, u16>,
+}
+
+impl PlannerIndex {
+    /// Reads CommunityDragon's `tftchampions-teamplanner.json` for the given set.
+    pub fn from_json(json: &Value, set: &str) -> Option<Self> {
+        let codes = json
+            .get(set)?
+            .as_array()?
+            .iter()
+            .filter_map(|champion| {
+                let api_name = champion.get("character_id")?.as_str()?;
+                let code = champion.get("team_planner_code")?.as_u64()?;
+                Some((api_name.to_string(), u16::try_from(code).ok()?))
+            })
+            .collect();
+        Some(Self {
+            set: set.to_string(),
+            codes,
+        })
+    }
+
+    /// An index without codes, for a set whose team planner data isn't loaded. Encoding and
+    /// decoding with it fails with `CodeError::NoPlanner`.
+    pub fn unavailable(set: &str) -> Self {
+        Self {
+            set: set.to_string(),
+            codes: BTreeMap::new(),
+        }
+    }
+
+    pub fn is_available(&self) -> bool {
+        !self.codes.is_empty()
+    }
+
+    fn check_available(&self) -> Result<(), CodeError> {
+        if self.is_available() {
+            Ok(())
+        } else {
+            Err(CodeError::NoPlanner(self.set.clone()))
+        }
+    }
+
+    pub fn code_of(&self, api_name: &str) -> Option<u16> {
+        self.codes.get(api_name).copied()
+    }
+
+    pub fn champion_of(&self, code: u16) -> Option<&str> {
+        self.codes
+            .iter()
+            .find(|(_, c)| **c == code)
+            .map(|(api_name, _)| api_name.as_str())
+    }
+
+    /// The team planner code of a comp. Only the champions make it into the code.
+    pub fn encode_comp(&self, comp: &Comp) -> Result<String, CodeError> {
+        self.check_available()?;
+        let champions = comp
+            .units
+            .iter()
+            .map(|unit| {
+                self.code_of(&unit.champion)
+                    .ok_or_else(|| CodeError::UnknownChampion(unit.champion.clone()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        PlannerCode {
+            set: self.set.clone(),
+            champions,
+        }
+        .encode()
+    }
+
+    /// Planner codes carry no positions, so units fill the board from the first hex.
+    pub fn decode_comp(&self, code: &str) -> Result<Comp, CodeError> {
+        self.check_available()?;
+        let planner_code = PlannerCode::decode(code)?;
+        if planner_code.set != self.set {
+            return Err(CodeError::WrongSet {
+                expected: self.set.clone(),
+                found: planner_code.set,
+            });
+        }
+        let units = planner_code
+            .champions
+            .into_iter()
+            .zip(BoardPosition::all())
+            .map(|(code, position)| {
+                self.champion_of(code)
+                    .map(|api_name| CompUnit {
+                        position: Some(position),
+                        ..CompUnit::new(api_name.to_string())
+                    })
+                    .ok_or(CodeError::UnknownCode(code))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Comp {
+            name: String::from("Imported comp"),
+            units,
+            notes: String::new(),
+        })
+    }
+}
+
+/// The app's share code: the whole comp with stars, items and positions.
+pub fn encode_share_code(comp: &Comp) -> String {
+    let json = serde_json::to_vec(comp).unwrap();
+    format!("{SHARE_CODE_PREFIX}{}", URL_SAFE_NO_PAD.encode(json))
+}
+
+pub fn decode_share_code(code: &str) -> Result<Comp, CodeError> {
+    let encoded = code
+        .trim()
+        .strip_prefix(SHARE_CODE_PREFIX)
+        .ok_or_else(|| CodeError::Malformed(String::from("not a share code")))?;
+    let json = URL_SAFE_NO_PAD
+        .decode(encoded)
+        .map_err(|e| CodeError::Malformed(e.to_string()))?;
+    let comp: Comp =
+        serde_json::from_slice(&json).map_err(|e| CodeError::Malformed(e.to_string()))?;
+    check_units(&comp)?;
+    Ok(comp)
+}
+
+/// Share codes are pasted text, so their units are checked like the comp editor would place them.
+fn check_units(comp: &Comp) -> Result<(), CodeError> {
+    let mut taken = BTreeMap::new();
+    for unit in &comp.units {
+        if !(1..=3).contains(&unit.star) {
+            return Err(CodeError::Malformed(format!(
+                "{} has star level {}, it has to be between 1 and 3",
+                unit.champion, unit.star
+            )));
+        }
+        let Some(position) = unit.position else {
+            continue;
+        };
+        if position.row >= BOARD_ROWS || position.col >= BOARD_COLS {
+            return Err(CodeError::Malformed(format!(
+                "{} is off the board",
+                unit.champion
+            )));
+        }
+        if let Some(other) = taken.insert(position, &unit.champion) {
+            return Err(CodeError::Malformed(format!(
+                "{other} and {} are on the same hex",
+                unit.champion
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Decodes either a share code or a team planner code.
+pub fn decode_any(code: &str, index: &PlannerIndex) -> Result<Comp, CodeError> {
+    if code.trim().starts_with(SHARE_CODE_PREFIX) {
+        decode_share_code(code)
+    } else {
+        index.decode_comp(code)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Shaped like CommunityDragon's `tftchampions-teamplanner.json`.
+    const TEAM_PLANNER: &str = include_str!("../tests/fixtures/teamplanner.json");
+
+    /// Garen, Ahri and Ryze in the version 2 format: three hex digits per slot.
+    const V2_CODE: &str = "0200101a12c000000000000000000000TFTSet9";
+
+    fn index() -> PlannerIndex {
+        let json = serde_json::from_str(TEAM_PLANNER).unwrap();
+        PlannerIndex::from_json(&json, "TFTSet9").unwrap()
+    }
+
+    fn comp(champions: &[&str]) -> Comp {
+        Comp {
+            units: champions
+                .iter()
+                .map(|champion| CompUnit::new(champion.to_string()))
+                .collect(),
+            ..Comp::new(String::from("Test comp"))
+        }
+    }
+
+    fn champions(comp: &Comp) -> Vec<&str> {
+        comp.units
+            .iter()
+            .map(|unit| unit.champion.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn planner_codes_round_trip() {
+        let planner_code = PlannerCode::decode(V2_CODE).unwrap();
+        assert_eq!(planner_code.set, "TFTSet9");
+        assert_eq!(planner_code.champions, [1, 26, 300]);
+        assert_eq!(planner_code.encode().unwrap(), V2_CODE);
+
+        // version 1 codes have two digits per slot and are re-encoded as version 2
+        let v1 = PlannerCode::decode("01011a0000000000000000TFTSet9").unwrap();
+        assert_eq!(v1.champions, [1, 26]);
+    }
+
+    #[test]
+    fn comps_round_trip_through_the_official_index() {
+        let index = index();
+        assert_eq!(index.code_of("TFT9_Ryze"), Some(300));
+        assert_eq!(index.code_of("TFT8_Annie"), None);
+
+        let comp = comp(&["TFT9_Garen", "TFT9_Ahri", "TFT9_Ryze"]);
+        assert_eq!(index.encode_comp(&comp).unwrap(), V2_CODE);
+        let decoded = index.decode_comp(V2_CODE).unwrap();
+        assert_eq!(
+            champions(&decoded),
+            ["TFT9_Garen", "TFT9_Ahri", "TFT9_Ryze"]
+        );
+        assert!(decoded.units.iter().all(|unit| unit.position.is_some()));
+    }
+
+    #[test]
+    fn codes_need_the_official_index() {
+        let index = PlannerIndex::unavailable("TFTSet9");
+        let no_planner = CodeError::NoPlanner(String::from("TFTSet9"));
+        assert_eq!(
+            index.encode_comp(&comp(&["TFT9_Ahri"])),
+            Err(no_planner.clone())
+        );
+        assert_eq!(index.decode_comp(V2_CODE), Err(no_planner.clone()));
+        assert_eq!(decode_any(V2_CODE, &index), Err(no_planner));
+    }
+
+    #[test]
+    fn bad_planner_codes_are_errors() {
+        let index = index();
+        assert_eq!(
+            index.encode_comp(&comp(&["TFT9_Garen", "TFT8_Annie"])),
+            Err(CodeError::UnknownChampion(String::from("TFT8_Annie")))
+        );
+        assert_eq!(
+            index.decode_comp("02001000000000000000000000000000TFTSet8"),
+            Err(CodeError::WrongSet {
+                expected: String::from("TFTSet9"),
+                found: String::from("TFTSet8"),
+            })
+        );
+        assert_eq!(
+            index.decode_comp("02002000000000000000000000000000TFTSet9"),
+            Err(CodeError::UnknownCode(2))
+        );
+        let eleven = comp(&["TFT9_Garen"; PLANNER_SLOTS + 1]);
+        assert_eq!(
+            index.encode_comp(&eleven),
+            Err(CodeError::TooManyUnits(PLANNER_SLOTS + 1))
+        );
+        for malformed in [
+            "",
+            "03001TFTSet9",
+            "02001",
+            "02001000000000000000000000000000",
+        ] {
+            assert!(
+                matches!(PlannerCode::decode(malformed), Err(CodeError::Malformed(_))),
+                "{malformed}"
+            );
+        }
+    }
+
+    #[test]
+    fn share_codes_keep_the_whole_comp() {
+        let mut comp = comp(&["TFT9_Ahri", "TFT8_Annie"]);
+        comp.notes = String::from("level 7 reroll");
+        comp.units[0].star = 3;
+        comp.units[0].items = vec![String::from("TFT_Item_BlueBuff")];
+        comp.units[1].position = Some(BoardPosition { row: 3, col: 6 });
+
+        let code = encode_share_code(&comp);
+        assert!(code.starts_with(SHARE_CODE_PREFIX));
+        assert_eq!(decode_share_code(&code), Ok(comp.clone()));
+        // share codes don't need the planner
+        assert_eq!(
+            decode_any(&code, &PlannerIndex::unavailable("TFTSet9")),
+            Ok(comp)
+        );
+        assert!(matches!(
+            decode_share_code("TFTN1.not base64!"),
+            Err(CodeError::Malformed(_))
+        ));
+    }
+
+    #[test]
+    fn share_codes_with_impossible_units_are_errors() {
+        let mut four_star = comp(&["TFT9_Ahri"]);
+        four_star.units[0].star = 4;
+        let mut off_board = comp(&["TFT9_Ahri"]);
+        off_board.units[0].position = Some(BoardPosition { row: 4, col: 0 });
+        let mut stacked = comp(&["TFT9_Ahri", "TFT9_Garen"]);
+        for unit in stacked.units.iter_mut() {
+            unit.position = Some(BoardPosition { row: 1, col: 2 });
+        }
+        for comp in [four_star, off_board, stacked] {
+            assert!(
+                matches!(
+                    decode_share_code(&encode_share_code(&comp)),
+                    Err(CodeError::Malformed(_))
+                ),
+                "{comp:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn pasted_text_is_not_sliced_apart() {
+        let index = index();
+        let accented = format!("02{}TFTSet9", "é".repeat(15));
+        let plus = format!("02+01{}TFTSet9", "0".repeat(27));
+        for pasted in [accented.as_str(), plus.as_str(), "02ééTFTSet9", "0é"] {
+            assert!(
+                matches!(index.decode_comp(pasted), Err(CodeError::Malformed(_))),
+                "{pasted}"
+            );
+        }
+    }
+}
@@ -0,0 +1,787 @@
+use std::fmt::Display;
+
+use serde::Serialize;
+
+use crate::{
+    calc::{ability_damage, mitigation, Bonus, UnitStats},
+    comp::{BoardPosition, BOARD_COLS, BOARD_ROWS},
+    tft_data::{Champion, Item},
+};
+
+/// Length of a simulation step.
+pub const TICK_SECONDS: f64 = 0.05;
+/// Time a unit takes to walk to the next hex.
+const MOVE_SECONDS: f64 = 0.4;
+const MANA_PER_ATTACK: f64 = 10.0;
+/// Mana gained from damage taken, as shares of the damage before and after mitigation.
+const MANA_FROM_DAMAGE_BEFORE_MITIGATION: f64 = 0.01;
+const MANA_FROM_DAMAGE_AFTER_MITIGATION: f64 = 0.03;
+/// Item api names whose holders can crit with abilities.
+const ABILITY_CRIT_ITEMS: &[&str] = &["JeweledGauntlet"];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize)]
+pub enum Team {
+    Blue,
+    Red,
+}
+
+impl Display for Team {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Team::Blue => write!(f, "Blue"),
+            Team::Red => write!(f, "Red"),
+        }
+    }
+}
+
+/// A unit placed for a fight.
+#[derive(Debug, Clone)]
+pub struct UnitSpec {
+    pub champion: Champion,
+    pub star: u8,
+    pub items: Vec<Item>,
+    /// Hex on its team's half, row 0 being the front row.
+    pub position: BoardPosition,
+    pub traits: Bonus,
+}
+
+impl UnitSpec {
+    pub fn new(champion: &Champion, star: u8, items: &[Item], position: BoardPosition) -> Self {
+        Self {
+            champion: champion.clone(),
+            star,
+            items: items.to_vec(),
+            position,
+            traits: Bonus::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SimConfig {
+    /// Seed of the crit rolls. The same seed and units always give the same fight.
+    pub seed: u64,
+    /// The fight is a draw if both teams are still standing after this long.
+    pub max_seconds: f64,
+}
+
+impl Default for SimConfig {
+    fn default() -> Self {
+        Self {
+            seed: 1,
+            max_seconds: 30.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub enum Event {
+    /// Row and column on the full board, blue's half being rows 4 to 7.
+    Move {
+        row: u8,
+        col: u8,
+    },
+    Attack {
+        target: usize,
+        damage: f64,
+        crit: bool,
+    },
+    Cast {
+        target: usize,
+        damage: f64,
+        crit: bool,
+    },
+    Death,
+}
+
+/// Something a unit did, `unit` being its index in `SimResult::units`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct LogEntry {
+    pub seconds: f64,
+    pub unit: usize,
+    pub event: Event,
+}
+
+/// What one unit did over the fight.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct UnitReport {
+    pub name: String,
+    pub team: Option<Team>,
+    /// Damage dealt after mitigation.
+    pub attack_damage: f64,
+    pub ability_damage: f64,
+    pub damage_taken: f64,
+    pub healing: f64,
+    pub attacks: u32,
+    pub casts: u32,
+    /// Health left, 0 if the unit died.
+    pub health: f64,
+}
+
+impl UnitReport {
+    pub fn total_damage(&self) -> f64 {
+        self.attack_damage + self.ability_damage
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct SimResult {
+    /// `None` for a draw.
+    pub winner: Option<Team>,
+    pub seconds: f64,
+    pub log: Vec<LogEntry>,
+    /// Blue units first, in the order they were given.
+    pub units: Vec<UnitReport>,
+}
+
+impl SimResult {
+    /// The log as text, one line per entry.
+    pub fn log_lines(&self) -> Vec<String> {
+        let name = |unit: usize| {
+            format!(
+                "{} {}",
+                self.units[unit].team.unwrap(),
+                self.units[unit].name
+            )
+        };
+        self.log
+            .iter()
+            .map(|entry| {
+                let what = match entry.event {
+                    Event::Move { row, col } => format!("moves to {row},{col}"),
+                    Event::Attack {
+                        target,
+                        damage,
+                        crit,
+                    } => format!(
+                        "attacks {} for {:.0}{}",
+                        name(target),
+                        damage,
+                        if crit { " (crit)" } else { "" }
+                    ),
+                    Event::Cast {
+                        target,
+                        damage,
+                        crit,
+                    } => format!(
+                        "casts on {} for {:.0}{}",
+                        name(target),
+                        damage,
+                        if crit { " (crit)" } else { "" }
+                    ),
+                    Event::Death => String::from("dies"),
+                };
+                format!("{:>5.2}s {} {what}", entry.seconds, name(entry.unit))
+            })
+            .collect()
+    }
+}
+
+/// splitmix64, so fights don't depend on a random number crate.
+struct Rng(u64);
+
+impl Rng {
+    /// A number in `0..1`.
+    fn next(&mut self) -> f64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+        (z >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// A hex of the full board in offset coordinates, odd rows shifted half a hex right.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Hex {
+    row: i32,
+    col: i32,
+}
+
+impl Hex {
+    /// Blue fills the bottom half facing up, red the top half facing down.
+    fn from_position(position: BoardPosition, team: Team) -> Self {
+        let (row, col) = (position.row as i32, position.col as i32);
+        match team {
+            Team::Blue => Hex {
+                row: BOARD_ROWS as i32 + row,
+                col,
+            },
+            Team::Red => Hex {
+                row: BOARD_ROWS as i32 - 1 - row,
+                col: BOARD_COLS as i32 - 1 - col,
+            },
+        }
+    }
+
+    fn distance(&self, other: &Hex) -> i32 {
+        let cube = |hex: &Hex| {
+            let x = hex.col - (hex.row - (hex.row & 1)) / 2;
+            (x, hex.row, -x - hex.row)
+        };
+        let (a, b) = (cube(self), cube(other));
+        (a.0 - b.0)
+            .abs()
+            .max((a.1 - b.1).abs())
+            .max((a.2 - b.2).abs())
+    }
+
+    fn neighbours(&self) -> impl Iterator<Item = Hex> + '_ {
+        (-1..=1)
+            .flat_map(move |dr| {
+                (-1..=1).map(move |dc| Hex {
+                    row: self.row + dr,
+                    col: self.col + dc,
+                })
+            })
+            .filter(|hex| {
+                (0..BOARD_ROWS as i32 * 2).contains(&hex.row)
+                    && (0..BOARD_COLS as i32).contains(&hex.col)
+                    && hex.distance(self) == 1
+            })
+    }
+}
+
+struct Fighter {
+    team: Team,
+    hex: Hex,
+    stats: UnitStats,
+    health: f64,
+    mana: f64,
+    max_mana: f64,
+    range: i32,
+    /// Largest damage value of the ability, before ability power.
+    ability_damage: f64,
+    abilities_crit: bool,
+    omnivamp: f64,
+    mana_per_attack: f64,
+    /// Seconds until the next attack.
+    attack_cooldown: f64,
+    /// Seconds until the next step.
+    move_cooldown: f64,
+    target: Option<usize>,
+}
+
+impl Fighter {
+    fn new(spec: &UnitSpec, team: Team) -> Self {
+        let stats = UnitStats::new(&spec.champion, spec.star, &spec.items, &spec.traits);
+        let effect =
+            |name: &str| -> f64 { spec.items.iter().filter_map(|item| item.effect(name)).sum() };
+        let base = spec.champion.stats.at_star(spec.star);
+        Self {
+            team,
+            hex: Hex::from_position(spec.position, team),
+            stats,
+            health: stats.health,
+            mana: base.initial_mana + effect("Mana"),
+            max_mana: base.mana,
+            range: (base.range.round() as i32).max(1),
+            // ability power is applied on cast, so pass the base value here
+            ability_damage: ability_damage(&spec.champion, spec.star, 100.0)
+                .into_iter()
+                .map(|(_, damage)| damage)
+                .fold(0.0, f64::max),
+            abilities_crit: spec.items.iter().any(|item| {
+                ABILITY_CRIT_ITEMS
+                    .iter()
+                    .any(|name| item.api_name.contains(name))
+            }),
+            omnivamp: (effect("Omnivamp") + effect("LifeSteal")) / 100.0,
+            mana_per_attack: MANA_PER_ATTACK + effect("FlatManaRestore"),
+            attack_cooldown: 0.0,
+            move_cooldown: 0.0,
+            target: None,
+        }
+    }
+
+    fn alive(&self) -> bool {
+        self.health > 0.0
+    }
+}
+
+/// Runs a fight between two teams.
+///
+/// Every tick each living unit, blue ones first, picks the closest enemy, walks towards it
+/// until it is in range, then attacks whenever its attack speed allows. Attacks give mana and
+/// a full mana bar casts the ability on the target instead of the next attack.
+pub fn simulate(blue: &[UnitSpec], red: &[UnitSpec], config: &SimConfig) -> SimResult {
+    let mut rng = Rng(config.seed);
+    let mut fighters: Vec<Fighter> = blue
+        .iter()
+        .map(|spec| Fighter::new(spec, Team::Blue))
+        .chain(red.iter().map(|spec| Fighter::new(spec, Team::Red)))
+        .collect();
+    let mut result = SimResult {
+        units: blue
+            .iter()
+            .map(|spec| (spec, Team::Blue))
+            .chain(red.iter().map(|spec| (spec, Team::Red)))
+            .map(|(spec, team)| UnitReport {
+                name: spec.champion.name.clone(),
+                team: Some(team),
+                ..UnitReport::default()
+            })
+            .collect(),
+        ..SimResult::default()
+    };
+
+    let mut ticks = 0u32;
+    loop {
+        let seconds = ticks as f64 * TICK_SECONDS;
+        let standing = |team: Team, fighters: &[Fighter]| {
+            fighters
+                .iter()
+                .any(|fighter| fighter.team == team && fighter.alive())
+        };
+        match (
+            standing(Team::Blue, &fighters),
+            standing(Team::Red, &fighters),
+        ) {
+            (true, true) if seconds < config.max_seconds => {}
+            (blue_standing, red_standing) => {
+                result.winner = match (blue_standing, red_standing) {
+                    (true, false) => Some(Team::Blue),
+                    (false, true) => Some(Team::Red),
+                    _ => None,
+                };
+                result.seconds = seconds;
+                break;
+            }
+        }
+
+        for unit in 0..fighters.len() {
+            if !fighters[unit].alive() {
+                continue;
+            }
+            step(unit, seconds, &mut fighters, &mut rng, &mut result);
+        }
+        ticks += 1;
+    }
+
+    for (report, fighter) in result.units.iter_mut().zip(fighters.iter()) {
+        report.health = fighter.health.max(0.0);
+    }
+    result
+}
+
+/// One tick of one unit.
+fn step(
+    unit: usize,
+    seconds: f64,
+    fighters: &mut [Fighter],
+    rng: &mut Rng,
+    result: &mut SimResult,
+) {
+    let fighter = &fighters[unit];
+    let target_alive = fighter
+        .target
+        .is_some_and(|target| fighters[target].alive());
+    if !target_alive {
+        let target = fighters
+            .iter()
+            .enumerate()
+            .filter(|(_, other)| other.team != fighter.team && other.alive())
+            .min_by_key(|(_, other)| other.hex.distance(&fighter.hex))
+            .map(|(index, _)| index);
+        fighters[unit].target = target;
+    }
+    let Some(target) = fighters[unit].target else {
+        return;
+    };
+
+    let fighter = &mut fighters[unit];
+    fighter.attack_cooldown -= TICK_SECONDS;
+    fighter.move_cooldown -= TICK_SECONDS;
+    let target_hex = fighters[target].hex;
+    let fighter = &fighters[unit];
+    if fighter.hex.distance(&target_hex) > fighter.range {
+        if fighter.move_cooldown > 0.0 {
+            return;
+        }
+        let next = fighter
+            .hex
+            .neighbours()
+            .filter(|hex| {
+                !fighters
+                    .iter()
+                    .any(|other| other.alive() && other.hex == *hex)
+            })
+            .min_by_key(|hex| hex.distance(&target_hex));
+        if let Some(next) = next {
+            let fighter = &mut fighters[unit];
+            fighter.hex = next;
+            fighter.move_cooldown = MOVE_SECONDS;
+            result.log.push(LogEntry {
+                seconds,
+                unit,
+                event: Event::Move {
+                    row: next.row as u8,
+                    col: next.col as u8,
+                },
+            });
+        }
+        return;
+    }
+    if fighter.attack_cooldown > 0.0 {
+        return;
+    }
+
+    let fighter = &mut fighters[unit];
+    fighter.attack_cooldown = 1.0 / fighter.stats.attack_speed.max(0.01);
+    let stats = fighter.stats;
+    let casting = fighter.max_mana > 0.0 && fighter.mana >= fighter.max_mana;
+    let crit = rng.next() < stats.crit_chance && (!casting || fighter.abilities_crit);
+    let crit_factor = if crit { stats.crit_multiplier } else { 1.0 };
+    let (raw, resist) = if casting {
+        fighter.mana = 0.0;
+        (
+            fighter.ability_damage * stats.ability_power / 100.0 * crit_factor,
+            fighters[target].stats.magic_resist,
+        )
+    } else {
+        fighter.mana += fighter.mana_per_attack;
+        (
+            stats.attack_damage * crit_factor,
+            fighters[target].stats.armor,
+        )
+    };
+    let damage = raw * mitigation(resist);
+
+    let report = &mut result.units[unit];
+    if casting {
+        report.casts += 1;
+        report.ability_damage += damage;
+    } else {
+        report.attacks += 1;
+        report.attack_damage += damage;
+    }
+    result.log.push(LogEntry {
+        seconds,
+        unit,
+        event: if casting {
+            Event::Cast {
+                target,
+                damage,
+                crit,
+            }
+        } else {
+            Event::Attack {
+                target,
+                damage,
+                crit,
+            }
+        },
+    });
+
+    let fighter = &mut fighters[unit];
+    let heal = (damage * fighter.omnivamp).min(fighter.stats.health - fighter.health);
+    if heal > 0.0 {
+        fighter.health += heal;
+        result.units[unit].healing += heal;
+    }
+
+    let defender = &mut fighters[target];
+    defender.health -= damage;
+    defender.mana +=
+        raw * MANA_FROM_DAMAGE_BEFORE_MITIGATION + damage * MANA_FROM_DAMAGE_AFTER_MITIGATION;
+    result.units[target].damage_taken += damage;
+    if !defender.alive() {
+        result.log.push(LogEntry {
+            seconds,
+            unit: target,
+            event: Event::Death,
+        });
+    }
+}
+
+/// Fights the same champion with each item set against the same opponent, for comparing the
+/// sets. The champion stands in the middle of blue's front row, the opponent across from it.
+pub fn compare_items(
+    champion: &Champion,
+    star: u8,
+    item_sets: &[Vec<Item>],
+    opponent: &UnitSpec,
+    config: &SimConfig,
+) -> Vec<SimResult> {
+    let position = BoardPosition {
+        row: 0,
+        col: BOARD_COLS / 2,
+    };
+    item_sets
+        .iter()
+        .map(|items| {
+            simulate(
+                &[UnitSpec::new(champion, star, items, position)],
+                std::slice::from_ref(opponent),
+                config,
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::tft_data::{Ability, Stats, Variable};
+
+    /// Hits for 100 once a second from the next hex, with a 300 damage ability once it has 10
+    /// mana.
+    fn hitter() -> Champion {
+        Champion {
+            api_name: String::from("TFT9_Hitter"),
+            name: String::from("Hitter"),
+            ability: Ability {
+                variables: vec![Variable {
+                    name: String::from("Damage"),
+                    value: vec![0.0, 300.0, 450.0, 700.0],
+                }],
+                ..Ability::default()
+            },
+            stats: Stats {
+                hp: Some(1000.0),
+                damage: Some(100.0),
+                attack_speed: Some(1.0),
+                crit_multiplier: 1.4,
+                mana: 10.0,
+                range: 1.0,
+                ..Stats::default()
+            },
+            ..Champion::default()
+        }
+    }
+
+    /// Never moves, barely attacks and takes a long time to kill.
+    fn dummy(hp: f64, armor: f64, magic_resist: f64) -> Champion {
+        Champion {
+            api_name: String::from("TFT9_Dummy"),
+            name: String::from("Dummy"),
+            stats: Stats {
+                hp: Some(hp),
+                armor: Some(armor),
+                magic_resist: Some(magic_resist),
+                range: 8.0,
+                ..Stats::default()
+            },
+            ..Champion::default()
+        }
+    }
+
+    fn position(row: u8, col: u8) -> BoardPosition {
+        BoardPosition { row, col }
+    }
+
+    /// `champion` in the middle of blue's front row against `opponent` right across from it.
+    fn fight(champion: Champion, opponent: Champion, config: &SimConfig) -> SimResult {
+        simulate(
+            &[UnitSpec::new(&champion, 1, &[], position(0, 3))],
+            &[UnitSpec::new(&opponent, 1, &[], position(0, 3))],
+            config,
+        )
+    }
+
+    /// Time and damage of everything `unit` hit with.
+    fn hits(result: &SimResult, unit: usize) -> Vec<(f64, f64, bool)> {
+        result
+            .log
+            .iter()
+            .filter(|entry| entry.unit == unit)
+            .filter_map(|entry| match entry.event {
+                Event::Attack { damage, .. } => Some((entry.seconds, damage, false)),
+                Event::Cast { damage, .. } => Some((entry.seconds, damage, true)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn the_same_seed_gives_the_same_fight() {
+        let critter = Champion {
+            stats: Stats {
+                crit_chance: Some(0.5),
+                ..hitter().stats
+            },
+            ..hitter()
+        };
+        let config = SimConfig::default();
+        let first = fight(critter.clone(), dummy(5000.0, 0.0, 0.0), &config);
+        assert_eq!(
+            first,
+            fight(critter.clone(), dummy(5000.0, 0.0, 0.0), &config)
+        );
+        assert!(first
+            .log
+            .iter()
+            .any(|entry| matches!(entry.event, Event::Attack { crit: true, .. })));
+
+        let reseeded = SimConfig { seed: 2, ..config };
+        assert_ne!(
+            first.log,
+            fight(critter, dummy(5000.0, 0.0, 0.0), &reseeded).log
+        );
+    }
+
+    #[test]
+    fn resistances_mitigate_damage() {
+        let result = fight(
+            hitter(),
+            dummy(100_000.0, 100.0, 50.0),
+            &SimConfig::default(),
+        );
+        let hits = hits(&result, 0);
+        // attacks are reduced by armor, 100 / (100 + 100)
+        assert_eq!(hits[0], (0.0, 50.0, false));
+        // the first attack fills the mana bar, the ability is reduced by magic resist
+        assert!(hits[1].2);
+        assert_eq!(hits[1].1, 200.0);
+        assert_eq!(
+            result.units[1].damage_taken,
+            hits.iter().map(|hit| hit.1).sum::<f64>()
+        );
+
+        // negative resistances don't add damage
+        let shredded = fight(
+            hitter(),
+            dummy(100_000.0, -50.0, 0.0),
+            &SimConfig::default(),
+        );
+        assert_eq!(
+            shredded.log[0].event,
+            Event::Attack {
+                target: 1,
+                damage: 100.0,
+                crit: false,
+            }
+        );
+    }
+
+    #[test]
+    fn attacks_follow_attack_speed() {
+        let slow = Champion {
+            stats: Stats {
+                attack_speed: Some(0.5),
+                mana: 0.0,
+                ..hitter().stats
+            },
+            ..hitter()
+        };
+        let result = fight(slow, dummy(100_000.0, 0.0, 0.0), &SimConfig::default());
+        let times: Vec<f64> = hits(&result, 0).iter().map(|hit| hit.0).collect();
+        assert_eq!(times.len(), 15, "{times:?}");
+        for gap in times.windows(2).map(|pair| pair[1] - pair[0]) {
+            // an attack can slip to the next tick, never earlier
+            assert!(
+                (2.0 - 1e-9..2.0 + TICK_SECONDS + 1e-9).contains(&gap),
+                "{times:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn a_full_mana_bar_casts_instead_of_attacking() {
+        let caster = Champion {
+            stats: Stats {
+                mana: 30.0,
+                ..hitter().stats
+            },
+            ..hitter()
+        };
+        let result = fight(caster, dummy(100_000.0, 0.0, 0.0), &SimConfig::default());
+        let casts: Vec<bool> = hits(&result, 0).iter().take(8).map(|hit| hit.2).collect();
+        assert_eq!(
+            casts,
+            [false, false, false, true, false, false, false, true]
+        );
+        let report = &result.units[0];
+        assert_eq!(report.casts, report.attacks / 3);
+        assert_eq!(report.ability_damage, report.casts as f64 * 300.0);
+    }
+
+    #[test]
+    fn units_walk_into_range() {
+        let result = simulate(
+            &[UnitSpec::new(&hitter(), 1, &[], position(0, 3))],
+            &[UnitSpec::new(
+                &dummy(100_000.0, 0.0, 0.0),
+                1,
+                &[],
+                position(3, 3),
+            )],
+            &SimConfig::default(),
+        );
+        let start = Hex::from_position(position(0, 3), Team::Blue);
+        let target = Hex::from_position(position(3, 3), Team::Red);
+        assert_eq!(start.distance(&target), 4);
+
+        let moves: Vec<(f64, Hex)> = result
+            .log
+            .iter()
+            .filter_map(|entry| match entry.event {
+                Event::Move { row, col } if entry.unit == 0 => Some((
+                    entry.seconds,
+                    Hex {
+                        row: row as i32,
+                        col: col as i32,
+                    },
+                )),
+                _ => None,
+            })
+            .collect();
+        // one hex closer with every step, until next to the target
+        assert_eq!(moves.len(), 3);
+        let mut from = start;
+        for (steps, (_, hex)) in moves.iter().enumerate() {
+            assert_eq!(from.distance(hex), 1);
+            assert_eq!(hex.distance(&target), 3 - steps as i32);
+            from = *hex;
+        }
+        // attacking starts after the last step
+        assert!(hits(&result, 0)[0].0 > moves[2].0);
+    }
+
+    #[test]
+    fn hexes_have_six_neighbours_inside_the_board() {
+        let middle = Hex { row: 5, col: 3 };
+        assert_eq!(middle.neighbours().count(), 6);
+        assert!(middle.neighbours().all(|hex| hex.distance(&middle) == 1));
+        let corner = Hex { row: 0, col: 0 };
+        assert_eq!(corner.neighbours().count(), 2);
+        // odd rows are shifted right, so their neighbours lean right
+        let odd = Hex { row: 1, col: 0 };
+        assert!(odd.neighbours().any(|hex| hex == Hex { row: 0, col: 1 }));
+        assert!(odd.neighbours().all(|hex| hex.col >= 0));
+        assert_eq!(Hex { row: 0, col: 0 }.distance(&Hex { row: 7, col: 6 }), 10);
+    }
+
+    #[test]
+    fn item_sets_are_compared_against_the_same_opponent() {
+        let mut sword = Item::default();
+        sword.api_name = String::from("TFT_Item_BFSword");
+        sword.effects = json!({"AD": 50.0});
+        let opponent = UnitSpec::new(&dummy(1000.0, 0.0, 0.0), 1, &[], position(0, 3));
+        let no_mana = Champion {
+            stats: Stats {
+                mana: 0.0,
+                ..hitter().stats
+            },
+            ..hitter()
+        };
+        let results = compare_items(
+            &no_mana,
+            1,
+            &[vec![], vec![sword]],
+            &opponent,
+            &SimConfig::default(),
+        );
+        assert_eq!(results.len(), 2);
+        assert!(results
+            .iter()
+            .all(|result| result.winner == Some(Team::Blue)));
+        // 10 attacks of 100 against 7 of 150
+        assert_eq!(results[0].units[0].attacks, 10);
+        assert_eq!(results[1].units[0].attacks, 7);
+        assert!(results[1].seconds < results[0].seconds);
+    }
+}
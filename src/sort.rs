@@ -0,0 +1,252 @@
+use std::{cmp::Ordering, collections::BTreeMap, fmt::Display};
+
+use serde::{Deserialize, Serialize};
+
+use crate::tft_data::*;
+
+/// How the champion grid is laid out.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub enum SortChampMethod {
+    /// One list, ordered by name unless secondary keys come first.
+    #[default]
+    Alphabetical,
+    /// A section per cost.
+    Cost,
+    /// A section per trait. Champions show up in every section of a trait they have.
+    Trait,
+}
+
+impl SortChampMethod {
+    pub const ALL: [SortChampMethod; 3] = [
+        SortChampMethod::Alphabetical,
+        SortChampMethod::Cost,
+        SortChampMethod::Trait,
+    ];
+}
+
+impl Display for SortChampMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SortChampMethod::Alphabetical => write!(f, "Alphabetical"),
+            SortChampMethod::Cost => write!(f, "By Cost"),
+            SortChampMethod::Trait => write!(f, "By Trait"),
+        }
+    }
+}
+
+/// Orders champions inside a section.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum SortKey {
+    Name,
+    Cost,
+    Health,
+    Range,
+}
+
+impl SortKey {
+    pub const ALL: [SortKey; 4] = [
+        SortKey::Name,
+        SortKey::Cost,
+        SortKey::Health,
+        SortKey::Range,
+    ];
+
+    pub fn compare(&self, a: &Champion, b: &Champion) -> Ordering {
+        match self {
+            SortKey::Name => a.name.cmp(&b.name),
+            SortKey::Cost => a.cost.cmp(&b.cost),
+            SortKey::Health => a
+                .stats
+                .hp
+                .unwrap_or_default()
+                .total_cmp(&b.stats.hp.unwrap_or_default()),
+            SortKey::Range => a.stats.range.total_cmp(&b.stats.range),
+        }
+    }
+}
+
+impl Display for SortKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SortKey::Name => write!(f, "Name"),
+            SortKey::Cost => write!(f, "Cost"),
+            SortKey::Health => write!(f, "Health"),
+            SortKey::Range => write!(f, "Range"),
+        }
+    }
+}
+
+/// A section of the champion grid. Ungrouped sorts produce a single section without a header.
+#[derive(Debug, Clone)]
+pub struct ChampionGroup<T> {
+    pub header: Option<GroupHeader>,
+    pub entries: Vec<T>,
+}
+
+/// Title of a section of the champion grid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GroupHeader {
+    Cost(u8),
+    /// Trait name.
+    Trait(String),
+}
+
+/// Splits `entries` into sections according to `method` and orders every section by the
+/// `secondary` keys, falling back to the champion name.
+///
+/// `champion` picks the champion out of an entry so any per-champion state can be grouped.
+pub fn group_champions<T: Clone>(
+    mut entries: Vec<T>,
+    champion: impl Fn(&T) -> &Champion,
+    method: SortChampMethod,
+    secondary: &[SortKey],
+) -> Vec<ChampionGroup<T>> {
+    let keys: Vec<SortKey> = secondary
+        .iter()
+        .copied()
+        .chain(std::iter::once(SortKey::Name))
+        .collect();
+    entries.sort_by(|a, b| {
+        keys.iter()
+            .map(|key| key.compare(champion(a), champion(b)))
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or(Ordering::Equal)
+    });
+
+    // entries are already sorted, so pushing them in order keeps every section sorted
+    match method {
+        SortChampMethod::Alphabetical => vec![ChampionGroup {
+            header: None,
+            entries,
+        }],
+        SortChampMethod::Cost => {
+            let mut sections: BTreeMap<u8, Vec<T>> = BTreeMap::new();
+            for entry in entries {
+                sections
+                    .entry(champion(&entry).cost)
+                    .or_default()
+                    .push(entry);
+            }
+            sections
+                .into_iter()
+                .map(|(cost, entries)| ChampionGroup {
+                    header: Some(GroupHeader::Cost(cost)),
+                    entries,
+                })
+                .collect()
+        }
+        SortChampMethod::Trait => {
+            let mut sections: BTreeMap<String, Vec<T>> = BTreeMap::new();
+            for entry in entries {
+                for name in champion(&entry).traits.iter() {
+                    sections
+                        .entry(name.clone())
+                        .or_default()
+                        .push(entry.clone());
+                }
+            }
+            sections
+                .into_iter()
+                .map(|(name, entries)| ChampionGroup {
+                    header: Some(GroupHeader::Trait(name)),
+                    entries,
+                })
+                .collect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Name, cost, health, range and traits of each champion.
+    fn champions() -> Vec<Champion> {
+        [
+            ("Garen", 1, 650.0, 1.0, &["Demacia", "Juggernaut"][..]),
+            ("Ahri", 3, 650.0, 4.0, &["Sorcerer"][..]),
+            ("Sion", 5, 1200.0, 1.0, &["Juggernaut"][..]),
+            ("Jinx", 1, 500.0, 4.0, &[][..]),
+        ]
+        .into_iter()
+        .map(|(name, cost, hp, range, traits)| Champion {
+            name: name.to_string(),
+            cost,
+            stats: Stats {
+                hp: Some(hp),
+                range,
+                ..Stats::default()
+            },
+            traits: traits.iter().map(|t| t.to_string()).collect(),
+            ..Champion::default()
+        })
+        .collect()
+    }
+
+    /// Section headers and the names in each section.
+    fn layout(
+        method: SortChampMethod,
+        secondary: &[SortKey],
+    ) -> Vec<(Option<GroupHeader>, Vec<String>)> {
+        group_champions(champions(), |champ| champ, method, secondary)
+            .into_iter()
+            .map(|group| {
+                let names = group.entries.into_iter().map(|champ| champ.name).collect();
+                (group.header, names)
+            })
+            .collect()
+    }
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn alphabetical_is_one_section_by_name() {
+        assert_eq!(
+            layout(SortChampMethod::Alphabetical, &[]),
+            [(None, names(&["Ahri", "Garen", "Jinx", "Sion"]))]
+        );
+    }
+
+    #[test]
+    fn secondary_keys_come_before_the_name() {
+        assert_eq!(
+            layout(SortChampMethod::Alphabetical, &[SortKey::Cost]),
+            [(None, names(&["Garen", "Jinx", "Ahri", "Sion"]))]
+        );
+        // ties on the first key fall back to the next one, then to the name
+        assert_eq!(
+            layout(
+                SortChampMethod::Alphabetical,
+                &[SortKey::Range, SortKey::Health]
+            ),
+            [(None, names(&["Garen", "Sion", "Jinx", "Ahri"]))]
+        );
+    }
+
+    #[test]
+    fn cost_sections_are_ordered_by_cost() {
+        assert_eq!(
+            layout(SortChampMethod::Cost, &[SortKey::Health]),
+            [
+                (Some(GroupHeader::Cost(1)), names(&["Jinx", "Garen"])),
+                (Some(GroupHeader::Cost(3)), names(&["Ahri"])),
+                (Some(GroupHeader::Cost(5)), names(&["Sion"])),
+            ]
+        );
+    }
+
+    #[test]
+    fn champions_show_up_under_each_of_their_traits() {
+        let trait_header = |name: &str| Some(GroupHeader::Trait(name.to_string()));
+        assert_eq!(
+            layout(SortChampMethod::Trait, &[]),
+            [
+                (trait_header("Demacia"), names(&["Garen"])),
+                (trait_header("Juggernaut"), names(&["Garen", "Sion"])),
+                (trait_header("Sorcerer"), names(&["Ahri"])),
+            ]
+        );
+    }
+}
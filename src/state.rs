@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+
+use crate::tft_data::*;
+
+/// A champion together with its saved BIS items.
+#[derive(Debug, Default, Deserialize, Clone, Serialize)]
+pub struct ChampionState {
+    pub champ: Champion,
+    pub items: Vec<Item>,
+}
+
+/// How many of a component the player is holding in the ItemDeterminer.
+#[derive(Debug, Default, Deserialize, Clone, Serialize)]
+pub struct ComponentState {
+    pub component: Item,
+    pub count: usize,
+}
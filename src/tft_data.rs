@@ -1,7 +1,5 @@
 use std::{fmt::Display, path::PathBuf, sync::OnceLock};
 
-use directories::ProjectDirs;
-
 use iced::widget::image;
 
 use serde::{Deserialize, Serialize};
@@ -11,8 +9,6 @@ use crate::serde_help::*;
 
 const CDRAGON_URL: &str = "https://raw.communitydragon.org/latest/game/";
 
-static DIR: OnceLock<ProjectDirs> = OnceLock::new();
-static CACHE_DIR: OnceLock<PathBuf> = OnceLock::new();
 static DATA_DIR: OnceLock<PathBuf> = OnceLock::new();
 
 trait ImageHandleDefault {
@@ -21,20 +17,7 @@ trait ImageHandleDefault {
 
 impl ImageHandleDefault for image::Handle {
     fn default() -> Self {
-        image::Handle::from_path(
-            CACHE_DIR
-                .get_or_init(|| {
-                    std::fs::create_dir_all(
-                        DIR.get_or_init(|| {
-                            ProjectDirs::from("", "Sharif Haason", "TFT_Notebook").unwrap()
-                        })
-                        .cache_dir(),
-                    )
-                    .unwrap();
-                    DIR.get().unwrap().cache_dir().to_path_buf()
-                })
-                .join("tft_item_unknown.png"),
-        )
+        image::Handle::from_path(cache_dir().join("tft_item_unknown.png"))
     }
 }
 
@@ -66,19 +49,78 @@ impl Serialize for Handle {
 #[serde(rename_all = "camelCase")]
 pub struct Item {
     pub api_name: String,
-    associated_traits: Vec<String>,
+    pub associated_traits: Vec<String>,
     pub composition: Vec<String>,
     #[serde(deserialize_with = "deserialize_null_default")]
-    desc: String,
-    effects: Value,
+    pub desc: String,
+    pub effects: Value,
     from: Option<Value>, // always None
     #[serde(deserialize_with = "deserialize_image")]
     pub icon: Handle,
     id: Option<Value>, // always None
-    incompatible_traits: Vec<String>,
+    pub incompatible_traits: Vec<String>,
     #[serde(deserialize_with = "deserialize_null_default")]
     pub name: String,
-    unique: bool,
+    pub unique: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+pub enum ItemKind {
+    Component,
+    Completed,
+    Emblem,
+}
+
+impl Display for ItemKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ItemKind::Component => write!(f, "Component"),
+            ItemKind::Completed => write!(f, "Completed"),
+            ItemKind::Emblem => write!(f, "Emblem"),
+        }
+    }
+}
+
+impl Item {
+    pub fn kind(&self) -> ItemKind {
+        if self.composition.is_empty() {
+            ItemKind::Component
+        } else if !self.associated_traits.is_empty() {
+            // emblems are the only completed items that grant a trait
+            ItemKind::Emblem
+        } else {
+            ItemKind::Completed
+        }
+    }
+
+    /// Numeric effects of the item, sorted by name. Effects without a value are skipped.
+    pub fn effect_values(&self) -> Vec<(String, f64)> {
+        let mut values: Vec<(String, f64)> = self
+            .effects
+            .as_object()
+            .map(|effects| {
+                effects
+                    .iter()
+                    .filter_map(|(name, value)| Some((name.clone(), value.as_f64()?)))
+                    .collect()
+            })
+            .unwrap_or_default();
+        values.sort_by(|a, b| a.0.cmp(&b.0));
+        values
+    }
+
+    pub fn effect(&self, name: &str) -> Option<f64> {
+        self.effects.as_object().and_then(|effects| {
+            effects
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case(name))
+                .and_then(|(_, value)| value.as_f64())
+        })
+    }
+
+    pub fn rendered_description(&self) -> String {
+        render_description(&self.desc, |name| self.effect(name))
+    }
 }
 
 impl Display for Item {
@@ -87,6 +129,106 @@ impl Display for Item {
     }
 }
 
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize,
+)]
+pub enum AugmentTier {
+    #[default]
+    Silver,
+    Gold,
+    Prismatic,
+}
+
+impl AugmentTier {
+    pub const ALL: [AugmentTier; 3] = [
+        AugmentTier::Silver,
+        AugmentTier::Gold,
+        AugmentTier::Prismatic,
+    ];
+
+    /// CommunityDragon has no tier field, but augment icons end in `-I`, `-II` or `-III` and
+    /// tiered api names in `1`, `2` or `3`. Anything else is treated as silver.
+    fn guess(api_name: &str, icon_url: &str) -> Self {
+        let file_name = icon_url.rsplit('/').next().unwrap_or_default();
+        let stem = file_name.split('.').next().unwrap_or_default();
+        let numeral = stem
+            .rsplit_once(['-', '_'])
+            .map(|(_, suffix)| suffix.to_lowercase());
+        match numeral.as_deref() {
+            Some("iii") => return AugmentTier::Prismatic,
+            Some("ii") => return AugmentTier::Gold,
+            Some("i") => return AugmentTier::Silver,
+            _ => {}
+        }
+        match api_name.chars().last() {
+            Some('3') => AugmentTier::Prismatic,
+            Some('2') => AugmentTier::Gold,
+            _ => AugmentTier::Silver,
+        }
+    }
+}
+
+impl Display for AugmentTier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AugmentTier::Silver => write!(f, "Silver"),
+            AugmentTier::Gold => write!(f, "Gold"),
+            AugmentTier::Prismatic => write!(f, "Prismatic"),
+        }
+    }
+}
+
+/// An augment. CommunityDragon lists them with the items.
+#[derive(Debug, Default, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Augment {
+    pub api_name: String,
+    pub name: String,
+    pub desc: String,
+    pub effects: Value,
+    pub icon: Handle,
+    pub tier: AugmentTier,
+    pub associated_traits: Vec<String>,
+}
+
+impl Augment {
+    /// Whether an entry of CommunityDragon's `items` array is an augment.
+    pub fn is_augment(item: &Item) -> bool {
+        item.api_name.contains("Augment") && !item.name.is_empty()
+    }
+
+    pub fn from_item(item: Item) -> Self {
+        Self {
+            tier: AugmentTier::guess(&item.api_name, &item.icon.url),
+            api_name: item.api_name,
+            name: item.name,
+            desc: item.desc,
+            effects: item.effects,
+            icon: item.icon,
+            associated_traits: item.associated_traits,
+        }
+    }
+
+    pub fn effect(&self, name: &str) -> Option<f64> {
+        self.effects.as_object().and_then(|effects| {
+            effects
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case(name))
+                .and_then(|(_, value)| value.as_f64())
+        })
+    }
+
+    pub fn rendered_description(&self) -> String {
+        render_description(&self.desc, |name| self.effect(name))
+    }
+}
+
+impl Display for Augment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
 pub struct ItemsDisplay(pub Vec<Item>);
 
 impl Display for ItemsDisplay {
@@ -109,49 +251,80 @@ impl Display for ItemsDisplay {
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct Variable {
     #[serde(deserialize_with = "deserialize_null_default")]
-    name: String,
+    pub name: String,
     #[serde(deserialize_with = "deserialize_null_default")]
-    value: Vec<f64>,
+    pub value: Vec<f64>,
 }
 
 #[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Ability {
     #[serde(deserialize_with = "deserialize_null_default")]
-    desc: String,
+    pub desc: String,
     #[serde(deserialize_with = "deserialize_image")]
     pub icon: Handle,
     #[serde(deserialize_with = "deserialize_null_default")]
-    name: String,
-    variables: Vec<Variable>,
+    pub name: String,
+    pub variables: Vec<Variable>,
+}
+
+impl Ability {
+    /// Value of an ability variable at the given star level.
+    ///
+    /// CommunityDragon stores one value per star level, with index 0 unused.
+    pub fn variable(&self, name: &str, star: u8) -> Option<f64> {
+        self.variables
+            .iter()
+            .find(|variable| variable.name.eq_ignore_ascii_case(name))
+            .and_then(|variable| variable.value.get(star as usize).copied())
+    }
+
+    pub fn rendered_description(&self, star: u8) -> String {
+        render_description(&self.desc, |name| self.variable(name, star))
+    }
 }
 
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Stats {
-    armor: Option<f64>,
-    attack_speed: Option<f64>,
-    crit_chance: Option<f64>,
-    crit_multiplier: f64,
-    damage: Option<f64>,
-    hp: Option<f64>,
-    initial_mana: f64,
-    magic_resist: Option<f64>,
-    mana: f64,
-    range: f64,
+    pub armor: Option<f64>,
+    pub attack_speed: Option<f64>,
+    pub crit_chance: Option<f64>,
+    pub crit_multiplier: f64,
+    pub damage: Option<f64>,
+    pub hp: Option<f64>,
+    pub initial_mana: f64,
+    pub magic_resist: Option<f64>,
+    pub mana: f64,
+    pub range: f64,
+}
+
+/// Health and attack damage grow by 80% with every star level.
+const STAR_SCALING: f64 = 1.8;
+
+impl Stats {
+    /// Stats of a unit upgraded to the given star level.
+    pub fn at_star(&self, star: u8) -> Stats {
+        let multiplier = STAR_SCALING.powi(star.saturating_sub(1) as i32);
+        Stats {
+            damage: self.damage.map(|damage| damage * multiplier),
+            hp: self.hp.map(|hp| hp * multiplier),
+            ..self.clone()
+        }
+    }
 }
 
 #[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Champion {
-    ability: Ability,
+    pub ability: Ability,
     pub api_name: String,
     pub cost: u8,
     #[serde(deserialize_with = "deserialize_image")]
     pub square_icon: Handle,
     #[serde(deserialize_with = "deserialize_null_default")]
     pub name: String,
-    stats: Stats,
+    pub stats: Stats,
     pub traits: Vec<String>,
 }
 
@@ -160,3 +333,221 @@ impl Display for Champion {
         write!(f, "{}", self.name)
     }
 }
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraitEffect {
+    #[serde(deserialize_with = "deserialize_null_default")]
+    pub max_units: u32,
+    #[serde(deserialize_with = "deserialize_null_default")]
+    pub min_units: u32,
+    #[serde(deserialize_with = "deserialize_null_default")]
+    pub style: u32,
+    pub variables: Value,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Trait {
+    pub api_name: String,
+    #[serde(deserialize_with = "deserialize_null_default")]
+    pub desc: String,
+    pub effects: Vec<TraitEffect>,
+    #[serde(deserialize_with = "deserialize_image")]
+    pub icon: Handle,
+    #[serde(deserialize_with = "deserialize_null_default")]
+    pub name: String,
+}
+
+impl Display for Trait {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+/// Formats a number with at most two decimals, dropping them for whole numbers.
+pub fn format_number(value: f64) -> String {
+    let rounded = (value * 100.0).round() / 100.0;
+    if rounded.fract() == 0.0 {
+        format!("{}", rounded as i64)
+    } else {
+        format!("{}", rounded)
+    }
+}
+
+/// Turns a CommunityDragon description template into plain text.
+///
+/// `@Name@` and `@Name*100@` placeholders are filled in through `lookup` (unknown ones become
+/// `?`), `<br>` becomes a newline, and other markup tags and `%i:scaleAP%` icons are dropped.
+pub fn render_description(desc: &str, lookup: impl Fn(&str) -> Option<f64>) -> String {
+    let mut rendered = String::new();
+    let mut rest = desc;
+    while let Some(start) = rest.find(['@', '<', '%']) {
+        rendered.push_str(&rest[..start]);
+        let tail = &rest[start..];
+        let (open, close) = match tail.as_bytes()[0] {
+            b'<' => ('<', '>'),
+            b'@' => ('@', '@'),
+            _ if tail.starts_with("%i:") => ('%', '%'),
+            _ => {
+                // a plain percent sign
+                rendered.push('%');
+                rest = &tail[1..];
+                continue;
+            }
+        };
+        let Some(end) = tail[1..].find(close) else {
+            rendered.push_str(tail);
+            rest = "";
+            break;
+        };
+        let token = &tail[1..end + 1];
+        match open {
+            '@' => {
+                let (name, factor) = match token.split_once('*') {
+                    Some((name, factor)) => (name, factor.parse::<f64>().unwrap_or(1.0)),
+                    None => (token, 1.0),
+                };
+                match lookup(name) {
+                    Some(value) => rendered.push_str(&format_number(value * factor)),
+                    None => rendered.push('?'),
+                }
+            }
+            '<' if token.trim_end_matches('/').eq_ignore_ascii_case("br") => rendered.push('\n'),
+            _ => {}
+        }
+        rest = &tail[end + 2..];
+    }
+    rendered.push_str(rest);
+    rendered
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ability() -> Ability {
+        Ability {
+            desc: String::from(
+                "Deal <magicDamage>@ModifiedDamage@%i:scaleAP%</magicDamage> damage and \
+                 heal @HealPercent*100@% of it.<br>Lasts @Duration@ seconds.",
+            ),
+            variables: vec![
+                Variable {
+                    name: String::from("ModifiedDamage"),
+                    value: vec![0.0, 200.0, 300.0, 450.5],
+                },
+                Variable {
+                    name: String::from("HealPercent"),
+                    value: vec![0.0, 0.25, 0.333, 0.5],
+                },
+            ],
+            ..Ability::default()
+        }
+    }
+
+    #[test]
+    fn descriptions_use_the_values_of_the_star_level() {
+        assert_eq!(
+            ability().rendered_description(1),
+            "Deal 200 damage and heal 25% of it.\nLasts ? seconds."
+        );
+        assert_eq!(
+            ability().rendered_description(2),
+            "Deal 300 damage and heal 33.3% of it.\nLasts ? seconds."
+        );
+        assert_eq!(
+            ability().rendered_description(3),
+            "Deal 450.5 damage and heal 50% of it.\nLasts ? seconds."
+        );
+        // star levels past the data are unknown too
+        assert_eq!(
+            ability().rendered_description(4),
+            "Deal ? damage and heal ?% of it.\nLasts ? seconds."
+        );
+    }
+
+    #[test]
+    fn descriptions_keep_what_isnt_markup() {
+        let lookup = |name: &str| (name == "Gold").then_some(2.0);
+        assert_eq!(
+            render_description("Gain @Gold@ gold", lookup),
+            "Gain 2 gold"
+        );
+        assert_eq!(render_description("100% of @Gold", lookup), "100% of @Gold");
+        assert_eq!(
+            render_description("a <b>bold</b> move<br/>", lookup),
+            "a bold move\n"
+        );
+        assert_eq!(render_description("@gold*1.5@, @Gold*x@", lookup), "?, 2");
+        assert_eq!(render_description("", lookup), "");
+    }
+
+    #[test]
+    fn stars_scale_health_and_damage() {
+        let stats = Stats {
+            hp: Some(500.0),
+            damage: Some(50.0),
+            armor: Some(30.0),
+            mana: 60.0,
+            ..Stats::default()
+        };
+        assert_eq!(stats.at_star(1), stats);
+        assert_eq!(stats.at_star(0), stats);
+        let three = stats.at_star(3);
+        assert!((three.hp.unwrap() - 1620.0).abs() < 1e-9);
+        assert!((three.damage.unwrap() - 162.0).abs() < 1e-9);
+        assert_eq!(
+            Stats {
+                hp: None,
+                damage: None,
+                ..three
+            },
+            Stats {
+                hp: None,
+                damage: None,
+                ..stats.clone()
+            }
+        );
+        // missing values stay missing
+        assert_eq!(Stats::default().at_star(2), Stats::default());
+    }
+
+    #[test]
+    fn augment_tiers_are_guessed_from_the_icon_then_the_name() {
+        let icon =
+            |file_name: &str| format!("{CDRAGON_URL}assets/maps/tft/icons/augments/{file_name}");
+        let cases = [
+            (
+                "TFT9_Augment_Jeweled",
+                "hustler-iii.png",
+                AugmentTier::Prismatic,
+            ),
+            ("TFT9_Augment_Jeweled", "hustler_II.png", AugmentTier::Gold),
+            (
+                "TFT9_Augment_Jeweled3",
+                "hustler-i.png",
+                AugmentTier::Silver,
+            ),
+            (
+                "TFT9_Augment_CyberneticBulk2",
+                "cyberneticbulk.png",
+                AugmentTier::Gold,
+            ),
+            ("TFT9_Augment_CyberneticBulk3", "", AugmentTier::Prismatic),
+            ("TFT9_Augment_CyberneticBulk1", "", AugmentTier::Silver),
+            (
+                "TFT9_Augment_Shimmerscale",
+                "shimmerscale.png",
+                AugmentTier::Silver,
+            ),
+        ];
+        for (api_name, file_name, tier) in cases {
+            assert_eq!(
+                AugmentTier::guess(api_name, &icon(file_name)),
+                tier,
+                "{api_name} {file_name}"
+            );
+        }
+    }
+}
@@ -0,0 +1,388 @@
+use std::fmt::Display;
+
+use ::image as img;
+use img::{
+    imageops::{self, FilterType},
+    Rgba, RgbaImage,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{database::TftDatabase, serde_help::cached_image_path, tft_data::Champion};
+
+/// A rank of the augment tier list, best first. New champion tier lists get a row per rank.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+pub enum Rank {
+    S,
+    A,
+    B,
+    C,
+    D,
+}
+
+impl Rank {
+    pub const ALL: [Rank; 5] = [Rank::S, Rank::A, Rank::B, Rank::C, Rank::D];
+}
+
+impl Display for Rank {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Rank::S => write!(f, "S"),
+            Rank::A => write!(f, "A"),
+            Rank::B => write!(f, "B"),
+            Rank::C => write!(f, "C"),
+            Rank::D => write!(f, "D"),
+        }
+    }
+}
+
+/// Side of a champion icon in an exported image.
+const ICON_SIZE: u32 = 64;
+const LABEL_WIDTH: u32 = 96;
+/// Icons per line of an exported image, even if no row is this long.
+const MIN_COLUMNS: u32 = 8;
+const BACKGROUND: [u8; 4] = [32, 32, 36, 255];
+/// Background of the label of each row, repeating the last one for extra rows.
+const ROW_COLORS: [[u8; 4]; 6] = [
+    [255, 127, 127, 255],
+    [255, 191, 127, 255],
+    [255, 223, 127, 255],
+    [191, 255, 127, 255],
+    [127, 191, 255, 255],
+    [191, 191, 191, 255],
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TierRow {
+    pub label: String,
+    /// Champion api names, left to right.
+    pub champions: Vec<String>,
+}
+
+/// A named champion tier list. Champions in no row are unranked.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TierList {
+    pub name: String,
+    pub rows: Vec<TierRow>,
+}
+
+impl TierList {
+    /// An empty list with a row per `Rank`.
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            rows: Rank::ALL
+                .into_iter()
+                .map(|rank| TierRow {
+                    label: rank.to_string(),
+                    champions: vec![],
+                })
+                .collect(),
+        }
+    }
+
+    /// The row and position of a champion.
+    pub fn position(&self, champion: &str) -> Option<(usize, usize)> {
+        self.rows.iter().enumerate().find_map(|(row_index, row)| {
+            row.champions
+                .iter()
+                .position(|api_name| api_name == champion)
+                .map(|index| (row_index, index))
+        })
+    }
+
+    /// Takes a champion out of its row. Returns where it was.
+    pub fn remove(&mut self, champion: &str) -> Option<(usize, usize)> {
+        let (row, index) = self.position(champion)?;
+        self.rows[row].champions.remove(index);
+        Some((row, index))
+    }
+
+    /// Moves a champion to `row`, before the champion at `index` or last if there is none.
+    pub fn place(&mut self, champion: &str, row: usize, index: Option<usize>) {
+        if row >= self.rows.len() {
+            return;
+        }
+        let mut index = index.unwrap_or(usize::MAX);
+        if let Some((old_row, old_index)) = self.remove(champion) {
+            // the champions after it moved up by one
+            if old_row == row && old_index < index {
+                index -= 1;
+            }
+        }
+        let champions = &mut self.rows[row].champions;
+        champions.insert(index.min(champions.len()), champion.to_string());
+    }
+
+    /// Removes a row, leaving its champions unranked.
+    pub fn remove_row(&mut self, row: usize) {
+        if row < self.rows.len() {
+            self.rows.remove(row);
+        }
+    }
+
+    pub fn unranked<'a>(&self, champions: &'a [Champion]) -> Vec<&'a Champion> {
+        champions
+            .iter()
+            .filter(|champ| self.position(&champ.api_name).is_none())
+            .collect()
+    }
+
+    /// One line per row, `S: Ahri, Jinx`.
+    pub fn to_text(&self, db: &TftDatabase) -> String {
+        let mut text = format!("{}\n", self.name);
+        for row in self.rows.iter() {
+            let names: Vec<&str> = row
+                .champions
+                .iter()
+                .map(|api_name| {
+                    db.champion(api_name)
+                        .map_or(api_name.as_str(), |champ| &champ.name)
+                })
+                .collect();
+            text.push_str(&format!("{}: {}\n", row.label, names.join(", ")));
+        }
+        text
+    }
+
+    /// The list drawn with the cached champion icons. Icons that aren't cached are left grey.
+    pub fn to_image(&self, db: &TftDatabase) -> RgbaImage {
+        let columns = self
+            .rows
+            .iter()
+            .map(|row| row.champions.len() as u32)
+            .max()
+            .unwrap_or_default()
+            .max(MIN_COLUMNS);
+        let mut image = RgbaImage::from_pixel(
+            LABEL_WIDTH + columns * ICON_SIZE,
+            (self.rows.len().max(1) as u32) * ICON_SIZE,
+            Rgba(BACKGROUND),
+        );
+        for (row_index, row) in self.rows.iter().enumerate() {
+            let y = row_index as u32 * ICON_SIZE;
+            let color = ROW_COLORS[row_index.min(ROW_COLORS.len() - 1)];
+            fill(&mut image, 0, y, LABEL_WIDTH, ICON_SIZE, color);
+            draw_label(&mut image, &row.label, y);
+            for (index, api_name) in row.champions.iter().enumerate() {
+                let x = LABEL_WIDTH + index as u32 * ICON_SIZE;
+                let icon = db
+                    .champion(api_name)
+                    .and_then(|champ| img::open(cached_image_path(&champ.square_icon.url)).ok());
+                match icon {
+                    Some(icon) => {
+                        let icon = icon
+                            .resize_exact(ICON_SIZE, ICON_SIZE, FilterType::Triangle)
+                            .to_rgba8();
+                        imageops::overlay(&mut image, &icon, x as i64, y as i64);
+                    }
+                    None => fill(
+                        &mut image,
+                        x + 1,
+                        y + 1,
+                        ICON_SIZE - 2,
+                        ICON_SIZE - 2,
+                        [96, 96, 96, 255],
+                    ),
+                }
+            }
+        }
+        image
+    }
+}
+
+fn fill(image: &mut RgbaImage, x: u32, y: u32, width: u32, height: u32, color: [u8; 4]) {
+    for py in y..(y + height).min(image.height()) {
+        for px in x..(x + width).min(image.width()) {
+            image.put_pixel(px, py, Rgba(color));
+        }
+    }
+}
+
+/// Rows of a 3x5 pixel glyph, high bit on the left.
+fn glyph(c: char) -> [u8; 5] {
+    match c.to_ascii_uppercase() {
+        'A' => [2, 5, 7, 5, 5],
+        'B' => [6, 5, 6, 5, 6],
+        'C' => [3, 4, 4, 4, 3],
+        'D' => [6, 5, 5, 5, 6],
+        'E' => [7, 4, 6, 4, 7],
+        'F' => [7, 4, 6, 4, 4],
+        'G' => [3, 4, 5, 5, 3],
+        'H' => [5, 5, 7, 5, 5],
+        'I' => [7, 2, 2, 2, 7],
+        'J' => [1, 1, 1, 5, 2],
+        'K' => [5, 5, 6, 5, 5],
+        'L' => [4, 4, 4, 4, 7],
+        'M' => [5, 7, 7, 5, 5],
+        'N' => [6, 5, 5, 5, 5],
+        'O' => [2, 5, 5, 5, 2],
+        'P' => [6, 5, 6, 4, 4],
+        'Q' => [2, 5, 5, 6, 3],
+        'R' => [6, 5, 6, 5, 5],
+        'S' => [3, 4, 2, 1, 6],
+        'T' => [7, 2, 2, 2, 2],
+        'U' => [5, 5, 5, 5, 7],
+        'V' => [5, 5, 5, 5, 2],
+        'W' => [5, 5, 7, 7, 5],
+        'X' => [5, 5, 2, 5, 5],
+        'Y' => [5, 5, 2, 2, 2],
+        'Z' => [7, 1, 2, 4, 7],
+        '0' => [7, 5, 5, 5, 7],
+        '1' => [2, 6, 2, 2, 7],
+        '2' => [6, 1, 2, 4, 7],
+        '3' => [6, 1, 2, 1, 6],
+        '4' => [5, 5, 7, 1, 1],
+        '5' => [7, 4, 6, 1, 6],
+        '6' => [3, 4, 6, 5, 2],
+        '7' => [7, 1, 2, 2, 2],
+        '8' => [2, 5, 2, 5, 2],
+        '9' => [2, 5, 3, 1, 6],
+        '+' => [0, 2, 7, 2, 0],
+        '-' => [0, 0, 7, 0, 0],
+        ' ' => [0, 0, 0, 0, 0],
+        _ => [6, 1, 2, 0, 2],
+    }
+}
+
+/// Draws a row label in black, as large as fits the label column, centered in the row.
+fn draw_label(image: &mut RgbaImage, label: &str, row_y: u32) {
+    let chars = label.chars().count().max(1) as u32;
+    // a glyph is 3 pixels wide plus 1 of spacing
+    let scale = ((LABEL_WIDTH - 8) / (chars * 4)).clamp(1, 6);
+    let width = chars * 4 * scale - scale;
+    let x0 = (LABEL_WIDTH.saturating_sub(width)) / 2;
+    let y0 = row_y + (ICON_SIZE - 5 * scale) / 2;
+    for (char_index, c) in label.chars().enumerate() {
+        let char_x = x0 + char_index as u32 * 4 * scale;
+        if char_x + 3 * scale > LABEL_WIDTH {
+            // long labels are cut off rather than drawn over the icons
+            break;
+        }
+        for (line, bits) in glyph(c).into_iter().enumerate() {
+            for column in 0..3 {
+                if bits & (4 >> column) != 0 {
+                    fill(
+                        image,
+                        char_x + column * scale,
+                        y0 + line as u32 * scale,
+                        scale,
+                        scale,
+                        [0, 0, 0, 255],
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn list(rows: &[&[&str]]) -> TierList {
+        let mut list = TierList::new("Patch 9.1");
+        list.rows.truncate(rows.len());
+        for (row, champions) in list.rows.iter_mut().zip(rows) {
+            row.champions = champions.iter().map(|champ| champ.to_string()).collect();
+        }
+        list
+    }
+
+    #[test]
+    fn champions_are_placed_before_the_index() {
+        let mut tiers = list(&[&["Ahri", "Jinx", "Lux", "Zed"], &["Vi"]]);
+        // later in its own row: the champions after it move up first
+        tiers.place("Ahri", 0, Some(2));
+        assert_eq!(tiers, list(&[&["Jinx", "Ahri", "Lux", "Zed"], &["Vi"]]));
+        // earlier in its own row
+        tiers.place("Zed", 0, Some(0));
+        assert_eq!(tiers, list(&[&["Zed", "Jinx", "Ahri", "Lux"], &["Vi"]]));
+        // last in its own row
+        tiers.place("Zed", 0, None);
+        assert_eq!(tiers, list(&[&["Jinx", "Ahri", "Lux", "Zed"], &["Vi"]]));
+        // across rows, where nothing moves up
+        tiers.place("Jinx", 1, Some(1));
+        assert_eq!(tiers, list(&[&["Ahri", "Lux", "Zed"], &["Vi", "Jinx"]]));
+        tiers.place("Vi", 0, Some(1));
+        assert_eq!(tiers, list(&[&["Ahri", "Vi", "Lux", "Zed"], &["Jinx"]]));
+        // unranked champions and indices past the end
+        tiers.place("Sona", 1, Some(10));
+        assert_eq!(
+            tiers,
+            list(&[&["Ahri", "Vi", "Lux", "Zed"], &["Jinx", "Sona"]])
+        );
+        // rows that don't exist leave the champion where it was
+        tiers.place("Ahri", 2, None);
+        assert_eq!(tiers.position("Ahri"), Some((0, 0)));
+    }
+
+    #[test]
+    fn removed_rows_leave_their_champions_unranked() {
+        let mut tiers = list(&[&["Ahri"], &["Jinx", "Vi"], &["Zed"]]);
+        tiers.remove_row(1);
+        tiers.remove_row(5);
+        assert_eq!(tiers.rows.len(), 2);
+        assert_eq!(tiers.position("Zed"), Some((1, 0)));
+        let db = TftDatabase::with_names(&["Ahri", "Jinx", "Vi", "Zed"], &[]);
+        let unranked: Vec<&str> = tiers
+            .unranked(&db.champions)
+            .into_iter()
+            .map(|champ| champ.api_name.as_str())
+            .collect();
+        assert_eq!(unranked, ["Jinx", "Vi"]);
+    }
+
+    #[test]
+    fn text_has_a_line_per_row() {
+        let mut db = TftDatabase::with_names(&["TFT9_Ahri"], &[]);
+        db.champions[0].name = String::from("Ahri");
+        let tiers = list(&[&["TFT9_Ahri", "TFT9_Gone"], &[], &["TFT9_Ahri"]]);
+        assert_eq!(
+            tiers.to_text(&db),
+            "Patch 9.1\nS: Ahri, TFT9_Gone\nA: \nB: Ahri\n"
+        );
+    }
+
+    #[test]
+    fn images_have_a_line_of_icons_per_row() {
+        let db = TftDatabase::with_names(&["Ahri"], &[]);
+        let tiers = list(&[&["Ahri"], &[]]);
+        let image = tiers.to_image(&db);
+        assert_eq!(
+            image.dimensions(),
+            (LABEL_WIDTH + MIN_COLUMNS * ICON_SIZE, 2 * ICON_SIZE)
+        );
+        assert_eq!(image.get_pixel(1, 1), &Rgba(ROW_COLORS[0]));
+        assert_eq!(image.get_pixel(1, ICON_SIZE + 1), &Rgba(ROW_COLORS[1]));
+        // the icon isn't cached, so it's left grey, and the rest of the row is background
+        let center = ICON_SIZE / 2;
+        assert_eq!(
+            image.get_pixel(LABEL_WIDTH + center, center),
+            &Rgba([96, 96, 96, 255])
+        );
+        assert_eq!(
+            image.get_pixel(LABEL_WIDTH + ICON_SIZE + center, center),
+            &Rgba(BACKGROUND)
+        );
+        // the label is drawn in black
+        assert!((0..LABEL_WIDTH).any(|x| image.get_pixel(x, center) == &Rgba([0, 0, 0, 255])));
+
+        let long_row: Vec<String> = (0..MIN_COLUMNS + 2).map(|i| i.to_string()).collect();
+        let mut tiers = TierList::new("Long");
+        tiers.rows[4].champions = long_row;
+        assert_eq!(
+            tiers.to_image(&db).dimensions(),
+            (
+                LABEL_WIDTH + (MIN_COLUMNS + 2) * ICON_SIZE,
+                Rank::ALL.len() as u32 * ICON_SIZE
+            )
+        );
+        // extra rows repeat the last color
+        tiers.rows.extend(tiers.rows.clone());
+        let image = tiers.to_image(&db);
+        assert_eq!(
+            image.get_pixel(1, 9 * ICON_SIZE + 1),
+            &Rgba(ROW_COLORS[ROW_COLORS.len() - 1])
+        );
+    }
+}
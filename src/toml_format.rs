@@ -0,0 +1,371 @@
+use std::{collections::BTreeMap, fmt::Display, ops::Range};
+
+use serde::{Deserialize, Serialize};
+use toml::Spanned;
+
+use crate::{
+    comp::*,
+    database::TftDatabase,
+    search::closest_match,
+    tft_data::{Champion, Item},
+};
+
+/// Human-editable BIS lists and comps.
+///
+/// ```toml
+/// [bis]
+/// Ashe = ["Guinsoo's Rageblade", "Infinity Edge", "TFT_Item_LastWhisper"]
+///
+/// [[comps]]
+/// name = "Reroll Ashe"
+/// notes = "Roll at 6"
+///
+/// [[comps.units]]
+/// champion = "Ashe"
+/// star = 3
+/// items = ["Infinity Edge"]
+/// position = { row = 3, col = 0 }
+/// ```
+///
+/// Champions and items can be written either by display name or by api name.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(bound(deserialize = "N: Ord + Deserialize<'de>"))]
+struct Document<N: Ord> {
+    #[serde(default)]
+    bis: BTreeMap<N, Vec<N>>,
+    #[serde(default)]
+    comps: Vec<DocumentComp<N>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(bound(deserialize = "N: Deserialize<'de>"))]
+struct DocumentComp<N> {
+    name: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    notes: String,
+    #[serde(default)]
+    units: Vec<DocumentUnit<N>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(bound(deserialize = "N: Deserialize<'de>"))]
+struct DocumentUnit<N> {
+    champion: N,
+    #[serde(default = "one_star")]
+    star: Spanned<u8>,
+    #[serde(default)]
+    items: Vec<N>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    position: Option<Spanned<BoardPosition>>,
+}
+
+fn one_star() -> Spanned<u8> {
+    Spanned::new(0..0, 1)
+}
+
+/// A problem in an imported TOML document, with a 1-based location.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TomlError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl TomlError {
+    fn new(source: &str, span: Range<usize>, message: String) -> Self {
+        let before = &source[..span.start.min(source.len())];
+        let line = before.matches('\n').count() + 1;
+        let column = before.chars().rev().take_while(|c| *c != '\n').count() + 1;
+        Self {
+            line,
+            column,
+            message,
+        }
+    }
+}
+
+impl Display for TomlError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl std::error::Error for TomlError {}
+
+/// BIS lists and comps read from TOML, resolved to api names.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Imported {
+    pub bis: BTreeMap<String, Vec<String>>,
+    pub comps: Vec<Comp>,
+}
+
+/// Finds a name among `candidates`, by api name, name or English name of localized data.
+fn resolve<'a, T>(
+    source: &str,
+    db: &TftDatabase,
+    name: &Spanned<String>,
+    candidates: &'a [T],
+    kind: &str,
+    names: impl Fn(&'a T) -> (&'a str, &'a str),
+    errors: &mut Vec<TomlError>,
+) -> Option<String> {
+    let wanted = name.get_ref().trim();
+    let found = candidates.iter().map(&names).find(|(api_name, name)| {
+        api_name.eq_ignore_ascii_case(wanted)
+            || name.to_lowercase() == wanted.to_lowercase()
+            || db
+                .english_name(api_name)
+                .is_some_and(|english_name| english_name.eq_ignore_ascii_case(wanted))
+    });
+    if let Some((api_name, _)) = found {
+        return Some(api_name.to_string());
+    }
+    let suggestion = closest_match(wanted, candidates.iter().map(|c| names(c).1));
+    let message = match suggestion {
+        Some(suggestion) => format!("unknown {kind} \"{wanted}\", did you mean \"{suggestion}\"?"),
+        None => format!("unknown {kind} \"{wanted}\""),
+    };
+    errors.push(TomlError::new(source, name.span(), message));
+    None
+}
+
+fn champion_names(champ: &Champion) -> (&str, &str) {
+    (&champ.api_name, &champ.name)
+}
+
+fn item_names(item: &Item) -> (&str, &str) {
+    (&item.api_name, &item.name)
+}
+
+/// Parses a TOML document, checking every name against `db`. All problems are reported at once.
+pub fn import(source: &str, db: &TftDatabase) -> Result<Imported, Vec<TomlError>> {
+    let document: Document<Spanned<String>> = toml::from_str(source).map_err(|e| {
+        vec![TomlError::new(
+            source,
+            e.span().unwrap_or(0..0),
+            e.message().to_string(),
+        )]
+    })?;
+
+    let items: Vec<Item> = db
+        .items
+        .iter()
+        .chain(db.components.iter())
+        .cloned()
+        .collect();
+    let mut errors = vec![];
+    let resolve_items = |names: &[Spanned<String>], errors: &mut Vec<TomlError>| {
+        names
+            .iter()
+            .filter_map(|name| resolve(source, db, name, &items, "item", item_names, errors))
+            .collect::<Vec<_>>()
+    };
+
+    let mut imported = Imported::default();
+    for (champion, item_names) in &document.bis {
+        let items = resolve_items(item_names, &mut errors);
+        if let Some(champion) = resolve(
+            source,
+            db,
+            champion,
+            &db.champions,
+            "champion",
+            champion_names,
+            &mut errors,
+        ) {
+            imported.bis.insert(champion, items);
+        }
+    }
+
+    for comp in &document.comps {
+        let mut units = vec![];
+        for unit in &comp.units {
+            let items = resolve_items(&unit.items, &mut errors);
+            let champion = resolve(
+                source,
+                db,
+                &unit.champion,
+                &db.champions,
+                "champion",
+                champion_names,
+                &mut errors,
+            );
+            let star = *unit.star.get_ref();
+            if !(1..=3).contains(&star) {
+                errors.push(TomlError::new(
+                    source,
+                    unit.star.span(),
+                    format!("star level {star} has to be between 1 and 3"),
+                ));
+            }
+            let position = unit.position.as_ref().map(|position| {
+                let BoardPosition { row, col } = *position.get_ref();
+                if row >= BOARD_ROWS || col >= BOARD_COLS {
+                    errors.push(TomlError::new(
+                        source,
+                        position.span(),
+                        format!(
+                            "position is off the board, rows go up to {} and columns up to {}",
+                            BOARD_ROWS - 1,
+                            BOARD_COLS - 1
+                        ),
+                    ));
+                }
+                *position.get_ref()
+            });
+            if let Some(champion) = champion {
+                units.push(CompUnit {
+                    champion,
+                    star,
+                    items,
+                    position,
+                });
+            }
+        }
+        imported.comps.push(Comp {
+            name: comp.name.clone(),
+            units,
+            notes: comp.notes.clone(),
+        });
+    }
+
+    if errors.is_empty() {
+        Ok(imported)
+    } else {
+        errors.sort_by_key(|e| (e.line, e.column));
+        Err(errors)
+    }
+}
+
+/// Writes BIS lists and comps as TOML, using display names wherever `db` knows them.
+pub fn export(bis: &BTreeMap<String, Vec<String>>, comps: &[Comp], db: &TftDatabase) -> String {
+    let champion_name = |api_name: &String| {
+        db.champion(api_name)
+            .map_or(api_name.clone(), |champ| champ.name.clone())
+    };
+    let item_names = |api_names: &[String]| {
+        api_names
+            .iter()
+            .map(|api_name| {
+                db.item(api_name)
+                    .map_or(api_name.clone(), |item| item.name.clone())
+            })
+            .collect()
+    };
+    let document = Document {
+        bis: bis
+            .iter()
+            .map(|(champion, items)| (champion_name(champion), item_names(items)))
+            .collect(),
+        comps: comps
+            .iter()
+            .map(|comp| DocumentComp {
+                name: comp.name.clone(),
+                notes: comp.notes.clone(),
+                units: comp
+                    .units
+                    .iter()
+                    .map(|unit| DocumentUnit {
+                        champion: champion_name(&unit.champion),
+                        star: Spanned::new(0..0, unit.star),
+                        items: item_names(&unit.items),
+                        position: unit.position.map(|position| Spanned::new(0..0, position)),
+                    })
+                    .collect(),
+            })
+            .collect(),
+    };
+    toml::to_string_pretty(&document).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn db() -> TftDatabase {
+        let mut db = TftDatabase::with_names(
+            &["TFT9_Ashe", "TFT9_Ahri"],
+            &["TFT_Item_InfinityEdge", "TFT_Item_BlueBuff"],
+        );
+        db.champions[0].name = String::from("Ashe");
+        db.champions[1].name = String::from("Ahri");
+        db.items[0].name = String::from("Infinity Edge");
+        db.items[1].name = String::from("Blue Buff");
+        db
+    }
+
+    fn error_at(source: &str) -> (usize, usize, String) {
+        let errors = import(source, &db()).unwrap_err();
+        assert_eq!(errors.len(), 1, "{errors:?}");
+        let TomlError {
+            line,
+            column,
+            message,
+        } = errors[0].clone();
+        (line, column, message)
+    }
+
+    #[test]
+    fn syntax_errors_point_at_the_problem() {
+        let source = "[bis]\nAshe = [\"Infinity Edge\"\nAhri = []\n";
+        let (line, column, _) = error_at(source);
+        assert_eq!((line, column), (3, 1));
+    }
+
+    #[test]
+    fn unknown_names_point_at_the_name() {
+        let source = "[bis]\nAshe = [\"Infinity Edge\", \"Infinty Edg\"]\n";
+        let (line, column, message) = error_at(source);
+        assert_eq!((line, column), (2, 26));
+        assert_eq!(
+            message,
+            "unknown item \"Infinty Edg\", did you mean \"Infinity Edge\"?"
+        );
+    }
+
+    #[test]
+    fn every_problem_is_reported_in_order() {
+        let source = "\
+[[comps]]
+name = \"Reroll\"
+
+[[comps.units]]
+champion = \"Ashe\"
+star = 4
+
+[[comps.units]]
+champion = \"Zed\"
+position = { row = 5, col = 0 }
+";
+        let errors = import(source, &db()).unwrap_err();
+        let locations: Vec<(usize, usize)> = errors.iter().map(|e| (e.line, e.column)).collect();
+        assert_eq!(locations, [(6, 8), (9, 12), (10, 12)]);
+        assert_eq!(errors[1].message, "unknown champion \"Zed\"");
+    }
+
+    #[test]
+    fn exports_import_again() {
+        let db = db();
+        let bis = BTreeMap::from([(
+            String::from("TFT9_Ashe"),
+            vec![String::from("TFT_Item_InfinityEdge")],
+        )]);
+        let comps = vec![Comp {
+            name: String::from("Reroll Ashe"),
+            units: vec![CompUnit {
+                star: 3,
+                items: vec![String::from("TFT_Item_BlueBuff")],
+                position: Some(BoardPosition { row: 3, col: 0 }),
+                ..CompUnit::new(String::from("TFT9_Ashe"))
+            }],
+            notes: String::from("Roll at 6"),
+        }];
+        let source = export(&bis, &comps, &db);
+        assert!(source.contains("Infinity Edge"), "{source}");
+        assert_eq!(import(&source, &db), Ok(Imported { bis, comps }));
+    }
+}